@@ -0,0 +1,268 @@
+//! Pluggable memory for queue allocations.
+//!
+//! Every queue is a single allocation (header plus buffer, see
+//! [`QueuePtr`](crate::queue::QueuePtr)). By default it comes from the global
+//! allocator; the `channel_in` constructors take an [`Allocator`] instead, so a
+//! queue can live in an arena, a huge-page pool, or pinned DMA memory.
+//!
+//! The allocator is moved into the allocation itself, after the buffer, and
+//! read back out when the last handle drops. That keeps queue handles the same
+//! type whatever allocator built them, and means the memory always goes back to
+//! the allocator it came from.
+
+use core::ptr::NonNull;
+
+use crate::alloc::{self, Layout};
+
+/// A source of memory for queues.
+///
+/// This is a stable stand-in for the unstable `core::alloc::Allocator`, with
+/// only the two operations queues need.
+///
+/// # Safety
+///
+/// A block returned by [`allocate`](Allocator::allocate) must be valid for reads
+/// and writes of `layout.size()` bytes, aligned to `layout.align()`, and must
+/// stay valid until passed to [`deallocate`](Allocator::deallocate) on the same
+/// allocator. Moving the allocator value must not invalidate its blocks: the
+/// queue stores it inside the block it allocated.
+///
+/// # Examples
+///
+/// ```
+/// use core::{alloc::Layout, ptr::NonNull, sync::atomic::{AtomicUsize, Ordering}};
+/// use gil::{Allocator, Global};
+///
+/// /// Counts live bytes, delegating to the global allocator.
+/// struct Counting(&'static AtomicUsize);
+///
+/// unsafe impl Allocator for Counting {
+///     fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+///         self.0.fetch_add(layout.size(), Ordering::Relaxed);
+///         Global.allocate(layout)
+///     }
+///
+///     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+///         self.0.fetch_sub(layout.size(), Ordering::Relaxed);
+///         unsafe { Global.deallocate(ptr, layout) }
+///     }
+/// }
+/// ```
+pub unsafe trait Allocator {
+    /// Allocates a block for `layout`, or returns `None` if out of memory.
+    ///
+    /// `layout` never has zero size.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Frees a block.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have come from [`allocate`](Allocator::allocate) on this
+    /// allocator with the same `layout`, and not been freed since.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The global allocator, used by every `channel` constructor without an `_in`
+/// suffix.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+unsafe impl Allocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // SAFETY: callers never pass a zero-sized layout.
+        NonNull::new(unsafe { alloc::alloc(layout) })
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { alloc::dealloc(ptr.as_ptr(), layout) }
+    }
+}
+
+unsafe impl<A: Allocator + ?Sized> Allocator for &A {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        (**self).allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { (**self).deallocate(ptr, layout) }
+    }
+}
+
+/// How to free a queue allocation: its layout, where its allocator sits in it,
+/// and the allocator's monomorphised free routine. Lives in the queue header.
+#[derive(Clone, Copy)]
+pub(crate) struct Release {
+    layout: Layout,
+    allocator_offset: usize,
+    release: unsafe fn(NonNull<u8>, Layout, usize),
+}
+
+impl Release {
+    /// Extends `layout` with room for an `A`, allocates the whole block from
+    /// `allocator` and moves `allocator` into it.
+    ///
+    /// Returns the block and the record that frees it.
+    pub(crate) fn allocate<A: Allocator>(layout: Layout, allocator: A) -> (NonNull<u8>, Self) {
+        let (layout, allocator_offset) = layout.extend(Layout::new::<A>()).unwrap();
+        let layout = layout.pad_to_align();
+
+        let Some(block) = allocator.allocate(layout) else {
+            alloc::handle_alloc_error(layout);
+        };
+        // SAFETY: in bounds and aligned for `A` by construction of the layout.
+        unsafe {
+            block
+                .byte_add(allocator_offset)
+                .cast::<A>()
+                .write(allocator)
+        };
+
+        let release = Self {
+            layout,
+            allocator_offset,
+            release: release::<A>,
+        };
+        (block, release)
+    }
+
+    /// A record that frees a block of `layout` with `release`, for memory that
+    /// did not come from an [`Allocator`]. `release` gets `0` as the offset.
+    #[cfg(all(feature = "mirror", target_os = "linux", not(feature = "loom")))]
+    pub(crate) fn custom(layout: Layout, release: unsafe fn(NonNull<u8>, Layout, usize)) -> Self {
+        Self {
+            layout,
+            allocator_offset: 0,
+            release,
+        }
+    }
+
+    /// Frees `block` through the allocator stored in it.
+    ///
+    /// # Safety
+    ///
+    /// `block` must be the block returned alongside `self` by
+    /// [`allocate`](Self::allocate), with nothing in it still in use. `self`
+    /// must not live inside `block`; copy it out first.
+    pub(crate) unsafe fn release(self, block: NonNull<u8>) {
+        unsafe { (self.release)(block, self.layout, self.allocator_offset) }
+    }
+}
+
+unsafe fn release<A: Allocator>(block: NonNull<u8>, layout: Layout, allocator_offset: usize) {
+    // move the allocator out before handing back the memory it lives in
+    let allocator = unsafe { block.byte_add(allocator_offset).cast::<A>().read() };
+    unsafe { allocator.deallocate(block, layout) };
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use core::num::NonZeroUsize;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+    use crate::Box;
+
+    /// Counts live blocks and bytes, delegating to the global allocator.
+    #[derive(Clone, Default)]
+    struct Counting {
+        blocks: Arc<AtomicUsize>,
+        bytes: Arc<AtomicUsize>,
+    }
+
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            self.blocks.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(layout.size(), Ordering::Relaxed);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.blocks.fetch_sub(1, Ordering::Relaxed);
+            self.bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    impl Counting {
+        fn blocks(&self) -> usize {
+            self.blocks.load(Ordering::Relaxed)
+        }
+
+        fn bytes(&self) -> usize {
+            self.bytes.load(Ordering::Relaxed)
+        }
+    }
+
+    #[test]
+    fn spsc_frees_through_allocator() {
+        let alloc = Counting::default();
+        let (mut tx, mut rx) =
+            crate::spsc::channel_in::<Box<usize>, _>(NonZeroUsize::new(8).unwrap(), alloc.clone());
+        assert_eq!(alloc.blocks(), 1);
+        assert!(alloc.bytes() >= 8 * size_of::<Box<usize>>());
+
+        tx.send(Box::new(1));
+        tx.send(Box::new(2));
+        assert_eq!(*rx.recv(), 1);
+
+        drop(tx);
+        assert_eq!(alloc.blocks(), 1);
+        // the in-flight item is dropped before the block goes back
+        drop(rx);
+        assert_eq!(alloc.blocks(), 0);
+        assert_eq!(alloc.bytes(), 0);
+    }
+
+    #[test]
+    fn mpsc_frees_after_last_clone() {
+        let alloc = Counting::default();
+        let (tx, mut rx) =
+            crate::mpsc::channel_in::<usize, _>(NonZeroUsize::new(4).unwrap(), alloc.clone());
+        let mut tx2 = tx.clone();
+
+        tx2.send(7);
+        assert_eq!(rx.recv(), 7);
+
+        drop(tx);
+        drop(rx);
+        assert_eq!(alloc.blocks(), 1);
+        drop(tx2);
+        assert_eq!(alloc.blocks(), 0);
+    }
+
+    #[test]
+    fn sharded_allocates_per_shard() {
+        let alloc = Counting::default();
+        let (mut tx, mut rx) = crate::mpmc::sharded::channel_in::<usize, _>(
+            NonZeroUsize::new(4).unwrap(),
+            NonZeroUsize::new(16).unwrap(),
+            alloc.clone(),
+        );
+        assert_eq!(alloc.blocks(), 4);
+
+        tx.send(3);
+        assert_eq!(rx.recv(), 3);
+
+        drop(tx);
+        drop(rx);
+        assert_eq!(alloc.blocks(), 0);
+        assert_eq!(alloc.bytes(), 0);
+    }
+
+    #[test]
+    fn borrowed_allocator() {
+        static ALLOC: Global = Global;
+        let (mut tx, mut rx) =
+            crate::spsc::channel_in::<usize, _>(NonZeroUsize::new(2).unwrap(), &ALLOC);
+        tx.send(1);
+        assert_eq!(rx.recv(), 1);
+    }
+}
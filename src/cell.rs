@@ -1,6 +1,10 @@
 //! The Vyukov queue slot and its shared teardown/initialisation policies.
 
-use core::{cell::UnsafeCell, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+use core::{
+    cell::UnsafeCell, cmp::Ordering as Cmp, marker::PhantomData, mem::MaybeUninit, ptr::NonNull,
+};
+
+use alloc_crate::vec::Vec;
 
 use crate::{
     atomic::{AtomicUsize, Ordering},
@@ -12,7 +16,7 @@ use crate::{
 /// The epoch is the per-cell state machine the mpsc/mpmc/spmc cores synchronise
 /// on. `data` is interior-mutable so an endpoint can write the payload through a
 /// shared reference once it has claimed the cell via the epoch protocol.
-#[repr(align(64))]
+#[repr(C, align(64))]
 #[cfg_attr(all(target_arch = "aarch64", target_os = "macos"), repr(align(128)))]
 pub(crate) struct Cell<T> {
     epoch: AtomicUsize,
@@ -43,6 +47,27 @@ impl<T> Cell<T> {
         unsafe { (*self.data.get()).write(value) };
     }
 
+    /// Lets `f` build the payload in place. Same contract as [`set`](Self::set).
+    ///
+    /// # Panics
+    ///
+    /// If `f` panics or returns the wrong slot (see
+    /// [`construct`](crate::emplace::construct)); the payload is then
+    /// uninitialised.
+    #[inline(always)]
+    pub(crate) fn emplace(&self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        // SAFETY: the caller claimed the cell, so no one else touches the payload.
+        crate::emplace::construct(unsafe { &mut *self.data.get() }, f);
+    }
+
+    /// Pointer to the payload, for endpoints that work on it in place.
+    ///
+    /// Dereferencing it carries the same contract as [`get`](Self::get).
+    #[inline(always)]
+    pub(crate) fn data_ptr(&self) -> *mut T {
+        unsafe { (*self.data.get()).as_mut_ptr() }
+    }
+
     /// Drops the value in place.
     ///
     /// # Safety
@@ -55,6 +80,298 @@ impl<T> Cell<T> {
     }
 }
 
+/// Aborts if dropped during unwinding; [`disarm`](Self::disarm) it once past
+/// the critical section.
+///
+/// The mpmc/spmc consumers claim cells with a CAS on the head before reading
+/// them. A claim cannot be rolled back, so a panic while handing the values
+/// out would leave the cells unreleased and the producers waiting at them a
+/// lap later. Panicking inside `Drop` while already unwinding aborts instead.
+pub(crate) struct AbortOnUnwind(pub(crate) &'static str);
+
+impl AbortOnUnwind {
+    #[inline(always)]
+    pub(crate) fn disarm(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        panic!("{}", self.0);
+    }
+}
+
+/// A Vyukov producer's view of its queue: the shared tail to claim cells
+/// from, and the cells to fill or skip. Implemented by the heap and
+/// shared-memory queues alike.
+pub(crate) trait Cells {
+    type Item;
+
+    fn tail(&self) -> &AtomicUsize;
+
+    fn cell_at(&self, ticket: usize) -> &Cell<Self::Item>;
+
+    fn capacity(&self) -> usize;
+
+    /// Called once cells have been skipped, for queues whose consumers may be
+    /// asleep waiting on them.
+    #[inline(always)]
+    fn skipped(&self) {}
+}
+
+/// Claims the cell at the shared tail for this producer, advancing
+/// `local_tail` past it. Returns `None` if the queue is full.
+#[inline(always)]
+pub(crate) fn claim<'a, Q: Cells>(
+    queue: &'a Q,
+    local_tail: &mut usize,
+    retried: impl FnMut(),
+) -> Option<&'a Cell<Q::Item>> {
+    let (start, n) = claim_range(queue, local_tail, 1, retried);
+    (n == 1).then(|| queue.cell_at(start))
+}
+
+/// Claims up to `max` consecutive free cells at the shared tail with a single
+/// compare-exchange, advancing `local_tail` past them. Returns the first
+/// claimed ticket and the number claimed, zero if the queue is full.
+/// `retried` runs each time another producer wins the compare-exchange.
+///
+/// A free cell's epoch equals its ticket, and only the producer that claims
+/// the ticket changes it, so cells seen free before the compare-exchange are
+/// still free once it succeeds.
+pub(crate) fn claim_range<Q: Cells>(
+    queue: &Q,
+    local_tail: &mut usize,
+    max: usize,
+    mut retried: impl FnMut(),
+) -> (usize, usize) {
+    let mut backoff = crate::ExponentialBackoff::new(6, 10);
+
+    loop {
+        let start = *local_tail;
+        let epoch = queue.cell_at(start).epoch().load(Ordering::Acquire);
+
+        match epoch.cmp(&start) {
+            // consumer hasn't read the value
+            Cmp::Less => return (start, 0),
+
+            // consumer has read the value, cell is free; extend over the free
+            // cells after it
+            Cmp::Equal => {
+                let max = max.min(queue.capacity());
+                let mut n = 1;
+                while n < max {
+                    let ticket = start.wrapping_add(n);
+                    if queue.cell_at(ticket).epoch().load(Ordering::Acquire) != ticket {
+                        break;
+                    }
+                    n += 1;
+                }
+
+                let next = start.wrapping_add(n);
+                match queue.tail().compare_exchange_weak(
+                    start,
+                    next,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        *local_tail = next;
+                        return (start, n);
+                    }
+                    // we weren't fast enough, some other producer claimed
+                    // these cells already, probably
+                    Err(cur_tail) => {
+                        retried();
+                        *local_tail = cur_tail;
+                    }
+                }
+            }
+
+            // some other producer has written to this cell before us
+            Cmp::Greater => *local_tail = queue.tail().load(Ordering::Relaxed),
+        };
+
+        backoff.backoff();
+    }
+}
+
+/// Cells a producer has claimed but not yet filled, in ticket order.
+///
+/// A claim on the shared tail cannot be rolled back, and the consumer waits at
+/// each claimed cell until its producer publishes it. Whatever is still
+/// unfilled when this drops, whether on unwind or because the producer ran
+/// out of items, is published as skipped instead: epoch `ticket + capacity`,
+/// which frees the cell for the next lap and which the consumer, expecting
+/// `ticket + 1`, steps over without reading. That needs a capacity of at
+/// least 2.
+pub(crate) struct Unfilled<'a, Q: Cells> {
+    queue: &'a Q,
+    next: usize,
+    end: usize,
+}
+
+impl<'a, Q: Cells> Unfilled<'a, Q> {
+    #[inline(always)]
+    pub(crate) fn new(queue: &'a Q, start: usize, n: usize) -> Self {
+        debug_assert!(queue.capacity() >= 2);
+        Self {
+            queue,
+            next: start,
+            end: start.wrapping_add(n),
+        }
+    }
+
+    /// Number of claimed cells not yet filled.
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.end.wrapping_sub(self.next)
+    }
+
+    /// Publishes `value` in the next claimed cell.
+    #[inline(always)]
+    pub(crate) fn fill(&mut self, value: Q::Item) {
+        debug_assert!(self.len() > 0);
+        let cell = self.queue.cell_at(self.next);
+        cell.set(value);
+        self.publish(cell);
+    }
+
+    /// Lets `f` build the item in the next claimed cell, then publishes it.
+    /// If `f` panics, the cell is skipped when this drops.
+    #[inline(always)]
+    pub(crate) fn fill_with(&mut self, f: impl FnOnce(&mut MaybeUninit<Q::Item>) -> &mut Q::Item) {
+        debug_assert!(self.len() > 0);
+        let cell = self.queue.cell_at(self.next);
+        cell.emplace(f);
+        self.publish(cell);
+    }
+
+    #[inline(always)]
+    fn publish(&mut self, cell: &Cell<Q::Item>) {
+        self.next = self.next.wrapping_add(1);
+        cell.epoch().store(self.next, Ordering::Release);
+    }
+}
+
+impl<Q: Cells> Drop for Unfilled<'_, Q> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.len() == 0 {
+            return;
+        }
+        while self.next != self.end {
+            let ticket = self.next;
+            self.queue.cell_at(ticket).epoch().store(
+                ticket.wrapping_add(self.queue.capacity()),
+                Ordering::Release,
+            );
+            self.next = ticket.wrapping_add(1);
+        }
+        self.queue.skipped();
+    }
+}
+
+/// Values a Vyukov receiver has claimed but not yet handed out.
+///
+/// Cells interleave epochs with payloads, so the ring itself can't back a
+/// `&[T]`. Batch reads move a run of claimed values in here and lend the slice
+/// from this buffer instead. `buf[head..]` is initialised.
+pub(crate) struct Staged<T> {
+    buf: Vec<MaybeUninit<T>>,
+    head: usize,
+}
+
+impl<T> Staged<T> {
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.head == self.buf.len()
+    }
+
+    #[inline]
+    pub(crate) fn as_slice(&self) -> &[T] {
+        let live = &self.buf[self.head..];
+        // SAFETY: everything from `head` on is initialised.
+        unsafe { core::slice::from_raw_parts(live.as_ptr().cast(), live.len()) }
+    }
+
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let slot = self.buf.get(self.head)?;
+        self.head += 1;
+        // SAFETY: the slot was initialised and is now behind `head`.
+        Some(unsafe { slot.assume_init_read() })
+    }
+
+    /// Forgets the first `n` values without dropping them; the caller has
+    /// taken them through [`as_slice`](Self::as_slice).
+    #[inline]
+    pub(crate) fn advance(&mut self, n: usize) {
+        debug_assert!(n <= self.buf.len() - self.head);
+        self.head += n;
+    }
+
+    /// Moves up to `max` values into `dst`.
+    pub(crate) fn drain_into(&mut self, dst: &mut Vec<T>, max: usize) -> usize {
+        let n = max.min(self.buf.len() - self.head);
+        dst.reserve(n);
+        // SAFETY: the source values are initialised and skipped below, and
+        // `dst` has room for `n` more.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                self.buf.as_ptr().add(self.head).cast(),
+                dst.as_mut_ptr().add(dst.len()),
+                n,
+            );
+            dst.set_len(dst.len() + n);
+        }
+        self.head += n;
+        n
+    }
+
+    /// Empties the buffer for a refill. Must only be called once every value
+    /// has been handed out.
+    #[inline]
+    pub(crate) fn reset(&mut self) {
+        debug_assert!(self.is_empty());
+        self.buf.clear();
+        self.head = 0;
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, value: T) {
+        self.buf.push(MaybeUninit::new(value));
+    }
+}
+
+impl<T> Default for Staged<T> {
+    fn default() -> Self {
+        Self {
+            buf: Vec::new(),
+            head: 0,
+        }
+    }
+}
+
+/// A clone starts with nothing staged; the values belong to the original.
+impl<T> Clone for Staged<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<T> Drop for Staged<T> {
+    fn drop(&mut self) {
+        if core::mem::needs_drop::<T>() {
+            for slot in &mut self.buf[self.head..] {
+                // SAFETY: initialised and never read again.
+                unsafe { slot.assume_init_drop() };
+            }
+        }
+    }
+}
+
 /// Sets each cell's epoch to its index — Vyukov's initial "free, awaiting the
 /// first producer lap" state. Shared by the mpsc/mpmc/spmc constructors.
 pub(crate) struct CellInit<T> {
@@ -65,7 +382,9 @@ impl<T> crate::Initializer for CellInit<T> {
     type Item = Cell<T>;
 
     fn initialize(idx: usize, cell: &mut Self::Item) {
-        cell.epoch().store(idx, Ordering::Relaxed);
+        // the buffer is fresh from the allocator, so construct the atomic rather
+        // than storing into it (loom tracks atomics by construction)
+        unsafe { core::ptr::write(&mut cell.epoch, AtomicUsize::new(idx)) };
     }
 }
 
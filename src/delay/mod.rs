@@ -0,0 +1,224 @@
+//! Delay queue: a multi-producer single-consumer channel with timed delivery.
+//!
+//! Every item carries a deadline. [`Sender::send_at`] and [`Sender::send_after`]
+//! enqueue it immediately, but [`Receiver::recv`] only hands it out once its
+//! deadline has passed, earliest deadline first. Items with equal deadlines come
+//! out in the order the receiver saw them. This suits retry schedules, rate
+//! limiting and timeouts, where work must wait rather than be dropped.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use core::num::NonZeroUsize;
+//! use gil::delay::channel;
+//!
+//! let (mut tx, mut rx) = channel::<&str>(NonZeroUsize::new(64).unwrap());
+//!
+//! let start = Instant::now();
+//! tx.send_after("second attempt", Duration::from_millis(20));
+//! tx.send_after("first attempt", Duration::from_millis(10));
+//!
+//! assert_eq!(rx.recv(), "first attempt");
+//! assert_eq!(rx.recv(), "second attempt");
+//! assert!(start.elapsed() >= Duration::from_millis(20));
+//! ```
+//!
+//! # Performance
+//!
+//! Senders push into a plain [MPSC queue](crate::mpsc), so sending costs the
+//! same as on [`mpsc::channel`](crate::mpsc::channel) plus one fence. The
+//! receiver moves items into a binary heap as it sees them, so receiving is
+//! `O(log n)` in the number of pending items.
+//!
+//! While waiting, the receiver parks on a futex with a timeout at the earliest
+//! pending deadline rather than spinning. A sender only wakes it when the new
+//! item is due sooner than that. Senders never take a lock. The timed wait is a
+//! real futex wait on Linux only; other targets recheck at least every
+//! millisecond while an item is pending.
+//!
+//! # Gotchas
+//!
+//! - **Capacity Bounds the Inbox Only:** `capacity` limits items the receiver has
+//!   not pulled in yet. Pulled-in items wait in the receiver's heap, which grows
+//!   without bound. Every `recv` and `try_recv` pulls in the whole inbox, so
+//!   senders never block on a receiver that is busy waiting out a deadline, but
+//!   the number of items not yet due is only bounded by how fast they are
+//!   sent. Rate-limit sends upstream if that matters.
+//! - **Timer Resolution:** Items are delivered no earlier than their deadline,
+//!   but may be late by the OS timer resolution and scheduling latency.
+//! - **Std Only:** Deadlines and parking need `std`.
+
+use core::num::NonZeroUsize;
+
+pub use self::{receiver::Receiver, sender::Sender};
+
+mod queue;
+mod receiver;
+mod sender;
+
+/// Creates a new delay queue.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Arguments
+///
+/// * `capacity` - The capacity of the inbox between senders and the receiver,
+///   rounded up to the next power of two.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::delay::channel;
+///
+/// let (tx, rx) = channel::<usize>(NonZeroUsize::new(1024).unwrap());
+/// ```
+pub fn channel<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = crate::mpsc::channel(capacity);
+    let shared = crate::Arc::new(queue::Shared::new());
+
+    (Sender::new(tx, shared.clone()), Receiver::new(rx, shared))
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+
+    #[test]
+    fn deadline_order() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+        let now = Instant::now();
+        for i in [3, 1, 4, 0, 2] {
+            tx.send_at(i, now + Duration::from_millis(i as u64));
+        }
+
+        for i in 0..5 {
+            assert_eq!(rx.recv(), i);
+        }
+        assert!(now.elapsed() >= Duration::from_millis(4));
+    }
+
+    #[test]
+    fn equal_deadlines_are_fifo() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+        let deadline = Instant::now();
+        for i in 0..10 {
+            tx.send_at(i, deadline);
+        }
+        for i in 0..10 {
+            assert_eq!(rx.try_recv(), Some(i));
+        }
+    }
+
+    #[test]
+    fn not_due_yet() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+        tx.send_after(1, Duration::from_secs(60));
+        tx.send_after(0, Duration::ZERO);
+
+        assert_eq!(rx.try_recv(), Some(0));
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn earlier_item_wakes_receiver() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+        tx.send_after(1, Duration::from_secs(60));
+
+        let mut tx2 = tx.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx2.send_after(0, Duration::from_millis(10));
+        });
+
+        let start = Instant::now();
+        assert_eq!(rx.recv(), 0);
+        assert!(start.elapsed() < Duration::from_secs(30));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn wakes_from_empty() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            tx.send_after(7, Duration::ZERO);
+        });
+
+        assert_eq!(rx.recv(), 7);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn concurrent_senders() {
+        const SENDERS: usize = 4;
+        const ITER: usize = 1_000;
+
+        let (tx, mut rx) = channel::<Instant>(NonZeroUsize::new(64).unwrap());
+
+        thread::scope(|scope| {
+            for _ in 0..SENDERS {
+                let mut tx = tx.clone();
+                scope.spawn(move || {
+                    for i in 0..ITER {
+                        let deadline = Instant::now() + Duration::from_micros((i % 50) as u64);
+                        tx.send_at(deadline, deadline);
+                    }
+                });
+            }
+
+            for _ in 0..SENDERS * ITER {
+                let deadline = rx.recv();
+                assert!(Instant::now() >= deadline);
+            }
+        });
+    }
+
+    #[test]
+    fn drops_pending() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        {
+            let (mut tx, mut rx) = channel::<DropCounter>(NonZeroUsize::new(4).unwrap());
+            for _ in 0..3 {
+                tx.send_after(DropCounter(dropped.clone()), Duration::from_secs(60));
+            }
+            // pull them into the heap, then leave one in the inbox; cloning
+            // must not need `DropCounter: Clone`
+            assert!(rx.try_recv().is_none());
+            tx.clone()
+                .send_after(DropCounter(dropped.clone()), Duration::from_secs(60));
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn iter_drains_pending_after_disconnect() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+        let start = Instant::now();
+        tx.send_after(1, Duration::from_millis(20));
+        tx.send_after(0, Duration::ZERO);
+        drop(tx);
+        assert!(rx.is_disconnected());
+
+        assert!(rx.iter().eq([0, 1]));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
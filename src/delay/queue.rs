@@ -0,0 +1,153 @@
+//! State shared between the senders and the receiver of a delay queue.
+//!
+//! Items travel through a plain [MPSC queue](crate::mpsc) and are sorted by the
+//! receiver. The only other shared state is the receiver's parking slot (the
+//! deadline it is parked until, and the futex word it parks on) and the count
+//! of live senders.
+//!
+//! Parking follows the announce → recheck → sleep / publish → wake protocol of
+//! the [futex](crate::futex) module. The park deadline is stored before the
+//! announce and loaded before the wake, so the futex fences order it too, and a
+//! sender only wakes the receiver when its item is due before the receiver
+//! would wake anyway.
+
+use core::cmp::Ordering as CmpOrdering;
+use std::time::Instant;
+
+use crate::{
+    atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering, fence},
+    futex::{Futex, RECEIVER_WAITING},
+};
+
+/// The receiver is not parked.
+const NOT_PARKED: u64 = 0;
+/// The receiver is parked with nothing pending, until a sender wakes it.
+const PARKED_FOREVER: u64 = u64::MAX;
+
+pub(crate) struct Shared {
+    /// Reference point for encoding deadlines into `parked_until`.
+    epoch: Instant,
+    /// [`NOT_PARKED`], [`PARKED_FOREVER`], or the encoded park deadline.
+    parked_until: AtomicU64,
+    futex: AtomicU32,
+    /// Live [`Sender`](super::Sender)s.
+    senders: AtomicUsize,
+}
+
+impl Shared {
+    pub(crate) fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            parked_until: AtomicU64::new(NOT_PARKED),
+            futex: AtomicU32::new(crate::futex::FREE),
+            senders: AtomicUsize::new(0),
+        }
+    }
+
+    /// Encodes `deadline` as nanoseconds past `epoch`, offset to stay clear of
+    /// the sentinels. Comparisons between encodings match the deadlines'.
+    fn encode(&self, deadline: Instant) -> u64 {
+        let nanos = deadline.saturating_duration_since(self.epoch).as_nanos();
+        u64::try_from(nanos)
+            .unwrap_or(u64::MAX)
+            .clamp(1, PARKED_FOREVER - 1)
+    }
+
+    #[inline(always)]
+    fn futex(&self) -> Futex {
+        Futex::new(&self.futex)
+    }
+
+    /// Announces that the receiver is about to park until `deadline` (receiver
+    /// step 1). The caller must recheck the inbox before sleeping.
+    pub(crate) fn announce(&self, deadline: Option<Instant>) {
+        let encoded = deadline.map_or(PARKED_FOREVER, |d| self.encode(d));
+        self.parked_until.store(encoded, Ordering::Relaxed);
+        // Only the receiver waits on this word, so the announce cannot fail.
+        // Its fence also orders the deadline store before the recheck.
+        self.futex().announce(RECEIVER_WAITING);
+    }
+
+    /// Parks until `deadline`, a wake, or a spurious wakeup (receiver step 3).
+    pub(crate) fn sleep(&self, deadline: Option<Instant>) {
+        match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if deadline > now {
+                    self.futex().sleep_timeout(RECEIVER_WAITING, deadline - now);
+                }
+            }
+            None => self.futex().sleep(RECEIVER_WAITING),
+        }
+        self.parked_until.store(NOT_PARKED, Ordering::Relaxed);
+    }
+
+    /// Withdraws an announce without sleeping.
+    pub(crate) fn cancel(&self) {
+        self.parked_until.store(NOT_PARKED, Ordering::Relaxed);
+    }
+
+    /// Wakes the receiver if it is parked past `deadline`. Call after pushing
+    /// the item into the inbox.
+    pub(crate) fn wake(&self, deadline: Instant) {
+        // Order the inbox push before the parked-until load. Pairs with the
+        // fence in `announce`.
+        fence(Ordering::SeqCst);
+        let parked_until = self.parked_until.load(Ordering::Relaxed);
+        if parked_until != NOT_PARKED && self.encode(deadline) < parked_until {
+            self.futex().wake();
+        }
+    }
+
+    /// Counts one more sender.
+    pub(crate) fn attach_sender(&self) {
+        self.senders.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Uncounts a sender; the last one wakes the receiver if it is parked, so
+    /// it sees the disconnection. Call after the sender's last push.
+    pub(crate) fn detach_sender(&self) {
+        if self.senders.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        // Order the count store before the parked-until load. Pairs with the
+        // fence in `announce`.
+        fence(Ordering::SeqCst);
+        if self.parked_until.load(Ordering::Relaxed) != NOT_PARKED {
+            self.futex().wake();
+        }
+    }
+
+    /// Whether every sender has been dropped.
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.senders.load(Ordering::Acquire) == 0
+    }
+}
+
+/// A pending item, ordered by deadline and then by arrival.
+pub(crate) struct Entry<T> {
+    pub(crate) deadline: Instant,
+    pub(crate) seq: u64,
+    pub(crate) value: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        // reversed, so the max-heap pops the earliest entry
+        (other.deadline, other.seq).cmp(&(self.deadline, self.seq))
+    }
+}
@@ -0,0 +1,235 @@
+use std::{collections::BinaryHeap, time::Instant};
+
+use super::queue::{Entry, Shared};
+
+/// The consumer end of the delay queue.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+///
+/// Items pulled from the inbox wait in a heap owned by the receiver until they
+/// are due. That heap is not bounded by the channel's capacity; see the
+/// [module-level gotchas](super#gotchas).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use core::num::NonZeroUsize;
+/// use gil::delay::channel;
+///
+/// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+/// tx.send_after(2, Duration::from_millis(20));
+/// tx.send_after(1, Duration::from_millis(10));
+/// assert_eq!(rx.recv(), 1);
+/// assert_eq!(rx.recv(), 2);
+/// ```
+pub struct Receiver<T> {
+    inbox: crate::mpsc::Receiver<(Instant, T)>,
+    shared: crate::Arc<Shared>,
+    pending: BinaryHeap<Entry<T>>,
+    /// Arrival counter, to keep equal deadlines FIFO.
+    seq: u64,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn new(
+        inbox: crate::mpsc::Receiver<(Instant, T)>,
+        shared: crate::Arc<Shared>,
+    ) -> Self {
+        Self {
+            inbox,
+            shared,
+            pending: BinaryHeap::new(),
+            seq: 0,
+        }
+    }
+
+    /// Receives the item with the earliest deadline, blocking until that
+    /// deadline passes.
+    ///
+    /// Parks until the earliest pending deadline, or indefinitely if nothing is
+    /// pending. A sender whose item is due sooner wakes it early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{thread, time::Duration};
+    /// use core::num::NonZeroUsize;
+    /// use gil::delay::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<&str>(NonZeroUsize::new(16).unwrap());
+    /// tx.send_after("retry", Duration::from_secs(60));
+    ///
+    /// thread::spawn(move || tx.send_after("urgent", Duration::ZERO));
+    /// // does not wait for the retry
+    /// assert_eq!(rx.recv(), "urgent");
+    /// ```
+    pub fn recv(&mut self) -> T {
+        let Some(value) = self.wait(|_| false) else {
+            unreachable!("the wait only gives up when asked to")
+        };
+        value
+    }
+
+    /// Receives the item with the earliest deadline, parking until it is due,
+    /// unless `give_up` returns `true` while nothing is pending.
+    fn wait(&mut self, give_up: impl Fn(&Self) -> bool) -> Option<T> {
+        loop {
+            // checked before draining, so every item sent before giving up is
+            // in the heap by the time it counts
+            let gave_up = give_up(self);
+            self.drain();
+            if let Some(value) = self.pop_due() {
+                return Some(value);
+            }
+            if gave_up && self.pending.is_empty() {
+                return None;
+            }
+
+            let deadline = self.next_deadline();
+            self.shared.announce(deadline);
+            // recheck: anything sent before the announce must be seen here
+            if self.drain() || (!gave_up && give_up(self)) {
+                self.shared.cancel();
+                continue;
+            }
+            self.shared.sleep(deadline);
+        }
+    }
+
+    /// Attempts to receive the item with the earliest deadline, without
+    /// blocking.
+    ///
+    /// Returns `None` if nothing is pending or the earliest deadline has not
+    /// passed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use core::num::NonZeroUsize;
+    /// use gil::delay::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// assert_eq!(rx.try_recv(), None);
+    ///
+    /// tx.send_at(1, Instant::now());
+    /// assert_eq!(rx.try_recv(), Some(1));
+    /// ```
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.drain();
+        self.pop_due()
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop. Once every sender has been dropped it
+    /// still waits out the pending deadlines, then ends. See
+    /// [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// The earliest deadline among the items received so far, due or not.
+    ///
+    /// Only reflects items the receiver has already pulled from the inbox; call
+    /// [`try_recv`](Receiver::try_recv) first for an up-to-date answer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use core::num::NonZeroUsize;
+    /// use gil::delay::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// let deadline = Instant::now() + Duration::from_secs(60);
+    /// tx.send_at(1, deadline);
+    ///
+    /// assert_eq!(rx.try_recv(), None);
+    /// assert_eq!(rx.next_deadline(), Some(deadline));
+    /// ```
+    #[inline]
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.peek().map(|entry| entry.deadline)
+    }
+
+    /// Returns `true` once every [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be pending.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.shared.is_disconnected()
+    }
+
+    /// Moves everything in the inbox into the pending heap. Returns `true` if
+    /// anything was moved.
+    fn drain(&mut self) -> bool {
+        let mut drained = false;
+        while let Some((deadline, value)) = self.inbox.try_recv() {
+            self.pending.push(Entry {
+                deadline,
+                seq: self.seq,
+                value,
+            });
+            self.seq += 1;
+            drained = true;
+        }
+        drained
+    }
+
+    fn pop_due(&mut self) -> Option<T> {
+        if self.next_deadline()? > Instant::now() {
+            return None;
+        }
+        self.pending.pop().map(|entry| entry.value)
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+
+    fn recv_until_disconnected(&mut self) -> Option<T> {
+        self.wait(Self::is_disconnected)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
@@ -0,0 +1,137 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use super::queue::Shared;
+
+/// The producer end of the delay queue.
+///
+/// This struct is `Clone` and `Send`. It can be shared across threads by cloning it.
+///
+/// # Examples
+///
+/// ```
+/// use std::{thread, time::Duration};
+/// use core::num::NonZeroUsize;
+/// use gil::delay::channel;
+///
+/// let (tx, mut rx) = channel::<&str>(NonZeroUsize::new(16).unwrap());
+///
+/// let mut tx2 = tx.clone();
+/// thread::spawn(move || tx2.send_after("later", Duration::from_millis(20)))
+///     .join()
+///     .unwrap();
+///
+/// let mut tx3 = tx.clone();
+/// thread::spawn(move || tx3.send_after("sooner", Duration::ZERO))
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(rx.recv(), "sooner");
+/// assert_eq!(rx.recv(), "later");
+/// ```
+pub struct Sender<T> {
+    inbox: crate::mpsc::Sender<(Instant, T)>,
+    shared: crate::Arc<Shared>,
+}
+
+// Not derived: cloning a handle must not require `T: Clone`.
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.attach_sender();
+        Self {
+            inbox: self.inbox.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.detach_sender();
+    }
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(
+        inbox: crate::mpsc::Sender<(Instant, T)>,
+        shared: crate::Arc<Shared>,
+    ) -> Self {
+        shared.attach_sender();
+        Self { inbox, shared }
+    }
+
+    /// Sends a value that becomes receivable at `deadline`, blocking if the
+    /// inbox is full.
+    ///
+    /// A deadline in the past makes the value receivable immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use core::num::NonZeroUsize;
+    /// use gil::delay::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// tx.send_at(1, deadline);
+    /// assert_eq!(rx.recv(), 1);
+    /// assert!(Instant::now() >= deadline);
+    /// ```
+    pub fn send_at(&mut self, value: T, deadline: Instant) {
+        self.inbox.send((deadline, value));
+        self.shared.wake(deadline);
+    }
+
+    /// Sends a value that becomes receivable after `delay`, blocking if the
+    /// inbox is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use core::num::NonZeroUsize;
+    /// use gil::delay::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    ///
+    /// tx.send_after(1, Duration::from_secs(60));
+    /// assert_eq!(rx.try_recv(), None);
+    /// ```
+    pub fn send_after(&mut self, value: T, delay: Duration) {
+        self.send_at(value, Instant::now() + delay);
+    }
+
+    /// Attempts to send a value that becomes receivable at `deadline`, without
+    /// blocking.
+    ///
+    /// Returns `Err(value)` if the inbox is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use core::num::NonZeroUsize;
+    /// use gil::delay::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(2).unwrap());
+    ///
+    /// assert!(tx.try_send_at(1, Instant::now()).is_ok());
+    /// assert!(tx.try_send_at(2, Instant::now()).is_ok());
+    /// assert_eq!(tx.try_send_at(3, Instant::now()), Err(3));
+    ///
+    /// // the receiver drains the inbox on every call
+    /// assert_eq!(rx.try_recv(), Some(1));
+    /// assert!(tx.try_send_at(3, Instant::now()).is_ok());
+    /// ```
+    pub fn try_send_at(&mut self, value: T, deadline: Instant) -> Result<(), T> {
+        self.inbox
+            .try_send((deadline, value))
+            .map_err(|(_, value)| value)?;
+        self.shared.wake(deadline);
+        Ok(())
+    }
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
@@ -0,0 +1,287 @@
+//! Bounded Chase-Lev work-stealing deque.
+//!
+//! The [`Worker`] pushes and pops tasks at the bottom (LIFO, for cache locality);
+//! any number of [`Stealer`]s take tasks from the top (FIFO, oldest first). Only
+//! the last item is ever contended between the two ends.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use core::num::NonZeroUsize;
+//! use gil::deque;
+//!
+//! let (mut worker, stealer) = deque::new::<usize>(NonZeroUsize::new(1024).unwrap());
+//!
+//! for i in 0..100 {
+//!     worker.push(i).unwrap();
+//! }
+//!
+//! let thieves: Vec<_> = (0..4)
+//!     .map(|_| {
+//!         let stealer = stealer.clone();
+//!         thread::spawn(move || {
+//!             let mut stolen = 0;
+//!             while let Some(_) = stealer.steal().success() {
+//!                 stolen += 1;
+//!             }
+//!             stolen
+//!         })
+//!     })
+//!     .collect();
+//!
+//! let mut popped = 0;
+//! while worker.pop().is_some() {
+//!     popped += 1;
+//! }
+//!
+//! let stolen: usize = thieves.into_iter().map(|h| h.join().unwrap()).sum();
+//! assert!(popped + stolen <= 100);
+//! ```
+//!
+//! # Performance
+//!
+//! - **Single Allocation:** `top`, `bottom` and the buffer share one allocation,
+//!   with `top` and `bottom` on separate cache lines.
+//! - **Uncontended Worker:** `push` is a plain store, and `pop` only needs a CAS
+//!   when it takes the last item.
+//!
+//! # Gotchas
+//!
+//! - **Bounded:** The deque does not grow. [`Worker::push`] hands the value back
+//!   when it is full; the capacity is rounded up to the next power of two.
+//! - **Retry:** [`Stealer::steal`] can return [`Steal::Retry`] after losing a race
+//!   even though the deque still has items. Loop on it if you need an answer.
+//!
+//! # Reference
+//!
+//! * [Correct and Efficient Work-Stealing for Weak Memory Models](https://fzn.fr/readings/ppopp13.pdf)
+
+use core::num::NonZeroUsize;
+
+pub use self::{stealer::Stealer, worker::Worker};
+
+mod queue;
+mod stealer;
+mod worker;
+
+/// The outcome of a steal attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Steal<T> {
+    /// The deque was empty.
+    Empty,
+    /// An item was stolen.
+    Success(T),
+    /// Lost a race with the worker or another stealer; the deque may still have
+    /// items.
+    Retry,
+}
+
+impl<T> Steal<T> {
+    /// Returns `true` for [`Steal::Success`].
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success(_))
+    }
+
+    /// Returns `true` for [`Steal::Retry`].
+    #[inline]
+    pub fn is_retry(&self) -> bool {
+        matches!(self, Self::Retry)
+    }
+
+    /// Returns the stolen item, if any.
+    #[inline]
+    pub fn success(self) -> Option<T> {
+        match self {
+            Self::Success(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Creates a new work-stealing deque.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Arguments
+///
+/// * `capacity` - The capacity of the deque, rounded up to the next power of two.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::deque;
+///
+/// let (worker, stealer) = deque::new::<usize>(NonZeroUsize::new(1024).unwrap());
+/// ```
+pub fn new<T>(capacity: NonZeroUsize) -> (Worker<T>, Stealer<T>) {
+    let queue = queue::QueuePtr::with_size(capacity);
+
+    (Worker::new(queue.clone()), Stealer::new(queue))
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use alloc_crate::vec::Vec;
+
+    use crate::thread;
+
+    #[test]
+    fn test_every_item_once() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        const THIEVES: usize = 4;
+        const ITER: usize = 100_000;
+
+        let (mut worker, stealer) = new::<usize>(NonZeroUsize::new(64).unwrap());
+        let done = AtomicBool::new(false);
+
+        let mut seen = thread::scope(|scope| {
+            let thieves: Vec<_> = (0..THIEVES)
+                .map(|_| {
+                    let stealer = stealer.clone();
+                    let done = &done;
+                    scope.spawn(move || {
+                        let mut stolen = Vec::new();
+                        loop {
+                            match stealer.steal() {
+                                Steal::Success(value) => stolen.push(value),
+                                Steal::Retry => {}
+                                Steal::Empty if done.load(Ordering::Acquire) => break,
+                                Steal::Empty => thread::yield_now(),
+                            }
+                        }
+                        stolen
+                    })
+                })
+                .collect();
+
+            let mut popped = Vec::new();
+            for i in 0..ITER {
+                let mut value = i;
+                while let Err(v) = worker.push(value) {
+                    value = v;
+                    popped.extend(worker.pop());
+                }
+                if i % 3 == 0 {
+                    popped.extend(worker.pop());
+                }
+            }
+            while let Some(value) = worker.pop() {
+                popped.push(value);
+            }
+            done.store(true, Ordering::Release);
+
+            for thief in thieves {
+                popped.extend(thief.join().unwrap());
+            }
+            popped
+        });
+
+        seen.sort_unstable();
+        assert_eq!(seen, (0..ITER).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_steal_batch() {
+        let (mut worker, stealer) = new::<usize>(NonZeroUsize::new(16).unwrap());
+        let (mut dest, _) = new::<usize>(NonZeroUsize::new(2).unwrap());
+
+        for i in 0..10 {
+            worker.push(i).unwrap();
+        }
+
+        // half would be 5, but `dest` only has room for 2
+        assert_eq!(stealer.steal_batch(&mut dest), Steal::Success(()));
+        assert_eq!(dest.pop(), Some(1));
+        assert_eq!(dest.pop(), Some(0));
+
+        let (mut dest, _) = new::<usize>(NonZeroUsize::new(16).unwrap());
+        assert_eq!(stealer.steal_batch(&mut dest), Steal::Success(()));
+        assert_eq!(
+            core::iter::from_fn(|| dest.pop()).collect::<Vec<_>>(),
+            [5, 4, 3, 2]
+        );
+        assert_eq!(worker.pop(), Some(9));
+    }
+
+    #[test]
+    fn test_drop_remaining() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        {
+            let (mut worker, stealer) = new(NonZeroUsize::new(4).unwrap());
+            for _ in 0..6 {
+                if let Err(value) = worker.push(DropCounter(dropped.clone())) {
+                    drop(worker.pop());
+                    worker.push(value).ok().unwrap();
+                }
+            }
+            // cloning must not need `DropCounter: Clone`
+            drop(stealer.clone().steal());
+            assert_eq!(dropped.load(Ordering::SeqCst), 3);
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 6);
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn pop_steal_last_item_loom() {
+        loom::model(|| {
+            let (mut worker, stealer) = new::<usize>(NonZeroUsize::new(2).unwrap());
+            worker.push(1).unwrap();
+
+            let handle = thread::spawn(move || stealer.steal().success());
+
+            let popped = worker.pop();
+            let stolen = handle.join().unwrap();
+
+            // exactly one side gets the item
+            assert_eq!(popped.is_some() as u8 + stolen.is_some() as u8, 1);
+            assert_eq!(popped.or(stolen), Some(1));
+        })
+    }
+
+    #[test]
+    fn pop_steal_two_items_loom() {
+        loom::model(|| {
+            let (mut worker, stealer) = new::<usize>(NonZeroUsize::new(2).unwrap());
+            worker.push(1).unwrap();
+            worker.push(2).unwrap();
+
+            let handle = thread::spawn(move || stealer.steal().success());
+
+            let mut popped = [worker.pop(), worker.pop()];
+            let stolen = handle.join().unwrap();
+
+            popped.sort();
+            match stolen {
+                Some(value) => {
+                    assert_eq!(value, 1);
+                    assert_eq!(popped, [None, Some(2)]);
+                }
+                None => assert_eq!(popped, [Some(1), Some(2)]),
+            }
+        })
+    }
+}
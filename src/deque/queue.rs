@@ -0,0 +1,52 @@
+//! Layout for the work-stealing deque.
+//!
+//! `top` is where stealers take from and `bottom` is the worker's end, so they
+//! map onto the ring's head and tail: items live in the `top..bottom` window,
+//! and [`DropWindow`] drops whatever is left there on teardown.
+
+use crate::{
+    atomic::AtomicUsize,
+    padded::Padded,
+    ring::{RingHead, RingTail},
+    spsc::queue::DropWindow,
+};
+
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Top {
+    top: Padded<AtomicUsize>,
+}
+
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Bottom {
+    bottom: Padded<AtomicUsize>,
+}
+
+impl RingHead for Top {
+    #[inline(always)]
+    fn head(&self) -> &AtomicUsize {
+        &self.top.value
+    }
+}
+
+impl RingTail for Bottom {
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        &self.bottom.value
+    }
+}
+
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Top, Bottom, T, DropWindow>;
+
+impl<T> QueuePtr<T> {
+    #[inline(always)]
+    pub(crate) fn top(&self) -> &AtomicUsize {
+        self.header().head.head()
+    }
+
+    #[inline(always)]
+    pub(crate) fn bottom(&self) -> &AtomicUsize {
+        self.header().tail.tail()
+    }
+}
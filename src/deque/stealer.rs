@@ -0,0 +1,180 @@
+use core::mem::MaybeUninit;
+
+use crate::atomic::{Ordering, fence};
+
+use super::{Steal, Worker, queue::QueuePtr};
+
+/// The thief end of a work-stealing deque.
+///
+/// Steals from the top, FIFO. This struct is `Clone`, `Send` and `Sync`: any
+/// number of threads can steal from the same deque.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use core::num::NonZeroUsize;
+/// use gil::deque;
+///
+/// let (mut worker, stealer) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+/// worker.push(1).unwrap();
+/// worker.push(2).unwrap();
+///
+/// let thief = stealer.clone();
+/// let stolen = thread::spawn(move || thief.steal().success()).join().unwrap();
+/// assert_eq!(stolen, Some(1));
+/// ```
+pub struct Stealer<T> {
+    ptr: QueuePtr<T>,
+}
+
+// Not derived: cloning a handle must not require `T: Clone`.
+impl<T> Clone for Stealer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            ptr: self.ptr.clone(),
+        }
+    }
+}
+
+impl<T> Stealer<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self { ptr }
+    }
+
+    /// Attempts to steal the oldest item from the top of the deque.
+    ///
+    /// Returns [`Steal::Retry`] if it lost a race with the worker or another
+    /// stealer; the deque may still have items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::deque::{self, Steal};
+    ///
+    /// let (mut worker, stealer) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+    /// assert_eq!(stealer.steal(), Steal::Empty);
+    ///
+    /// worker.push(1).unwrap();
+    /// assert_eq!(stealer.steal(), Steal::Success(1));
+    /// ```
+    pub fn steal(&self) -> Steal<T> {
+        let top = self.ptr.top().load(Ordering::Acquire);
+        // pairs with the fence in `Worker::pop`; see there
+        fence(Ordering::SeqCst);
+        let bottom = self.ptr.bottom().load(Ordering::Acquire);
+
+        if bottom.wrapping_sub(top) as isize <= 0 {
+            return Steal::Empty;
+        }
+
+        // Read speculatively: until the CAS below succeeds the worker may pop
+        // this item, or push over the slot after other steals, so the bytes are
+        // only trusted once top is ours.
+        let value = unsafe {
+            self.ptr
+                .at(top)
+                .cast::<MaybeUninit<T>>()
+                .as_ptr()
+                .read_volatile()
+        };
+
+        match self.ptr.top().compare_exchange(
+            top,
+            top.wrapping_add(1),
+            Ordering::SeqCst,
+            Ordering::Relaxed,
+        ) {
+            // SAFETY: the CAS claimed the item we read.
+            Ok(_) => Steal::Success(unsafe { value.assume_init() }),
+            Err(_) => Steal::Retry,
+        }
+    }
+
+    /// Steals about half of the items and pushes them onto `dest`.
+    ///
+    /// Takes at most as many items as `dest` has room for, oldest first, so they
+    /// keep their relative order at the bottom of `dest`. Returns
+    /// [`Steal::Success`] if at least one item moved, and [`Steal::Empty`] if
+    /// there was nothing to take or no room in `dest`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::deque;
+    ///
+    /// let (mut worker, stealer) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+    /// let (mut thief, _) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+    ///
+    /// for i in 0..4 {
+    ///     worker.push(i).unwrap();
+    /// }
+    /// assert!(stealer.steal_batch(&mut thief).is_success());
+    ///
+    /// assert_eq!(thief.pop(), Some(1));
+    /// assert_eq!(thief.pop(), Some(0));
+    /// assert_eq!(thief.pop(), None);
+    /// ```
+    pub fn steal_batch(&self, dest: &mut Worker<T>) -> Steal<()> {
+        let top = self.ptr.top().load(Ordering::Acquire);
+        let bottom = self.ptr.bottom().load(Ordering::Acquire);
+        let len = bottom.wrapping_sub(top) as isize;
+        if len <= 0 {
+            return Steal::Empty;
+        }
+
+        // Items go one CAS at a time: the worker pops without a CAS while more
+        // than one item is left, so a single CAS over several items could hand
+        // out one the worker has already taken.
+        let batch = (len as usize).div_ceil(2).min(dest.free());
+        let mut moved = 0;
+        let mut backoff = crate::ExponentialBackoff::new(6, 10);
+        while moved < batch {
+            match self.steal() {
+                Steal::Success(value) => {
+                    // cannot fail: `batch` fits in `dest`, which only we push to
+                    if dest.push(value).is_err() {
+                        unreachable!("dest has room for the batch");
+                    }
+                    moved += 1;
+                }
+                Steal::Empty => break,
+                Steal::Retry => {
+                    if backoff.backoff() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        match moved {
+            0 if batch == 0 => Steal::Empty,
+            0 => Steal::Retry,
+            _ => Steal::Success(()),
+        }
+    }
+
+    /// Returns `true` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::deque;
+    ///
+    /// let (mut worker, stealer) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+    /// assert!(stealer.is_empty());
+    /// worker.push(1).unwrap();
+    /// assert!(!stealer.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        let top = self.ptr.top().load(Ordering::Acquire);
+        let bottom = self.ptr.bottom().load(Ordering::Acquire);
+        bottom.wrapping_sub(top) as isize <= 0
+    }
+}
+
+unsafe impl<T: Send> Send for Stealer<T> {}
+unsafe impl<T: Send> Sync for Stealer<T> {}
@@ -0,0 +1,158 @@
+use crate::atomic::{Ordering, fence};
+
+use super::queue::QueuePtr;
+
+/// The owner end of a work-stealing deque.
+///
+/// Pushes and pops at the bottom, LIFO. This struct is `Send` but not `Sync` or
+/// `Clone`: there is exactly one worker per deque.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::deque;
+///
+/// let (mut worker, _stealer) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+/// worker.push(1).unwrap();
+/// worker.push(2).unwrap();
+/// assert_eq!(worker.pop(), Some(2));
+/// assert_eq!(worker.pop(), Some(1));
+/// assert_eq!(worker.pop(), None);
+/// ```
+pub struct Worker<T> {
+    ptr: QueuePtr<T>,
+}
+
+impl<T> Worker<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self { ptr }
+    }
+
+    /// Pushes a value onto the bottom of the deque.
+    ///
+    /// Never blocks. Returns `Err(value)` if the deque is full; a scheduler would
+    /// typically run the task inline instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::deque;
+    ///
+    /// let (mut worker, _stealer) = deque::new::<i32>(NonZeroUsize::new(2).unwrap());
+    /// assert_eq!(worker.push(1), Ok(()));
+    /// assert_eq!(worker.push(2), Ok(()));
+    /// assert_eq!(worker.push(3), Err(3));
+    /// ```
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let bottom = self.ptr.bottom().load(Ordering::Relaxed);
+        // `Acquire` pairs with the stealers' top CAS: a slot they moved past is
+        // done being read before we reuse it
+        let top = self.ptr.top().load(Ordering::Acquire);
+
+        if bottom.wrapping_sub(top) >= self.ptr.capacity {
+            return Err(value);
+        }
+
+        // SAFETY: the slot is outside the `top..bottom` window, so no stealer
+        // can claim it until the store below publishes it.
+        unsafe { self.ptr.at(bottom).write(value) };
+        self.ptr
+            .bottom()
+            .store(bottom.wrapping_add(1), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Pops the most recently pushed value from the bottom of the deque.
+    ///
+    /// Returns `None` if the deque is empty, including when a stealer won the
+    /// race for the last item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::deque;
+    ///
+    /// let (mut worker, stealer) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+    /// worker.push(1).unwrap();
+    /// worker.push(2).unwrap();
+    ///
+    /// assert_eq!(stealer.steal().success(), Some(1));
+    /// assert_eq!(worker.pop(), Some(2));
+    /// assert_eq!(worker.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        let bottom = self.ptr.bottom().load(Ordering::Relaxed).wrapping_sub(1);
+        // reserve the bottom item before looking at top, so a stealer either
+        // sees the smaller bottom or we see its advanced top
+        self.ptr.bottom().store(bottom, Ordering::Relaxed);
+        fence(Ordering::SeqCst);
+        let top = self.ptr.top().load(Ordering::Relaxed);
+
+        let len = bottom.wrapping_sub(top) as isize;
+        if len < 0 {
+            // already empty
+            self.ptr
+                .bottom()
+                .store(bottom.wrapping_add(1), Ordering::Relaxed);
+            return None;
+        }
+
+        if len > 0 {
+            // more than one item left: stealers cannot reach this one
+            // SAFETY: the slot is inside the window and no longer visible to
+            // stealers.
+            return Some(unsafe { self.ptr.at(bottom).read() });
+        }
+
+        // the last item: race the stealers for it on top
+        let won = self
+            .ptr
+            .top()
+            .compare_exchange(
+                top,
+                top.wrapping_add(1),
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            )
+            .is_ok();
+        self.ptr
+            .bottom()
+            .store(bottom.wrapping_add(1), Ordering::Relaxed);
+
+        // SAFETY: winning the CAS makes the item ours.
+        won.then(|| unsafe { self.ptr.at(bottom).read() })
+    }
+
+    /// Returns `true` if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::deque;
+    ///
+    /// let (mut worker, _stealer) = deque::new::<i32>(NonZeroUsize::new(16).unwrap());
+    /// assert!(worker.is_empty());
+    /// worker.push(1).unwrap();
+    /// assert!(!worker.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        let bottom = self.ptr.bottom().load(Ordering::Relaxed);
+        let top = self.ptr.top().load(Ordering::Relaxed);
+        bottom.wrapping_sub(top) as isize <= 0
+    }
+
+    /// Free slots left, as of the last time a stealer moved top.
+    #[inline]
+    pub(crate) fn free(&self) -> usize {
+        let bottom = self.ptr.bottom().load(Ordering::Relaxed);
+        let top = self.ptr.top().load(Ordering::Acquire);
+        self.ptr.capacity - bottom.wrapping_sub(top)
+    }
+}
+
+unsafe impl<T: Send> Send for Worker<T> {}
@@ -0,0 +1,226 @@
+use core::ops::{Index, IndexMut};
+
+use crate::atomic::Ordering;
+
+use super::queue::QueuePtr;
+
+/// One stage of a disruptor pipeline.
+///
+/// Sees every published item, in sequence order, after all earlier stages have
+/// released it. This struct is `Send` but not `Sync` or `Clone`.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::disruptor;
+///
+/// let (mut producer, mut stages) = disruptor::channel(
+///     NonZeroUsize::new(8).unwrap(),
+///     NonZeroUsize::new(2).unwrap(),
+///     || 0,
+/// );
+/// producer.claim(1)[0] = 1;
+///
+/// // the second stage waits for the first
+/// assert!(stages[1].try_read().is_none());
+/// stages[0].read()[0] *= 10;
+/// assert_eq!(stages[1].read()[0], 10);
+/// ```
+pub struct Consumer<T> {
+    ptr: QueuePtr<T>,
+    stage: usize,
+    /// Next sequence this stage has not processed.
+    cursor: usize,
+    /// Stale bound of what this stage may process, refreshed lazily.
+    available: usize,
+}
+
+impl<T> Consumer<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>, stage: usize) -> Self {
+        Self {
+            ptr,
+            stage,
+            cursor: 0,
+            available: 0,
+        }
+    }
+
+    /// This stage's position in the pipeline, starting at 0.
+    #[inline]
+    pub fn stage(&self) -> usize {
+        self.stage
+    }
+
+    /// Attempts to take every item the previous stage has released, without
+    /// blocking.
+    ///
+    /// Returns `None` if there is nothing new.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::disruptor;
+    ///
+    /// let (mut producer, mut stages) = disruptor::channel(
+    ///     NonZeroUsize::new(8).unwrap(),
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     || 0,
+    /// );
+    /// assert!(stages[0].try_read().is_none());
+    ///
+    /// producer.claim(3).iter_mut().for_each(|v| *v = 7);
+    /// assert_eq!(stages[0].try_read().unwrap().len(), 3);
+    /// ```
+    pub fn try_read(&mut self) -> Option<Batch<'_, T>> {
+        if self.refresh() == 0 {
+            return None;
+        }
+        Some(self.batch())
+    }
+
+    /// Takes every item the previous stage has released, spinning until there is
+    /// at least one.
+    ///
+    /// The items are released to the next stage (or back to the producers) when
+    /// the returned [`Batch`] is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use core::num::NonZeroUsize;
+    /// use gil::disruptor;
+    ///
+    /// let (mut producer, mut stages) = disruptor::channel(
+    ///     NonZeroUsize::new(8).unwrap(),
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     || 0,
+    /// );
+    ///
+    /// thread::spawn(move || producer.claim(1)[0] = 42);
+    /// assert_eq!(stages[0].read()[0], 42);
+    /// ```
+    pub fn read(&mut self) -> Batch<'_, T> {
+        let mut backoff = crate::Backoff::with_spin_count(128);
+        while self.refresh() == 0 {
+            backoff.backoff();
+        }
+        self.batch()
+    }
+
+    /// Items this stage may process, refreshing the bound when it has caught up.
+    fn refresh(&mut self) -> usize {
+        if self.available != self.cursor {
+            return self.available.wrapping_sub(self.cursor);
+        }
+
+        if self.stage == 0 {
+            // scan for published cells; the epoch `Acquire` pairs with the
+            // producer's publish of each one
+            let mut end = self.cursor;
+            while end.wrapping_sub(self.cursor) < self.ptr.capacity
+                && self.ptr.cell_at(end).epoch().load(Ordering::Acquire) == end.wrapping_add(1)
+            {
+                end = end.wrapping_add(1);
+            }
+            self.available = end;
+        } else {
+            self.available = self.ptr.stage(self.stage - 1).load(Ordering::Acquire);
+        }
+
+        self.available.wrapping_sub(self.cursor)
+    }
+
+    fn batch(&mut self) -> Batch<'_, T> {
+        let len = self.available.wrapping_sub(self.cursor);
+        Batch {
+            consumer: self,
+            len,
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for Consumer<T> {}
+
+/// A run of items available to one stage, accessible in place.
+///
+/// Indexing is relative to the start of the batch. The items are released to
+/// the next stage when the batch is dropped.
+pub struct Batch<'a, T> {
+    consumer: &'a mut Consumer<T>,
+    len: usize,
+}
+
+impl<T> Batch<'_, T> {
+    /// The number of items in the batch.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the batch is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The sequence number of the first item in the batch.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.consumer.cursor
+    }
+
+    /// Iterates over the items in the batch.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.len).map(|i| &self[i])
+    }
+
+    /// Iterates mutably over the items in the batch.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let (ptr, start) = (&self.consumer.ptr, self.consumer.cursor);
+        // SAFETY: no other stage or producer touches these sequences until this
+        // batch releases them, and each is yielded once.
+        (0..self.len).map(move |i| unsafe { &mut *ptr.cell_at(start.wrapping_add(i)).data_ptr() })
+    }
+}
+
+impl<T> Index<usize> for Batch<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        assert!(
+            index < self.len,
+            "index {index} out of a {}-item batch",
+            self.len
+        );
+        let sequence = self.consumer.cursor.wrapping_add(index);
+        // SAFETY: see `iter_mut`.
+        unsafe { &*self.consumer.ptr.cell_at(sequence).data_ptr() }
+    }
+}
+
+impl<T> IndexMut<usize> for Batch<'_, T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        assert!(
+            index < self.len,
+            "index {index} out of a {}-item batch",
+            self.len
+        );
+        let sequence = self.consumer.cursor.wrapping_add(index);
+        // SAFETY: see `iter_mut`.
+        unsafe { &mut *self.consumer.ptr.cell_at(sequence).data_ptr() }
+    }
+}
+
+impl<T> Drop for Batch<'_, T> {
+    fn drop(&mut self) {
+        let consumer = &mut *self.consumer;
+        consumer.cursor = consumer.cursor.wrapping_add(self.len);
+        consumer
+            .ptr
+            .stage(consumer.stage)
+            .store(consumer.cursor, Ordering::Release);
+    }
+}
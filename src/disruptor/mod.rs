@@ -0,0 +1,346 @@
+//! Disruptor-style sequencer with multi-stage consumer pipelines.
+//!
+//! Producers claim runs of slots in a preallocated ring, write them in place,
+//! and publish them. Every item then flows through a fixed chain of consumer
+//! stages, each working on the item in place and releasing it to the next. Once
+//! the last stage releases a slot, producers reuse it, along with whatever the
+//! stages left in it.
+//!
+//! Nothing is moved in or out of the ring: items are created once by the factory
+//! passed to [`channel`] and live until the disruptor is dropped. This suits
+//! pipelines over large or heap-owning records (decode → enrich → journal),
+//! where each stage would otherwise copy or reallocate.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use core::num::NonZeroUsize;
+//! use gil::disruptor;
+//!
+//! let (mut producer, stages) = disruptor::channel(
+//!     NonZeroUsize::new(64).unwrap(),
+//!     NonZeroUsize::new(2).unwrap(),
+//!     || 0u64,
+//! );
+//! let [mut double, mut sum]: [_; 2] = stages.try_into().ok().unwrap();
+//!
+//! thread::spawn(move || {
+//!     for i in 1..=100 {
+//!         producer.claim(1)[0] = i;
+//!     }
+//! });
+//! thread::spawn(move || {
+//!     let mut seen = 0;
+//!     while seen < 100 {
+//!         let mut batch = double.read();
+//!         batch.iter_mut().for_each(|v| *v *= 2);
+//!         seen += batch.len();
+//!     }
+//! });
+//!
+//! let (mut total, mut seen) = (0, 0);
+//! while seen < 100 {
+//!     let batch = sum.read();
+//!     total += batch.iter().sum::<u64>();
+//!     seen += batch.len();
+//! }
+//! assert_eq!(total, 2 * 5050);
+//! ```
+//!
+//! # Performance
+//!
+//! Each stage owns a cache-padded cursor and publishes it once per
+//! batch, so a stage that falls behind catches up in a single step and costs a
+//! single release store. Producers CAS a shared claim cursor once per claim and
+//! only reload the last stage's cursor when their cached view says the ring is
+//! full. The first stage finds published items by scanning the cells' sequence
+//! stamps, which keeps producers that finish out of order from blocking it.
+//!
+//! # Gotchas
+//!
+//! - **Capacity Rounding:** The capacity is rounded up to the next power of two.
+//! - **Slots Are Reused:** A claim exposes the previous occupant of each slot,
+//!   not a fresh value. Reset every field you rely on.
+//! - **Spinning:** [`Producer::claim`] and [`Consumer::read`] spin with backoff
+//!   and never park. Use the `try_` variants to integrate with your own waiting.
+//! - **Every Stage Must Run:** A stage that stops reading stalls the stages after
+//!   it and, once the ring is full, every producer.
+
+use core::num::NonZeroUsize;
+
+use alloc_crate::vec::Vec;
+
+pub use self::{
+    consumer::{Batch, Consumer},
+    producer::{Claim, Producer},
+};
+
+mod consumer;
+mod producer;
+mod queue;
+
+/// Creates a new disruptor with `stages` consumer stages.
+///
+/// Every slot is filled by calling `factory` once, up front. The consumers are
+/// returned in pipeline order: `consumers[0]` sees items first, and the last one
+/// releases them back to the producers.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Arguments
+///
+/// * `capacity` - The number of slots, rounded up to the next power of two.
+/// * `stages` - The number of consumer stages.
+/// * `factory` - Builds the initial value of each slot.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::disruptor;
+///
+/// let (producer, consumers) = disruptor::channel(
+///     NonZeroUsize::new(1024).unwrap(),
+///     NonZeroUsize::new(3).unwrap(),
+///     || [0u8; 256],
+/// );
+/// assert_eq!(consumers.len(), 3);
+/// ```
+pub fn channel<T>(
+    capacity: NonZeroUsize,
+    stages: NonZeroUsize,
+    factory: impl FnMut() -> T,
+) -> (Producer<T>, Vec<Consumer<T>>) {
+    let queue = queue::QueuePtr::with_header(capacity, queue::Head::new(stages.get()), ());
+    queue.initialize::<crate::cell::CellInit<T>>();
+    queue.fill(factory);
+
+    let consumers = (0..stages.get())
+        .map(|stage| Consumer::new(queue.clone(), stage))
+        .collect();
+    (Producer::new(queue), consumers)
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use crate::thread;
+
+    fn nz(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    #[test]
+    fn stages_run_in_order() {
+        const ITER: usize = 100_000;
+
+        let (mut producer, consumers) = channel(nz(64), nz(3), || (0usize, 0usize));
+
+        thread::scope(|scope| {
+            for (stage, mut consumer) in consumers.into_iter().enumerate() {
+                scope.spawn(move || {
+                    let mut expected = 0;
+                    while expected < ITER {
+                        let mut batch = consumer.read();
+                        assert_eq!(batch.sequence(), expected);
+                        for (value, step) in batch.iter_mut() {
+                            assert_eq!(*value, expected);
+                            assert_eq!(*step, stage);
+                            *step += 1;
+                            expected += 1;
+                        }
+                    }
+                });
+            }
+
+            let mut i = 0;
+            while i < ITER {
+                let mut claim = producer.claim((ITER - i).min(7));
+                for (value, step) in claim.iter_mut() {
+                    *value = i;
+                    *step = 0;
+                    i += 1;
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn multiple_producers() {
+        const PRODUCERS: usize = 4;
+        const ITER: usize = 25_000;
+
+        let (producer, mut consumers) = channel(nz(32), nz(1), || (0usize, 0usize));
+        let mut consumer = consumers.pop().unwrap();
+
+        thread::scope(|scope| {
+            for id in 0..PRODUCERS {
+                let mut producer = producer.clone();
+                scope.spawn(move || {
+                    for i in 0..ITER {
+                        let mut claim = producer.claim(1);
+                        claim[0] = (id, i);
+                    }
+                });
+            }
+
+            let mut next = [0; PRODUCERS];
+            let mut seen = 0;
+            while seen < PRODUCERS * ITER {
+                let batch = consumer.read();
+                for &(id, i) in batch.iter() {
+                    assert_eq!(next[id], i);
+                    next[id] += 1;
+                }
+                seen += batch.len();
+            }
+        });
+    }
+
+    #[test]
+    fn try_claim_gated_by_last_stage() {
+        let (mut producer, mut consumers) = channel(nz(4), nz(2), || 0);
+
+        drop(producer.try_claim(3).unwrap());
+        assert!(producer.try_claim(2).is_none());
+
+        // the first stage alone frees nothing
+        assert_eq!(consumers[0].try_read().unwrap().len(), 3);
+        assert!(producer.try_claim(2).is_none());
+
+        assert_eq!(consumers[1].try_read().unwrap().len(), 3);
+        assert!(consumers[1].try_read().is_none());
+        assert_eq!(producer.try_claim(4).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn unpublished_claim_blocks_first_stage() {
+        let (producer, mut consumers) = channel(nz(8), nz(1), || 0);
+        let (mut first, mut second) = (producer.clone(), producer);
+
+        let mut early = first.try_claim(1).unwrap();
+        early[0] = 1;
+        second.try_claim(1).unwrap()[0] = 2;
+        assert!(consumers[0].try_read().is_none());
+
+        drop(early);
+        let batch = consumers[0].try_read().unwrap();
+        assert_eq!(batch.iter().copied().collect::<Vec<_>>(), [1, 2]);
+    }
+
+    #[test]
+    fn drops_every_slot() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        {
+            let (mut producer, _consumers) = channel(nz(8), nz(2), || DropCounter(dropped.clone()));
+            // overwriting a slot drops its previous occupant
+            producer.claim(1)[0] = DropCounter(dropped.clone());
+            assert_eq!(dropped.load(Ordering::SeqCst), 1);
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 9);
+    }
+
+    #[test]
+    fn panicking_factory_drops_filled_prefix() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let mut made = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            channel(nz(8), nz(1), || {
+                made += 1;
+                assert!(made <= 3, "factory gives up");
+                DropCounter(dropped.clone())
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(dropped.load(Ordering::SeqCst), 3);
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_test {
+    use super::*;
+    use crate::thread;
+
+    #[test]
+    fn stage_gated_by_previous_loom() {
+        loom::model(|| {
+            let (mut producer, mut consumers) = channel(
+                NonZeroUsize::new(2).unwrap(),
+                NonZeroUsize::new(2).unwrap(),
+                || 0usize,
+            );
+            let mut last = consumers.pop().unwrap();
+            let mut first = consumers.pop().unwrap();
+
+            let handle = thread::spawn(move || {
+                let mut seen = 0;
+                while seen < 2 {
+                    let mut batch = first.read();
+                    batch.iter_mut().for_each(|v| *v *= 10);
+                    seen += batch.len();
+                }
+            });
+
+            producer.claim(1)[0] = 1;
+            producer.claim(1)[0] = 2;
+
+            let mut values = Vec::new();
+            while values.len() < 2 {
+                values.extend(last.read().iter().copied());
+            }
+            assert_eq!(values, [10, 20]);
+
+            handle.join().unwrap();
+        })
+    }
+
+    #[test]
+    fn producer_wraps_behind_last_stage_loom() {
+        loom::model(|| {
+            let (mut producer, mut consumers) = channel(
+                NonZeroUsize::new(2).unwrap(),
+                NonZeroUsize::new(1).unwrap(),
+                || 0usize,
+            );
+            let mut consumer = consumers.pop().unwrap();
+
+            let handle = thread::spawn(move || {
+                for i in 1..=3 {
+                    producer.claim(1)[0] = i;
+                }
+            });
+
+            let mut values = Vec::new();
+            while values.len() < 3 {
+                values.extend(consumer.read().iter().copied());
+            }
+            assert_eq!(values, [1, 2, 3]);
+
+            handle.join().unwrap();
+        })
+    }
+}
@@ -0,0 +1,228 @@
+use core::ops::{Index, IndexMut};
+
+use crate::atomic::Ordering;
+
+use super::queue::QueuePtr;
+
+/// The publishing end of a disruptor.
+///
+/// This struct is `Clone` and `Send`. Clone it to publish from several threads;
+/// each claim is a contiguous run of sequences, so batches from different
+/// producers never interleave.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::disruptor;
+///
+/// let (mut producer, mut stages) = disruptor::channel(
+///     NonZeroUsize::new(8).unwrap(),
+///     NonZeroUsize::new(1).unwrap(),
+///     || 0usize,
+/// );
+///
+/// let mut claim = producer.claim(2);
+/// claim[0] = 1;
+/// claim[1] = 2;
+/// drop(claim);
+///
+/// let batch = stages[0].read();
+/// assert_eq!(batch.iter().copied().collect::<Vec<_>>(), [1, 2]);
+/// ```
+pub struct Producer<T> {
+    ptr: QueuePtr<T>,
+    /// Stale snapshot of the last stage's cursor, refreshed lazily.
+    gate_cache: usize,
+}
+
+impl<T> Clone for Producer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            ptr: self.ptr.clone(),
+            gate_cache: self.gate_cache,
+        }
+    }
+}
+
+impl<T> Producer<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self { ptr, gate_cache: 0 }
+    }
+
+    /// Claims the next `n` slots, spinning until the last stage has released
+    /// enough of them.
+    ///
+    /// The slots still hold whatever was there before: the factory's value, or an
+    /// item that went through every stage a lap ago. Overwrite what you need and
+    /// drop the [`Claim`] to publish.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::disruptor;
+    ///
+    /// let (mut producer, mut stages) = disruptor::channel(
+    ///     NonZeroUsize::new(8).unwrap(),
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     Vec::<u8>::new,
+    /// );
+    ///
+    /// let mut claim = producer.claim(1);
+    /// claim[0].clear();
+    /// claim[0].extend_from_slice(b"reused");
+    /// drop(claim);
+    ///
+    /// assert_eq!(stages[0].read()[0], b"reused");
+    /// ```
+    pub fn claim(&mut self, n: usize) -> Claim<'_, T> {
+        let mut backoff = crate::Backoff::with_spin_count(128);
+        let start = loop {
+            match self.reserve(n) {
+                Some(start) => break start,
+                None => backoff.backoff(),
+            }
+        };
+        Claim::new(&self.ptr, start, n)
+    }
+
+    /// Attempts to claim the next `n` slots without blocking.
+    ///
+    /// Returns `None` if the last stage has not released enough slots yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::disruptor;
+    ///
+    /// let (mut producer, mut stages) = disruptor::channel(
+    ///     NonZeroUsize::new(4).unwrap(),
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     || 0,
+    /// );
+    ///
+    /// drop(producer.try_claim(4).unwrap());
+    /// assert!(producer.try_claim(1).is_none());
+    ///
+    /// drop(stages[0].read());
+    /// assert!(producer.try_claim(4).is_some());
+    /// ```
+    pub fn try_claim(&mut self, n: usize) -> Option<Claim<'_, T>> {
+        let start = self.reserve(n)?;
+        Some(Claim::new(&self.ptr, start, n))
+    }
+
+    /// Moves the claim cursor past `n` free slots, returning the first sequence.
+    fn reserve(&mut self, n: usize) -> Option<usize> {
+        let capacity = self.ptr.capacity;
+        assert!(
+            n <= capacity,
+            "claiming {n} slots of a {capacity}-slot ring"
+        );
+
+        let claim = self.ptr.claim();
+        let mut start = claim.load(Ordering::Relaxed);
+        loop {
+            let end = start.wrapping_add(n);
+            if end.wrapping_sub(self.gate_cache) > capacity {
+                // `Acquire` pairs with the last stage's release: it is done with
+                // every cell below its cursor before we hand them out again
+                self.gate_cache = self.ptr.last_stage().load(Ordering::Acquire);
+                if end.wrapping_sub(self.gate_cache) > capacity {
+                    return None;
+                }
+            }
+
+            match claim.compare_exchange_weak(start, end, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return Some(start),
+                Err(current) => start = current,
+            }
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for Producer<T> {}
+
+/// A run of claimed slots, writable in place.
+///
+/// Indexing is relative to the start of the claim. The slots are published to
+/// the first stage, in order, when the claim is dropped.
+pub struct Claim<'a, T> {
+    ptr: &'a QueuePtr<T>,
+    start: usize,
+    len: usize,
+}
+
+impl<'a, T> Claim<'a, T> {
+    fn new(ptr: &'a QueuePtr<T>, start: usize, len: usize) -> Self {
+        Self { ptr, start, len }
+    }
+
+    /// The number of claimed slots.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing was claimed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterates over the claimed slots.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let (ptr, start) = (self.ptr, self.start);
+        // SAFETY: the claimed sequences are ours alone until published, and each
+        // is yielded once.
+        (0..self.len).map(move |i| unsafe { &mut *ptr.cell_at(start.wrapping_add(i)).data_ptr() })
+    }
+}
+
+impl<T> Index<usize> for Claim<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        assert!(
+            index < self.len,
+            "index {index} out of a {}-slot claim",
+            self.len
+        );
+        // SAFETY: see `iter_mut`.
+        unsafe { &*self.ptr.cell_at(self.start.wrapping_add(index)).data_ptr() }
+    }
+}
+
+impl<T> IndexMut<usize> for Claim<'_, T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        assert!(
+            index < self.len,
+            "index {index} out of a {}-slot claim",
+            self.len
+        );
+        // SAFETY: see `iter_mut`.
+        unsafe { &mut *self.ptr.cell_at(self.start.wrapping_add(index)).data_ptr() }
+    }
+}
+
+impl<T> Drop for Claim<'_, T> {
+    fn drop(&mut self) {
+        for i in 0..self.len {
+            let sequence = self.start.wrapping_add(i);
+            self.ptr
+                .cell_at(sequence)
+                .epoch()
+                .store(sequence.wrapping_add(1), Ordering::Release);
+        }
+    }
+}
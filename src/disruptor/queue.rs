@@ -0,0 +1,102 @@
+//! Sequencer layout for the disruptor.
+//!
+//! Every item has a sequence number; sequence `s` lives in cell `s & mask`. The
+//! header holds one cursor per side of the pipeline:
+//!
+//! * `claim` — the next sequence a producer will claim. Producers CAS it forward
+//!   by a whole batch, so any number of them can share the ring.
+//! * `stages[i]` — how many sequences stage `i` has finished with. Stage `i` may
+//!   process up to `stages[i - 1]`, and producers may claim up to
+//!   `stages[last] + capacity`.
+//!
+//! Stage 0 cannot gate on `claim`, since a claimed cell may not be written yet.
+//! Instead each cell's epoch is set to `s + 1` when sequence `s` is published,
+//! and stage 0 scans forward until it finds a cell that is not.
+
+use core::ptr::NonNull;
+
+use alloc_crate::boxed::Box;
+
+use crate::{
+    atomic::{AtomicUsize, Ordering},
+    cell::Cell,
+    padded::Padded,
+};
+
+pub(crate) struct Head {
+    claim: Padded<AtomicUsize>,
+    stages: Box<[Padded<AtomicUsize>]>,
+    /// How many cells the factory has filled so far; only short of the
+    /// capacity if it panicked during construction.
+    filled: AtomicUsize,
+}
+
+impl Head {
+    pub(crate) fn new(stages: usize) -> Self {
+        Self {
+            claim: Padded::new(AtomicUsize::new(0)),
+            stages: (0..stages)
+                .map(|_| Padded::new(AtomicUsize::new(0)))
+                .collect(),
+            filled: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Every filled cell holds a value from construction to teardown, so drop
+/// them all.
+pub(crate) struct DropAll;
+
+impl<T> crate::DropInFlight<Head, (), Cell<T>> for DropAll {
+    unsafe fn drop_in_flight(
+        head: &Head,
+        _tail: &(),
+        _capacity: usize,
+        at: impl Fn(usize) -> NonNull<Cell<T>>,
+    ) {
+        if !core::mem::needs_drop::<T>() {
+            return;
+        }
+
+        for i in 0..head.filled.load(Ordering::Relaxed) {
+            unsafe { at(i).as_ref().drop_in_place() };
+        }
+    }
+}
+
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, (), Cell<T>, DropAll>;
+
+impl<T> QueuePtr<T> {
+    #[inline(always)]
+    pub(crate) fn claim(&self) -> &AtomicUsize {
+        &self.header().head.claim.value
+    }
+
+    #[inline(always)]
+    pub(crate) fn stage(&self, stage: usize) -> &AtomicUsize {
+        &self.header().head.stages[stage].value
+    }
+
+    #[inline(always)]
+    pub(crate) fn last_stage(&self) -> &AtomicUsize {
+        let stages = &self.header().head.stages;
+        &stages[stages.len() - 1].value
+    }
+
+    /// Fills every cell from `factory`, counting as it goes so a panicking
+    /// factory leaves only the filled prefix to drop.
+    pub(crate) fn fill(&self, mut factory: impl FnMut() -> T) {
+        let filled = &self.header().head.filled;
+        for i in 0..self.capacity {
+            self.cell_at(i).set(factory());
+            filled.store(i + 1, Ordering::Relaxed);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn cell_at(&self, sequence: usize) -> &Cell<T> {
+        // SAFETY: `at` masks the sequence into the buffer, and every cell is
+        // initialised at construction.
+        unsafe { self.at(sequence).as_ref() }
+    }
+}
@@ -0,0 +1,28 @@
+//! In-place construction for the `send_with` family.
+//!
+//! The closure gets an uninitialised slot and hands back the `&mut T` that
+//! [`MaybeUninit::write`] (or [`assume_init_mut`](MaybeUninit::assume_init_mut))
+//! returns. That reference is its proof that the slot is initialised, which is
+//! what keeps the API safe: returning some other `T` is caught here.
+
+use core::mem::MaybeUninit;
+
+/// Runs `f` on `slot` and checks that it returned `slot` itself.
+///
+/// # Panics
+///
+/// Panics if `f` returns a reference to anything but `slot`, in which case the
+/// slot must be treated as uninitialised. Panics from `f` propagate.
+#[inline(always)]
+pub(crate) fn construct<T>(
+    slot: &mut MaybeUninit<T>,
+    f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T,
+) {
+    let expected = slot.as_mut_ptr();
+    let written: *mut T = f(slot);
+    // a zero-sized value has no bytes to initialise
+    assert!(
+        size_of::<T>() == 0 || written == expected,
+        "send_with: the closure must return the slot it was given"
+    );
+}
@@ -52,6 +52,13 @@ pub(crate) const RECEIVER_WAITING: u32 = 2;
 pub(crate) struct Futex(NonNull<AtomicU32>);
 
 impl Futex {
+    /// Wraps a futex word that lives in a queue allocation. For headers with more
+    /// than one word; single-word headers go through [`HasFutex`] instead.
+    #[inline(always)]
+    pub(crate) fn new(word: &AtomicU32) -> Self {
+        Self(NonNull::from(word))
+    }
+
     #[inline(always)]
     fn word(&self) -> &AtomicU32 {
         // SAFETY: see the struct docs — the queue allocation outlives this handle.
@@ -100,33 +107,106 @@ impl Futex {
         }
     }
 
+    /// Like [`sleep`](Self::sleep), but returns after `timeout` at the latest.
+    ///
+    /// On Linux this is a timed futex wait. `atomic_wait` has no timed variant
+    /// elsewhere, so other targets sleep for at most a millisecond at a time
+    /// and rely on the caller's recheck loop.
+    #[cfg(not(feature = "loom"))]
+    #[inline]
+    pub(crate) fn sleep_timeout(self, who: u32, timeout: core::time::Duration) {
+        #[cfg(target_os = "linux")]
+        {
+            let timeout = libc::timespec {
+                tv_sec: timeout.as_secs().try_into().unwrap_or(libc::time_t::MAX),
+                tv_nsec: timeout.subsec_nanos().into(),
+            };
+            // SAFETY: the word is valid and aligned for the duration of the
+            // call (see the struct docs), and `timeout` outlives it.
+            unsafe {
+                libc::syscall(
+                    libc::SYS_futex,
+                    self.word().as_ptr(),
+                    libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+                    who,
+                    &raw const timeout,
+                );
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            if self.word().load(Ordering::Relaxed) == who {
+                std::thread::sleep(timeout.min(core::time::Duration::from_millis(1)));
+            }
+        }
+    }
+
     /// Wakes the parked waiter, if any. Call after publishing the new index.
+    /// Returns whether a wake was issued (for [`Counters::wake`](crate::stats::Counters::wake)).
     #[inline(always)]
-    pub(crate) fn wake(self) {
-        let word = self.word();
-        // Order the caller's index publish before the announce-word load.
-        // Pairs with the fence in `announce`; see the module docs.
-        fence(Ordering::SeqCst);
-        if word.load(Ordering::Relaxed) != FREE {
-            word.store(FREE, Ordering::Relaxed);
+    pub(crate) fn wake(self) -> bool {
+        let issued = self.take_waiters();
+        if issued {
             #[cfg(not(feature = "loom"))]
-            atomic_wait::wake_one(word);
+            atomic_wait::wake_one(self.word());
         }
+        issued
     }
 
-    /// Wakes every parked waiter. Used by the MPMC core, where one futex word
-    /// is shared by all senders and all receivers.
+    /// Wakes every parked waiter. Used wherever several threads can park on one
+    /// word: the MPMC core, and each side of the rendezvous channel.
     // TODO(deferred): wake_all causes a thundering herd; side-specific futexes
     // with wake_one are tracked in TODO.md.
     #[inline(always)]
-    pub(crate) fn wake_all(self) {
+    pub(crate) fn wake_all(self) -> bool {
+        let issued = self.take_waiters();
+        if issued {
+            #[cfg(not(feature = "loom"))]
+            atomic_wait::wake_all(self.word());
+        }
+        issued
+    }
+
+    /// Clears the word and reports whether anyone had announced (Dekker step 2,
+    /// waker side). The OS wake is up to the caller.
+    #[inline(always)]
+    fn take_waiters(self) -> bool {
         let word = self.word();
-        // Same fence pairing as `wake`; see the module docs.
+        // Order the caller's index publish before the announce-word load.
+        // Pairs with the fence in `announce`; see the module docs.
         fence(Ordering::SeqCst);
         if word.load(Ordering::Relaxed) != FREE {
             word.store(FREE, Ordering::Relaxed);
-            #[cfg(not(feature = "loom"))]
-            atomic_wait::wake_all(word);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Like [`sleep`](Self::sleep), for a word in memory shared with other
+    /// processes. Private futex ops only match waiters in the same address space.
+    #[cfg(all(feature = "shm", target_os = "linux", not(feature = "loom")))]
+    #[inline(always)]
+    pub(crate) fn sleep_shared(self, who: u32) {
+        crate::shm::futex_wait(self.word(), who);
+    }
+
+    /// Like [`wake`](Self::wake), for a word in shared memory.
+    #[cfg(all(feature = "shm", target_os = "linux", not(feature = "loom")))]
+    #[inline(always)]
+    pub(crate) fn wake_shared(self) {
+        if self.take_waiters() {
+            crate::shm::futex_wake(self.word(), 1);
+        }
+    }
+
+    /// Like [`wake_all`](Self::wake_all), for a word in shared memory.
+    #[cfg(all(feature = "shm", target_os = "linux", not(feature = "loom")))]
+    #[inline(always)]
+    pub(crate) fn wake_all_shared(self) {
+        if self.take_waiters() {
+            crate::shm::futex_wake(self.word(), i32::MAX);
         }
     }
 }
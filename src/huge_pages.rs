@@ -0,0 +1,193 @@
+//! An [`Allocator`] that backs queues with huge pages.
+//!
+//! Large rings pay for page faults on first touch and for TLB misses once
+//! warm. Mapping the queue on 2 MiB pages cuts the TLB footprint by 512x, and
+//! pre-faulting moves the first-touch cost to construction.
+//!
+//! Each allocation is its own anonymous mapping, rounded up to a whole huge
+//! page. Explicit huge pages (`MAP_HUGETLB`) are tried first; they need a
+//! reserved pool (`vm.nr_hugepages`). Without one, the mapping falls back to
+//! regular pages with a transparent huge page hint (`MADV_HUGEPAGE`), which the
+//! kernel honours when THP is enabled in `madvise` or `always` mode. THP can
+//! only back 2 MiB-aligned ranges, so the fallback maps one huge page extra and
+//! trims the mapping down to an aligned start.
+
+use core::ptr::NonNull;
+
+use crate::{Allocator, alloc::Layout};
+
+/// The huge page size assumed for rounding: the x86-64 and aarch64 default.
+const HUGE_PAGE: usize = 2 << 20;
+/// The base page size, used as the prefault stride.
+const PAGE: usize = 4 << 10;
+
+/// Allocates queues in anonymous `mmap`s on huge pages, optionally pre-faulted.
+///
+/// Pass it to any `channel_in` constructor. Sharded channels map every shard
+/// separately. Every queue takes at least one whole huge page, so this only
+/// pays off for multi-megabyte rings.
+///
+/// Requires the `huge-pages` feature; Linux only.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{HugePages, spsc};
+///
+/// let (mut tx, mut rx) = spsc::channel_in::<u64, _>(
+///     NonZeroUsize::new(1 << 20).unwrap(),
+///     HugePages::new().prefault(true),
+/// );
+/// tx.send(1);
+/// assert_eq!(rx.recv(), 1);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HugePages {
+    prefault: bool,
+}
+
+impl HugePages {
+    /// Huge-page mappings, faulted in lazily.
+    pub const fn new() -> Self {
+        Self { prefault: false }
+    }
+
+    /// Whether to touch every page at construction, so no send or receive
+    /// ever takes a first-touch fault.
+    pub const fn prefault(self, prefault: bool) -> Self {
+        Self { prefault }
+    }
+
+    fn mapping_len(layout: Layout) -> usize {
+        layout.size().next_multiple_of(HUGE_PAGE)
+    }
+}
+
+/// Maps `len` bytes of regular pages starting on a huge page boundary, which
+/// the kernel doesn't guarantee for regular pages: over-maps by one huge page,
+/// then unmaps the slack on either side.
+fn map_aligned(len: usize) -> Option<NonNull<u8>> {
+    let ptr = map(len + HUGE_PAGE, 0)?;
+    let addr = ptr.as_ptr() as usize;
+    let head = addr.next_multiple_of(HUGE_PAGE) - addr;
+    let tail = HUGE_PAGE - head;
+    // SAFETY: both ranges lie inside the mapping just made, and nothing else
+    // refers to them.
+    unsafe {
+        if head > 0 {
+            libc::munmap(ptr.as_ptr().cast(), head);
+        }
+        if tail > 0 {
+            libc::munmap(ptr.as_ptr().add(head + len).cast(), tail);
+        }
+        Some(ptr.add(head))
+    }
+}
+
+fn map(len: usize, flags: libc::c_int) -> Option<NonNull<u8>> {
+    // SAFETY: a fresh private anonymous mapping; the kernel picks the address.
+    let ptr = unsafe {
+        libc::mmap(
+            core::ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | flags,
+            -1,
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return None;
+    }
+    NonNull::new(ptr.cast())
+}
+
+unsafe impl Allocator for HugePages {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // every mapping starts on a huge page boundary
+        if layout.align() > HUGE_PAGE {
+            return None;
+        }
+        let len = Self::mapping_len(layout);
+
+        let ptr = map(len, libc::MAP_HUGETLB).or_else(|| {
+            let ptr = map_aligned(len)?;
+            // SAFETY: advice on a mapping we own; failure only means no THP.
+            unsafe { libc::madvise(ptr.as_ptr().cast(), len, libc::MADV_HUGEPAGE) };
+            Some(ptr)
+        })?;
+
+        if self.prefault {
+            // anonymous pages read back as zero, so writing zero is invisible
+            // apart from faulting the page in
+            for offset in (0..len).step_by(PAGE) {
+                unsafe { ptr.add(offset).write_volatile(0) };
+            }
+        }
+        Some(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: `ptr` is a whole mapping of this length, made by `allocate`.
+        unsafe { libc::munmap(ptr.as_ptr().cast(), Self::mapping_len(layout)) };
+    }
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use core::num::NonZeroUsize;
+
+    use super::*;
+    use crate::thread;
+
+    #[test]
+    fn large_spsc() {
+        const COUNT: usize = 1 << 20;
+        let (mut tx, mut rx) = crate::spsc::channel_in::<usize, _>(
+            NonZeroUsize::new(COUNT / 4).unwrap(),
+            HugePages::new().prefault(true),
+        );
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..COUNT {
+                    tx.send(i);
+                }
+            });
+            for i in 0..COUNT {
+                assert_eq!(rx.recv(), i);
+            }
+        });
+    }
+
+    #[test]
+    fn sharded_maps_each_shard() {
+        let (mut tx, mut rx) = crate::mpsc::sharded::channel_in::<usize, _>(
+            NonZeroUsize::new(4).unwrap(),
+            NonZeroUsize::new(1024).unwrap(),
+            HugePages::new(),
+        );
+        let mut tx2 = tx.try_clone().unwrap();
+
+        tx.send(1);
+        tx2.send(2);
+        let mut values = [rx.recv(), rx.recv()];
+        values.sort();
+        assert_eq!(values, [1, 2]);
+    }
+
+    #[test]
+    fn rounds_to_huge_pages() {
+        let layout = Layout::from_size_align(100, 64).unwrap();
+        let ptr = HugePages::new().prefault(true).allocate(layout).unwrap();
+        assert_eq!(ptr.as_ptr() as usize % HUGE_PAGE, 0);
+        unsafe {
+            ptr.write_bytes(0xAB, 100);
+            HugePages::new().deallocate(ptr, layout);
+        }
+
+        let over_aligned = Layout::from_size_align(64, 2 * HUGE_PAGE).unwrap();
+        assert!(HugePages::new().allocate(over_aligned).is_none());
+    }
+}
@@ -0,0 +1,212 @@
+//! Iterators over receivers.
+//!
+//! Every receiver with `recv`/`try_recv` has three ways to loop over it:
+//!
+//! * `iter()` — an [`Iter`] that blocks for each item, like calling `recv` in
+//!   a loop, and ends once the queue is empty and every sender has been
+//!   dropped. Also what `for item in &mut rx` uses.
+//! * `try_iter()` — yields the items available right now and stops at the
+//!   first empty poll, like calling `try_recv` in a loop. The SPSC and sharded
+//!   receivers return a [`Drain`] instead of a [`TryIter`]: it reads through
+//!   their batch path and publishes the head once per run of items rather
+//!   than once per item.
+//! * `into_iter()` — an [`IntoIter`] that owns the receiver and blocks like
+//!   [`Iter`].
+//!
+//! Each queue counts its live senders, so the blocking iterators end on
+//! disconnection: items sent before the last sender dropped are still
+//! yielded, then `next` returns `None`. The exception is the shared-memory
+//! queues (`mpsc::shm`, `spsc::shm`): their senders live in other processes,
+//! may attach after the receiver, and can exit without running their
+//! destructors, so those receivers never report disconnection and their
+//! blocking iterators never end on their own; `break` out of the loop there.
+//!
+//! ```
+//! use core::num::NonZeroUsize;
+//! use std::thread;
+//!
+//! let (tx, rx) = gil::mpsc::channel::<u32>(NonZeroUsize::new(8).unwrap());
+//! for id in 0..3 {
+//!     let mut tx = tx.clone();
+//!     thread::spawn(move || tx.send(id));
+//! }
+//! drop(tx);
+//!
+//! let mut ids: Vec<_> = rx.into_iter().collect();
+//! ids.sort();
+//! assert_eq!(ids, [0, 1, 2]);
+//! ```
+//!
+//! Receivers that hand out borrowed views rather than items (the bip buffer,
+//! the disruptor and `watch`) and the single-value `oneshot` have no
+//! iterators.
+
+use crate::read_guard::{BatchReader, ReadGuard};
+
+/// Receivers the iterators in this module can drive: anything with a
+/// blocking and a non-blocking receive.
+///
+/// Implemented by every receiver with `recv` and `try_recv` methods, which
+/// these forward to. Useful for writing code generic over queue flavours.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::iter::Receive;
+///
+/// fn sum(rx: &mut impl Receive<Item = u32>) -> u32 {
+///     rx.try_iter().sum()
+/// }
+///
+/// let (mut tx, mut rx) = gil::mpsc::channel::<u32>(NonZeroUsize::new(8).unwrap());
+/// tx.send_slice(&[1, 2, 3]);
+/// assert_eq!(sum(&mut rx), 6);
+/// ```
+pub trait Receive {
+    type Item;
+
+    /// Receives the next item, waiting for one if the queue is empty.
+    fn recv(&mut self) -> Self::Item;
+
+    /// Receives the next item if one is available.
+    fn try_recv(&mut self) -> Option<Self::Item>;
+
+    /// Whether every sender has been dropped. Items sent before that may
+    /// still be queued.
+    fn is_disconnected(&self) -> bool;
+
+    /// Receives the next item, waiting for one while any sender is alive.
+    /// Returns `None` once the queue is empty and every sender has been
+    /// dropped. What the blocking iterators call.
+    ///
+    /// The default polls [`try_recv`](Self::try_recv) with a [`Backoff`];
+    /// the parking receivers override it to park instead.
+    ///
+    /// [`Backoff`]: crate::Backoff
+    fn recv_until_disconnected(&mut self) -> Option<Self::Item> {
+        let mut backoff = crate::Backoff::with_spin_count(16);
+        loop {
+            if let Some(item) = self.try_recv() {
+                return Some(item);
+            }
+            if self.is_disconnected() {
+                // the last sender may have published just before dropping
+                return self.try_recv();
+            }
+            backoff.backoff();
+        }
+    }
+
+    /// A blocking iterator over the received items. See [`Iter`].
+    fn iter(&mut self) -> Iter<'_, Self>
+    where
+        Self: Sized,
+    {
+        Iter { receiver: self }
+    }
+
+    /// A non-blocking iterator over the items available now. See [`TryIter`].
+    fn try_iter(&mut self) -> TryIter<'_, Self>
+    where
+        Self: Sized,
+    {
+        TryIter { receiver: self }
+    }
+}
+
+/// A blocking iterator over a borrowed receiver.
+///
+/// Each [`next`](Iterator::next) waits for an item, and returns `None` once
+/// the queue is empty and every sender has been dropped. See the
+/// [module documentation](self).
+pub struct Iter<'a, R> {
+    receiver: &'a mut R,
+}
+
+impl<R: Receive> Iterator for Iter<'_, R> {
+    type Item = R::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv_until_disconnected()
+    }
+}
+
+/// A non-blocking iterator over a borrowed receiver, ending at the first
+/// empty poll.
+///
+/// Items that arrive while it runs are yielded too. Once it has returned
+/// `None`, a later `next` may yield again if more items arrived.
+pub struct TryIter<'a, R> {
+    receiver: &'a mut R,
+}
+
+impl<R: Receive> Iterator for TryIter<'_, R> {
+    type Item = R::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.try_recv()
+    }
+}
+
+/// A blocking iterator that owns its receiver. Ends like [`Iter`].
+pub struct IntoIter<R> {
+    receiver: R,
+}
+
+impl<R> IntoIter<R> {
+    pub(crate) fn new(receiver: R) -> Self {
+        Self { receiver }
+    }
+
+    /// Gives the receiver back.
+    pub fn into_inner(self) -> R {
+        self.receiver
+    }
+}
+
+impl<R: Receive> Iterator for IntoIter<R> {
+    type Item = R::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv_until_disconnected()
+    }
+}
+
+/// The `try_iter` of the SPSC and sharded receivers: a non-blocking iterator
+/// that reads through a [`ReadGuard`].
+///
+/// Items are moved out of the ring one by one, but the head is only published
+/// when a run is used up (or the iterator is dropped), and then the next run
+/// is read: whatever arrived since, or the next non-empty shard. Items not
+/// yet yielded when it is dropped stay in the queue.
+pub struct Drain<'a, R: BatchReader> {
+    guard: ReadGuard<'a, R>,
+}
+
+impl<'a, R: BatchReader> Drain<'a, R> {
+    pub(crate) fn new(receiver: &'a mut R) -> Self {
+        Self {
+            guard: ReadGuard::new(receiver),
+        }
+    }
+}
+
+impl<R: BatchReader> Iterator for Drain<'_, R> {
+    type Item = R::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.guard.next() {
+            return Some(item);
+        }
+        if self.guard.refill() {
+            self.guard.next()
+        } else {
+            None
+        }
+    }
+}
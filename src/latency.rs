@@ -0,0 +1,127 @@
+//! Time-in-queue tracing.
+//!
+//! Under the `latency` feature, SPSC rings and shards carry a [`Stamps`] array
+//! next to their slots. The producer cursor writes the publish time into a
+//! slot's stamp before it publishes the tail; the consumer cursor reads it back
+//! before it publishes the head and hands the difference to the receiver's
+//! [`LatencySink`]. The tail/head release-acquire pairs that hand over the slot
+//! hand over its stamp too, so the stamps themselves are relaxed atomics.
+//! The array is a separate allocation from the global allocator, whichever
+//! allocator the ring itself came from.
+//!
+//! Without the feature [`Tracer`] is zero-sized and its methods are empty.
+
+#[cfg(feature = "latency")]
+use std::time::{Duration, Instant};
+
+use crate::ring::RingPtr;
+#[cfg(feature = "latency")]
+use crate::{
+    Arc, Box,
+    atomic::{AtomicU64, Ordering},
+};
+
+/// Receives the time each item spent in the queue, from publish to receive.
+///
+/// Install one with a receiver's `set_latency_sink`. It is called on the
+/// receiving thread, once per item, before the slot is handed back to the
+/// sender, so it should be cheap: bump a histogram bucket, not log a line.
+///
+/// # Examples
+///
+/// ```
+/// use core::{num::NonZeroUsize, sync::atomic::{AtomicU64, Ordering}};
+/// use std::{sync::Arc, time::Duration};
+///
+/// use gil::{LatencySink, spsc::channel};
+///
+/// #[derive(Default)]
+/// struct Max(AtomicU64);
+///
+/// impl LatencySink for Max {
+///     fn record(&self, dwell: Duration) {
+///         self.0.fetch_max(dwell.as_nanos() as u64, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let max = Arc::new(Max::default());
+/// let (mut tx, mut rx) = channel::<u32>(NonZeroUsize::new(16).unwrap());
+/// rx.set_latency_sink(max.clone());
+///
+/// tx.send(1);
+/// rx.recv();
+/// println!("worst dwell: {}ns", max.0.load(Ordering::Relaxed));
+/// ```
+#[cfg(feature = "latency")]
+pub trait LatencySink: Send + Sync {
+    /// Records one item's dwell time.
+    fn record(&self, dwell: Duration);
+}
+
+/// Per-slot publish times, in nanoseconds since `origin`.
+#[cfg(feature = "latency")]
+pub(crate) struct Stamps {
+    origin: Instant,
+    slots: Box<[AtomicU64]>,
+}
+
+#[cfg(feature = "latency")]
+impl Stamps {
+    /// Stamps for a ring of `capacity` slots (a power of two).
+    pub(crate) fn new(capacity: usize) -> Self {
+        debug_assert!(capacity.is_power_of_two());
+        Self {
+            origin: Instant::now(),
+            slots: (0..capacity).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    #[inline]
+    fn now(&self) -> u64 {
+        self.origin.elapsed().as_nanos() as u64
+    }
+
+    #[inline]
+    fn slot(&self, index: usize) -> &AtomicU64 {
+        &self.slots[index & (self.slots.len() - 1)]
+    }
+
+    /// Stamps `n` slots from the raw cursor `from` with the current time.
+    #[inline]
+    pub(crate) fn publish(&self, from: usize, n: usize) {
+        let now = self.now();
+        for i in 0..n {
+            self.slot(from.wrapping_add(i))
+                .store(now, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The consumer side's sink, if one is installed.
+#[derive(Clone, Default)]
+pub(crate) struct Tracer {
+    #[cfg(feature = "latency")]
+    sink: Option<Arc<dyn LatencySink>>,
+}
+
+#[cfg_attr(not(feature = "latency"), allow(unused_variables))]
+impl Tracer {
+    #[cfg(feature = "latency")]
+    pub(crate) fn set(&mut self, sink: Arc<dyn LatencySink>) {
+        self.sink = Some(sink);
+    }
+
+    /// Reports the `n` items from the raw cursor `from`. Must run before the
+    /// head moves past them, while their stamps are still ours to read.
+    #[inline(always)]
+    pub(crate) fn receive<R: RingPtr>(&self, ring: &R, from: usize, n: usize) {
+        #[cfg(feature = "latency")]
+        if let (Some(sink), Some(stamps)) = (&self.sink, ring.stamps()) {
+            let now = stamps.now();
+            for i in 0..n {
+                let stamp = stamps.slot(from.wrapping_add(i)).load(Ordering::Relaxed);
+                sink.record(Duration::from_nanos(now.saturating_sub(stamp)));
+            }
+        }
+    }
+}
@@ -1,12 +1,14 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "alloc")]
 extern crate alloc as alloc_crate;
 #[cfg(any(test, feature = "std"))]
 extern crate std;
 
+#[cfg(feature = "alloc")]
 pub(crate) use alloc_crate::boxed::Box;
-#[cfg(not(feature = "loom"))]
+#[cfg(all(feature = "alloc", not(feature = "loom")))]
 pub(crate) use alloc_crate::{alloc, sync::Arc};
 
 #[expect(unused_imports)]
@@ -40,19 +42,61 @@ pub(crate) mod alloc {
     pub use loom::alloc::dealloc;
 }
 
+#[cfg(feature = "alloc")]
+mod allocator;
 mod backoff;
+#[cfg(feature = "alloc")]
 mod cell;
+#[cfg(all(feature = "std", not(feature = "loom")))]
+pub mod delay;
+#[cfg(feature = "alloc")]
+pub mod deque;
+#[cfg(feature = "alloc")]
+pub mod disruptor;
+mod emplace;
 #[cfg(feature = "std")]
 pub(crate) mod futex;
+#[cfg(all(feature = "huge-pages", target_os = "linux", not(feature = "loom")))]
+mod huge_pages;
+pub mod iter;
+pub(crate) mod latency;
+#[cfg(all(feature = "mirror", target_os = "linux", not(feature = "loom")))]
+mod mirror;
+#[cfg(feature = "alloc")]
 pub mod mpmc;
+#[cfg(feature = "alloc")]
 pub mod mpsc;
+#[cfg(feature = "alloc")]
+pub mod oneshot;
+#[cfg(feature = "alloc")]
+mod overwrite;
 mod padded;
+#[cfg(feature = "alloc")]
 pub(crate) mod queue;
 pub mod read_guard;
+#[cfg(feature = "alloc")]
+pub mod rendezvous;
 pub(crate) mod ring;
+#[cfg(feature = "alloc")]
 pub(crate) mod shard_table;
+#[cfg(all(feature = "shm", target_os = "linux", not(feature = "loom")))]
+mod shm;
+#[cfg(feature = "alloc")]
 pub mod spmc;
 pub mod spsc;
+pub(crate) mod stats;
+#[cfg(feature = "alloc")]
+pub mod watch;
+pub mod write_guard;
 
+#[cfg(feature = "alloc")]
+pub use allocator::{Allocator, Global};
 pub use backoff::*;
+#[cfg(all(feature = "huge-pages", target_os = "linux", not(feature = "loom")))]
+pub use huge_pages::HugePages;
+#[cfg(feature = "latency")]
+pub use latency::LatencySink;
+#[cfg(feature = "alloc")]
 pub(crate) use queue::*;
+#[cfg(feature = "stats")]
+pub use stats::Stats;
@@ -0,0 +1,110 @@
+//! Double-mapped ring buffers.
+//!
+//! A mirrored queue is one memfd holding the header and the buffer, mapped
+//! so the buffer shows up a second time right after itself:
+//!
+//! ```text
+//! | header | buffer | buffer (same pages) |
+//! ```
+//!
+//! Writing slot `capacity + i` lands in slot `i`, so a run of slots that wraps
+//! the end of the buffer is still one contiguous slice. The buffer has to be a
+//! whole number of pages for the second view to line up.
+
+use core::ptr::NonNull;
+
+use crate::{alloc::Layout, allocator::Release};
+
+/// The system page size.
+pub(crate) fn page_size() -> usize {
+    // SAFETY: no preconditions.
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+/// Maps `header` bytes followed by `buffer` bytes, then the same `buffer`
+/// bytes again. Both lengths must be page multiples.
+///
+/// Returns the start of the mapping and the record that unmaps it, or `None`
+/// if any step fails.
+pub(crate) fn map(header: usize, buffer: usize) -> Option<(NonNull<u8>, Release)> {
+    let file_len = header.checked_add(buffer)?;
+    let len = file_len.checked_add(buffer)?;
+
+    // SAFETY: the name is a valid C string.
+    let fd = unsafe { libc::memfd_create(c"gil-mirror".as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return None;
+    }
+    let ptr = map_fd(fd, header, file_len, len);
+    // the mappings keep the file alive
+    // SAFETY: `fd` is ours and not used after this.
+    unsafe { libc::close(fd) };
+
+    let layout = Layout::from_size_align(len, page_size()).ok()?;
+    Some((ptr?, Release::custom(layout, unmap)))
+}
+
+fn map_fd(fd: libc::c_int, header: usize, file_len: usize, len: usize) -> Option<NonNull<u8>> {
+    let buffer = len - file_len;
+    // SAFETY: `fd` is a fresh memfd; every mapping below is either fresh or
+    // replaces part of the reservation made here.
+    unsafe {
+        if libc::ftruncate(fd, file_len as libc::off_t) != 0 {
+            return None;
+        }
+
+        // reserve the whole range first so the two views land back-to-back
+        let base = libc::mmap(
+            core::ptr::null_mut(),
+            len,
+            libc::PROT_NONE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+        if base == libc::MAP_FAILED {
+            return None;
+        }
+
+        let rw = libc::PROT_READ | libc::PROT_WRITE;
+        let flags = libc::MAP_SHARED | libc::MAP_FIXED;
+        let whole = libc::mmap(base, file_len, rw, flags, fd, 0);
+        let mirror = libc::mmap(
+            base.byte_add(file_len),
+            buffer,
+            rw,
+            flags,
+            fd,
+            header as libc::off_t,
+        );
+        if whole == libc::MAP_FAILED || mirror == libc::MAP_FAILED {
+            libc::munmap(base, len);
+            return None;
+        }
+        NonNull::new(base.cast())
+    }
+}
+
+unsafe fn unmap(block: NonNull<u8>, layout: Layout, _offset: usize) {
+    // SAFETY: `block` is a whole mapping of this length, made by `map`.
+    unsafe { libc::munmap(block.as_ptr().cast(), layout.size()) };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn views_alias() {
+        let page = page_size();
+        let (ptr, release) = map(page, 2 * page).unwrap();
+        unsafe {
+            let buffer = ptr.add(page);
+            buffer.write(7);
+            assert_eq!(buffer.add(2 * page).read(), 7);
+            buffer.add(4 * page - 1).write(9);
+            assert_eq!(buffer.add(2 * page - 1).read(), 9);
+            release.release(ptr);
+        }
+    }
+}
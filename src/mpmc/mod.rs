@@ -46,11 +46,18 @@
 //! - **Cloneability:** Both [`Sender`] and [`Receiver`] implement `Clone`. Clone them to distribute
 //!   to multiple producer and consumer threads.
 //! - **No Async:** Unlike SPSC, this queue does not have async support.
-//! - **No Batch Operations:** The non-sharded variant does not support batch operations. Use
+//! - **Copying Batch Reads:** [`Receiver::recv_many`] and [`Receiver::read_guard`] take a
+//!   whole run of ready items at once, but cells aren't contiguous, so the items are moved
+//!   into a receiver-owned buffer rather than read in place. Use
 //!   [`sharded`] if you need zero-copy batch operations.
-//! - **Capacity Rounding:** The usable capacity is rounded up to the next power of two
-//!   (e.g. requesting 1000 yields a queue that holds 1024 items). This differs from the SPSC
-//!   queue, which holds exactly the requested capacity.
+//! - **Capacity Rounding:** The usable capacity is rounded up to the next power of two, and to
+//!   at least 2 (e.g. requesting 1000 yields a queue that holds 1024 items). This differs from
+//!   the SPSC queue, which holds exactly the requested capacity.
+//! - **Panicking Constructors:** A sender claims its cell before [`Sender::send_with`] runs the
+//!   closure, and the claim can't be handed back to the other senders. If the closure panics,
+//!   the cell is published as skipped: the receiver steps over it, and nothing is sent.
+//!   [`Sender::try_send_iter`] and [`Sender::send_all`] do the same with cells claimed for an
+//!   iterator that panics or runs dry early.
 //!
 //! # Reference
 //!
@@ -58,6 +65,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 pub use self::{receiver::Receiver, sender::Sender};
 
 mod queue;
@@ -86,7 +95,28 @@ pub mod sharded;
 /// let (tx, rx) = channel::<usize>(NonZeroUsize::new(1024).unwrap());
 /// ```
 pub fn channel<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
-    let queue = queue::QueuePtr::with_size(capacity);
+    channel_in(capacity, Global)
+}
+
+/// Like [`channel`], but allocates the queue from `allocator`.
+///
+/// The allocator moves into the queue and frees it once the last handle drops.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, mpmc::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(NonZeroUsize::new(1024).unwrap(), Global);
+/// ```
+pub fn channel_in<T, A: Allocator + Send + 'static>(
+    capacity: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    // a skipped cell's epoch must not read as published, which takes two cells
+    let capacity = capacity.max(NonZeroUsize::new(2).unwrap());
+    let queue = queue::QueuePtr::with_size_in(capacity, allocator);
     queue.initialize::<crate::cell::CellInit<T>>();
 
     (Sender::new(queue.clone()), Receiver::new(queue))
@@ -270,4 +300,199 @@ mod test {
             count
         );
     }
+
+    #[test]
+    fn send_with_try_full() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(2).unwrap());
+        tx.send_with(|slot| slot.write(1));
+        assert!(tx.try_send_with(|slot| slot.write(2)).is_ok());
+        assert!(tx.try_send_with(|_| unreachable!()).is_err());
+        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv(), 2);
+    }
+
+    #[test]
+    fn send_with_panic_skips_cell() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(1).unwrap());
+        assert_eq!(tx.capacity(), 2);
+
+        for round in 0..3 {
+            tx.send(round);
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                tx.send_with(|_| panic!("boom"));
+            }));
+            assert!(result.is_err());
+
+            assert_eq!(rx.try_recv(), Some(round));
+            // the skipped cell is free again, so the queue still holds two,
+            // one of them a lap ahead of the receiver
+            assert!(tx.try_send(10 + round).is_ok());
+            assert!(tx.try_send(20 + round).is_ok());
+            assert_eq!(tx.try_send(99), Err(99));
+
+            let mut out = std::vec::Vec::new();
+            assert_eq!(rx.try_recv_many(&mut out, 4), 2);
+            assert_eq!(out, [10 + round, 20 + round]);
+            assert_eq!(rx.try_recv(), None);
+        }
+    }
+
+    #[test]
+    fn try_send_iter_skips_unfilled_cells() {
+        /// Promises more items than it yields.
+        struct Short(core::ops::Range<usize>);
+
+        impl Iterator for Short {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                self.0.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (4, None)
+            }
+        }
+
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+        assert_eq!(tx.try_send_iter(Short(0..2)), 2);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tx.try_send_iter((2..6).map(|i| if i == 4 { panic!("boom") } else { i }));
+        }));
+        assert!(result.is_err());
+
+        // no lower bound: the cell claimed to look for a second item is skipped
+        assert_eq!(tx.try_send_iter((4..5).filter(|_| true)), 1);
+        tx.send(5);
+
+        // batch reads stop at a skipped cell; single reads step over it
+        assert_eq!(
+            rx.try_iter().collect::<std::vec::Vec<_>>(),
+            [0, 1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn send_slice_wraps() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        tx.send_slice(&[0, 1, 2]);
+        assert_eq!(rx.recv(), 0);
+        assert_eq!(rx.recv(), 1);
+
+        // tail at 3, two free cells after it and one at the front
+        tx.send_slice(&[3, 4, 5]);
+        assert_eq!(tx.len(), 4);
+        for i in 2..6 {
+            assert_eq!(rx.recv(), i);
+        }
+        #[cfg(feature = "stats")]
+        assert_eq!(tx.stats().sent, 6);
+    }
+
+    #[test]
+    fn len() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(3).unwrap());
+        assert!(tx.is_empty() && rx.is_empty());
+        assert_eq!((tx.capacity(), rx.capacity()), (4, 4));
+
+        for i in 0..4 {
+            tx.send(i);
+            assert_eq!((tx.len(), rx.len()), (i + 1, i + 1));
+        }
+        assert!(tx.is_full() && rx.is_full());
+
+        rx.recv();
+        rx.recv();
+        assert_eq!((tx.len(), rx.len()), (2, 2));
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats_are_per_handle() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        tx.send(1);
+        tx.send(2);
+
+        let mut tx2 = tx.clone();
+        assert_eq!(tx2.stats().sent, 0);
+        tx2.send(3);
+        assert_eq!((tx.stats().sent, tx2.stats().sent), (2, 1));
+
+        for _ in 0..3 {
+            rx.recv();
+        }
+        assert_eq!(rx.stats().received, 3);
+    }
+
+    #[test]
+    fn recv_many_concurrent() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        const SENDERS: usize = 4;
+        const RECEIVERS: usize = 4;
+        const MESSAGES: usize = 1000;
+
+        let (tx, rx) = channel(NonZeroUsize::new(64).unwrap());
+        let total_received = AtomicUsize::new(0);
+        let total_sum = AtomicUsize::new(0);
+
+        thread::scope(|s| {
+            for t in 0..SENDERS {
+                let mut tx = tx.clone();
+                s.spawn(move || {
+                    for i in 0..MESSAGES {
+                        tx.send(t * MESSAGES + i);
+                    }
+                });
+            }
+
+            for r in 0..RECEIVERS {
+                let mut rx = rx.clone();
+                let (total_received, total_sum) = (&total_received, &total_sum);
+                s.spawn(move || {
+                    let mut buf = std::vec::Vec::new();
+                    while total_received.load(Ordering::SeqCst) < SENDERS * MESSAGES {
+                        buf.clear();
+                        // alternate between the two batch paths
+                        if r % 2 == 0 {
+                            rx.try_recv_many(&mut buf, 16);
+                        } else {
+                            buf.extend(rx.read_guard());
+                        }
+                        total_sum.fetch_add(buf.iter().sum(), Ordering::SeqCst);
+                        total_received.fetch_add(buf.len(), Ordering::SeqCst);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(total_received.load(Ordering::SeqCst), SENDERS * MESSAGES);
+        let n = SENDERS * MESSAGES;
+        assert_eq!(total_sum.load(Ordering::SeqCst), n * (n - 1) / 2);
+    }
+
+    #[test]
+    fn iter_ends_for_parked_receivers() {
+        let (mut tx, rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+
+        thread::scope(|scope| {
+            let handles: std::vec::Vec<_> = (0..2)
+                .map(|_| {
+                    let rx = rx.clone();
+                    scope.spawn(move || rx.into_iter().count())
+                })
+                .collect();
+            drop(rx);
+
+            for i in 0..100 {
+                tx.send(i);
+            }
+            // let the receivers park before the disconnect has to wake them
+            thread::sleep(core::time::Duration::from_millis(20));
+            drop(tx);
+
+            let received: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+            assert_eq!(received, 100);
+        });
+    }
 }
@@ -2,7 +2,7 @@
 use crate::{atomic::AtomicU32, futex::HasFutex};
 use crate::{
     atomic::AtomicUsize,
-    cell::{Cell, DropTailScan},
+    cell::{Cell, Cells, DropTailScan},
     padded::Padded,
     ring::{RingHead, RingTail},
 };
@@ -64,3 +64,30 @@ impl<T> QueuePtr<T> {
         unsafe { self.at(index).as_ref() }
     }
 }
+
+impl<T> Cells for QueuePtr<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        QueuePtr::tail(self)
+    }
+
+    #[inline(always)]
+    fn cell_at(&self, ticket: usize) -> &Cell<T> {
+        QueuePtr::cell_at(self, ticket)
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// A receiver asleep on an empty queue may be waiting behind the skipped
+    /// cells for items already published after them.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn skipped(&self) {
+        self.futex().wake_all();
+    }
+}
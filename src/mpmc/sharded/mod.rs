@@ -55,6 +55,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 mod receiver;
 mod sender;
 use crate::shard_table::ShardTable;
@@ -90,7 +92,32 @@ pub fn channel<T>(
     max_shards: NonZeroUsize,
     capacity_per_shard: NonZeroUsize,
 ) -> (sender::Sender<T>, receiver::Receiver<T>) {
-    let table = ShardTable::new(max_shards, capacity_per_shard);
+    channel_in(max_shards, capacity_per_shard, Global)
+}
+
+/// Like [`channel`], but allocates every shard from a clone of `allocator`.
+///
+/// Each shard frees itself through its clone once the last endpoint using it
+/// drops, so the clones may outlive one another.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, mpmc::sharded::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(
+///     NonZeroUsize::new(4).unwrap(),
+///     NonZeroUsize::new(256).unwrap(),
+///     Global,
+/// );
+/// ```
+pub fn channel_in<T, A: Allocator + Clone + Send + 'static>(
+    max_shards: NonZeroUsize,
+    capacity_per_shard: NonZeroUsize,
+    allocator: A,
+) -> (sender::Sender<T>, receiver::Receiver<T>) {
+    let table = ShardTable::new_in(max_shards, capacity_per_shard, allocator);
 
     let receiver = receiver::Receiver::new(&table);
     let sender = sender::Sender::new(table);
@@ -322,6 +349,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn read_guard_spans_the_wrap() {
+        let (mut tx, mut rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        tx.send_slice(&[0, 1, 2]);
+        rx.recv();
+        rx.recv();
+        tx.send_slice(&[3, 4]);
+
+        let mut rx2 = rx.try_clone().unwrap();
+        let mut guard = rx.read_guard();
+        assert_eq!(guard.as_slices(), (&[2, 3][..], &[4][..]));
+        // the shard stays locked for the whole guard
+        assert_eq!(rx2.try_recv(), None);
+        guard.advance(3);
+        drop(guard);
+
+        tx.send(5);
+        assert_eq!(rx2.try_recv(), Some(5));
+    }
+
     #[test]
     fn test_drop_full_capacity() {
         use std::sync::Arc;
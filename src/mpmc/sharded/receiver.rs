@@ -6,6 +6,7 @@ use crate::{
     read_guard::BatchReader,
     ring::Consumer,
     shard_table::{Cursor, Shard, ShardTable},
+    stats::Counters,
     sync::atomic::{AtomicBool, AtomicUsize, Ordering},
 };
 
@@ -15,6 +16,9 @@ struct Shared<T> {
     /// Live receiver count; only bounds `try_clone` at one receiver per shard —
     /// the `Arc` holding this struct owns the memory.
     alive_receivers: AtomicUsize,
+    /// Read-only view of every shard for `len()`; the consumers above may be
+    /// locked by another receiver.
+    table: ShardTable<T>,
 }
 
 impl<T> Shared<T> {
@@ -64,6 +68,9 @@ impl<T> Shared<T> {
 pub struct Receiver<T> {
     shared: Arc<Shared<T>>,
     cursor: Cursor,
+    /// This receiver's own counts; the consumers' counters are shared by
+    /// every receiver that locks them.
+    stats: Counters,
 }
 
 impl<T> Receiver<T> {
@@ -81,8 +88,10 @@ impl<T> Receiver<T> {
                 consumers,
                 locks,
                 alive_receivers: AtomicUsize::new(1),
+                table: table.clone(),
             }),
             cursor: Cursor::new(table.len()),
+            stats: Counters::default(),
         }
     }
 
@@ -129,6 +138,7 @@ impl<T> Receiver<T> {
         Some(Self {
             shared: Arc::clone(&self.shared),
             cursor: Cursor::new(self.shared.max_shards()),
+            stats: Counters::default(),
         })
     }
 
@@ -234,13 +244,30 @@ impl<T> Receiver<T> {
 
         // SAFETY: the scan left this shard locked for us.
         let consumer = unsafe { &mut *shared.consumers[shard_idx].get() };
+        self.stats.observe(|| consumer.len());
         let value = consumer.pop();
+        self.stats.received(1);
         // SAFETY: locked in the scan; single unlock.
         unsafe { shared.unlock(shard_idx) };
 
         Some(value)
     }
 
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// every sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, ending once the
+    /// queue is empty. Reads through the batch path, so the head is published
+    /// once per run of items rather than per item. See
+    /// [`Drain`](crate::iter::Drain).
+    pub fn try_iter(&mut self) -> crate::iter::Drain<'_, Self> {
+        crate::iter::Drain::new(self)
+    }
+
     /// Returns a [`ReadGuard`](crate::read_guard::ReadGuard) providing read
     /// access to a batch of elements from the channel.
     ///
@@ -273,6 +300,81 @@ impl<T> Receiver<T> {
     pub fn read_guard(&mut self) -> crate::read_guard::ReadGuard<'_, Self> {
         crate::read_guard::ReadGuard::new(self)
     }
+
+    /// Total number of items across all shards.
+    ///
+    /// Each shard is read on its own while other handles keep sending and
+    /// receiving, so the sum is approximate. See [`shard_lens`](Self::shard_lens)
+    /// for the per-shard breakdown.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shard_lens().sum()
+    }
+
+    /// Returns `true` if every shard is empty. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if every shard is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once every [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.shared.table.is_disconnected()
+    }
+
+    /// Total capacity: `max_shards * capacity_per_shard`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.shared.table.len() * self.shared.table.shard_capacity()
+    }
+
+    /// Number of items in each shard, in shard index order. Each entry is a
+    /// snapshot, as with [`len`](Self::len).
+    pub fn shard_lens(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.shared.table.shard_lens()
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.stats.snapshot()
+    }
+
+    /// Reports how long each item spent in the queue to `sink`, from the
+    /// moment it was published to the moment it is received.
+    ///
+    /// Receivers share the shards, so the sink applies to every receiver of
+    /// this channel, current and future. Waits for each shard's lock in turn,
+    /// including any held by another receiver's
+    /// [`ReadGuard`](crate::read_guard::ReadGuard).
+    ///
+    /// Replaces any sink installed earlier. See [`LatencySink`](crate::LatencySink).
+    #[cfg(feature = "latency")]
+    pub fn set_latency_sink(&mut self, sink: Arc<dyn crate::LatencySink>) {
+        let shared = &*self.shared;
+        for shard_idx in 0..shared.max_shards() {
+            let mut backoff = Backoff::with_spin_count(128);
+            while !shared.try_lock(shard_idx) {
+                backoff.backoff();
+            }
+            // SAFETY: we hold this shard's lock.
+            unsafe {
+                (*shared.consumers[shard_idx].get())
+                    .tracer
+                    .set(sink.clone())
+            };
+            // SAFETY: locked above; single unlock.
+            unsafe { shared.unlock(shard_idx) };
+        }
+    }
 }
 
 /// # Safety
@@ -324,6 +426,12 @@ unsafe impl<T> BatchReader for Receiver<T> {
     /// assert_eq!(rx.try_recv(), None);
     /// ```
     fn read_buffer(&mut self) -> &[T] {
+        self.read_slices().0
+    }
+
+    /// Like [`read_buffer`](BatchReader::read_buffer), plus the locked shard's
+    /// items that wrapped to the start of its ring.
+    fn read_slices(&mut self) -> (&[T], &[T]) {
         let shared = &*self.shared;
         let found = self.cursor.find(|shard_idx| {
             if !shared.try_lock(shard_idx) {
@@ -333,22 +441,22 @@ unsafe impl<T> BatchReader for Receiver<T> {
             // SAFETY: we hold this shard's lock.
             let consumer = unsafe { &mut *shared.consumers[shard_idx].get() };
             consumer.resync();
-            let (ptr, len) = consumer.read_buffer_raw();
-            if len == 0 {
+            let runs = consumer.read_slices_raw();
+            if runs[0].1 == 0 {
                 // SAFETY: locked above; single unlock.
                 unsafe { shared.unlock(shard_idx) };
                 return None;
             }
 
             // keep the lock; `release` (via ReadGuard drop) unlocks
-            Some((ptr, len))
+            Some(runs)
         });
 
         match found {
             // SAFETY: raw parts of the locked shard's ring, which `self` keeps
-            // alive; rebuilt here only so the slice outlives the scan closure.
-            Some((ptr, len)) => unsafe { core::slice::from_raw_parts(ptr.as_ptr(), len) },
-            None => &[],
+            // alive; rebuilt here only so the slices outlive the scan closure.
+            Some(runs) => unsafe { crate::ring::slices_from_raw(runs) },
+            None => (&[], &[]),
         }
     }
 
@@ -356,6 +464,7 @@ unsafe impl<T> BatchReader for Receiver<T> {
         // SAFETY (deref): `read_buffer` left this shard locked for us.
         let consumer = unsafe { &mut *self.shared.consumers[self.cursor.index()].get() };
         unsafe { consumer.advance(n) };
+        self.stats.received(n);
     }
 
     /// Releases the shard spinlock acquired by
@@ -377,4 +486,41 @@ impl<T> Drop for Receiver<T> {
     }
 }
 
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 unsafe impl<T: Send> Send for Receiver<T> {}
@@ -35,6 +35,12 @@ pub struct Sender<T> {
     shard_idx: usize,
 }
 
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.table.detach_sender();
+    }
+}
+
 impl<T> Sender<T> {
     /// Attempts to clone the sender.
     ///
@@ -70,6 +76,7 @@ impl<T> Sender<T> {
 
     fn init(table: ShardTable<T>, start: usize) -> Option<Self> {
         let (shard_idx, shard) = table.claim_producer(start)?;
+        table.attach_sender();
         Some(Self {
             producer: Producer::attach(shard),
             table,
@@ -96,12 +103,58 @@ impl<T> Sender<T> {
     /// ```
     pub fn send(&mut self, value: T) {
         // Spin until the shard has space, then move the value straight into the ring.
+        self.wait_for_space();
+        self.producer.push(value);
+    }
+
+    /// Constructs the next item directly in the shard's tail slot, spinning
+    /// while the shard is full.
+    ///
+    /// `f` is called once a slot is free and gets the uninitialised slot, which
+    /// saves [`send`](Sender::send)'s stack-to-ring copy for large `T`. The item
+    /// is published when `f` returns; if `f` panics, nothing is sent.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpmc::sharded::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<[u32; 256]>(
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     NonZeroUsize::new(4).unwrap(),
+    /// );
+    /// tx.send_with(|slot| slot.write([3; 256]));
+    /// assert_eq!(rx.recv()[255], 3);
+    /// ```
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        self.wait_for_space();
+        unsafe { self.producer.push_with(f) };
+    }
+
+    /// Non-blocking [`send_with`](Sender::send_with). Returns `Err(f)` without
+    /// calling it if the shard is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.producer.try_push_with(f)
+    }
+
+    fn wait_for_space(&mut self) {
+        if self.producer.has_space() {
+            return;
+        }
+
         let mut backoff = crate::Backoff::with_spin_count(128);
         while self.producer.is_full() {
             backoff.backoff();
             self.producer.refresh_head();
         }
-        self.producer.push(value);
     }
 
     /// Attempts to send a value into the channel without blocking.
@@ -155,6 +208,12 @@ impl<T> Sender<T> {
         self.producer.write_buffer()
     }
 
+    /// Like [`write_buffer`](Self::write_buffer), plus the shard's free slots
+    /// that wrap to the start of its ring. Fill the first slice first.
+    pub fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        self.producer.write_slices()
+    }
+
     /// Commits `len` elements from the write buffer to the channel.
     ///
     /// # Safety
@@ -181,10 +240,137 @@ impl<T> Sender<T> {
     pub unsafe fn commit(&mut self, len: usize) {
         unsafe { self.producer.commit(len) }
     }
+
+    /// Returns a [`WriteGuard`](crate::write_guard::WriteGuard) over the free
+    /// slots of this sender's shard: a safe alternative to
+    /// [`write_buffer`](Self::write_buffer) and [`commit`](Self::commit).
+    ///
+    /// The guard commits exactly the items written through it when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpmc::sharded::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<usize>(
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     NonZeroUsize::new(128).unwrap(),
+    /// );
+    ///
+    /// let mut guard = tx.write_guard();
+    /// guard.extend([10, 20]);
+    /// drop(guard);
+    ///
+    /// assert_eq!(rx.recv(), 10);
+    /// assert_eq!(rx.recv(), 20);
+    /// ```
+    pub fn write_guard(&mut self) -> crate::write_guard::WriteGuard<'_, Self> {
+        crate::write_guard::WriteGuard::new(self)
+    }
+
+    /// Sends every item of `values`, blocking while the shard is full.
+    ///
+    /// Copies straight into the ring and publishes each contiguous run of free
+    /// slots at once, rather than item by item.
+    pub fn send_slice(&mut self, mut values: &[T])
+    where
+        T: Copy,
+    {
+        loop {
+            let sent = self.producer.push_slice(values);
+            values = &values[sent..];
+            if values.is_empty() {
+                return;
+            }
+            self.wait_for_space();
+        }
+    }
+
+    /// Sends items from `iter` until it runs dry or the shard fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// Only items there is room for are taken from `iter`; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let (Ok(sent) | Err(sent)) = self.producer.push_iter(&mut iter);
+        sent
+    }
+
+    /// Sends every item of `iter`, blocking while the shard is full.
+    ///
+    /// Like [`send_slice`](Self::send_slice), items are published a run of
+    /// free slots at a time.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        let mut iter = iter.into_iter().peekable();
+        loop {
+            let pushed = self.producer.push_iter(&mut iter);
+            if pushed.is_ok() || iter.peek().is_none() {
+                return;
+            }
+            self.wait_for_space();
+        }
+    }
+
+    /// Total number of items across all shards.
+    ///
+    /// Each shard is read on its own while other handles keep sending and
+    /// receiving, so the sum is approximate. See [`shard_lens`](Self::shard_lens)
+    /// for the per-shard breakdown.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shard_lens().sum()
+    }
+
+    /// Returns `true` if every shard is empty. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if every shard is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Total capacity: `max_shards * capacity_per_shard`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.table.len() * self.table.shard_capacity()
+    }
+
+    /// Number of items in each shard, in shard index order. Each entry is a
+    /// snapshot, as with [`len`](Self::len).
+    pub fn shard_lens(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.table.shard_lens()
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.producer.stats.snapshot()
+    }
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
 
+unsafe impl<T> crate::write_guard::BatchWriter for Sender<T> {
+    type Item = T;
+
+    fn write_buffer(&mut self) -> &mut [MaybeUninit<T>] {
+        Sender::write_buffer(self)
+    }
+
+    fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        Sender::write_slices(self)
+    }
+
+    unsafe fn commit(&mut self, n: usize) {
+        unsafe { Sender::commit(self, n) }
+    }
+}
+
 #[cfg(all(test, not(feature = "loom")))]
 mod test {
     use core::num::NonZeroUsize;
@@ -41,11 +41,18 @@
 //! - **Cloneability:** [`Sender`] implements `Clone`, but [`Receiver`] does not. Clone senders to
 //!   distribute to multiple producer threads.
 //! - **No Async:** Unlike SPSC, this queue does not have async support.
-//! - **No Batch Operations:** The non-sharded variant does not support batch operations. Use
+//! - **Copying Batch Reads:** [`Receiver::recv_many`] and [`Receiver::read_guard`] take a
+//!   whole run of ready items at once, but cells aren't contiguous, so the items are moved
+//!   into a receiver-owned buffer rather than read in place. Use
 //!   [`sharded`] if you need zero-copy batch operations.
-//! - **Capacity Rounding:** The usable capacity is rounded up to the next power of two
-//!   (e.g. requesting 1000 yields a queue that holds 1024 items). This differs from the SPSC
-//!   queue, which holds exactly the requested capacity.
+//! - **Capacity Rounding:** The usable capacity is rounded up to the next power of two, and to
+//!   at least 2 (e.g. requesting 1000 yields a queue that holds 1024 items). This differs from
+//!   the SPSC queue, which holds exactly the requested capacity.
+//! - **Panicking Constructors:** A sender claims its cell before [`Sender::send_with`] runs the
+//!   closure, and the claim can't be handed back to the other senders. If the closure panics,
+//!   the cell is published as skipped: the receiver steps over it, and nothing is sent.
+//!   [`Sender::try_send_iter`] and [`Sender::send_all`] do the same with cells claimed for an
+//!   iterator that panics or runs dry early.
 //!
 //! # Reference
 //!
@@ -53,8 +60,12 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 pub use self::{receiver::Receiver, sender::Sender};
 
+pub mod overwriting;
+pub mod priority;
 mod queue;
 // mod queue_old;
 mod receiver;
@@ -62,6 +73,8 @@ mod sender;
 pub mod sharded;
 #[cfg(feature = "std")]
 pub mod sharded_parking;
+#[cfg(all(feature = "shm", target_os = "linux", not(feature = "loom")))]
+pub mod shm;
 
 /// Creates a new multi-producer single-consumer (MPSC) queue.
 ///
@@ -84,7 +97,28 @@ pub mod sharded_parking;
 /// let (tx, rx) = channel::<usize>(NonZeroUsize::new(1024).unwrap());
 /// ```
 pub fn channel<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
-    let queue = queue::QueuePtr::with_size(capacity);
+    channel_in(capacity, Global)
+}
+
+/// Like [`channel`], but allocates the queue from `allocator`.
+///
+/// The allocator moves into the queue and frees it once the last handle drops.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, mpsc::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(NonZeroUsize::new(1024).unwrap(), Global);
+/// ```
+pub fn channel_in<T, A: Allocator + Send + 'static>(
+    capacity: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    // a skipped cell's epoch must not read as published, which takes two cells
+    let capacity = capacity.max(NonZeroUsize::new(2).unwrap());
+    let queue = queue::QueuePtr::with_size_in(capacity, allocator);
     queue.initialize::<crate::cell::CellInit<T>>();
 
     (Sender::new(queue.clone()), Receiver::new(queue))
@@ -177,4 +211,219 @@ mod test {
             count
         );
     }
+
+    #[test]
+    fn send_with() {
+        const THREADS: u64 = 4;
+        const ITER: u64 = 1000;
+
+        let (tx, mut rx) = channel::<[u64; 64]>(NonZeroUsize::new(4).unwrap());
+
+        thread::scope(move |scope| {
+            for thread_id in 0..THREADS {
+                let mut tx = tx.clone();
+                scope.spawn(move || {
+                    for i in 0..ITER {
+                        tx.send_with(|slot| slot.write([thread_id * ITER + i; 64]));
+                    }
+                });
+            }
+
+            let mut sum = 0;
+            for _ in 0..THREADS * ITER {
+                let item = rx.recv();
+                assert!(item.iter().all(|&x| x == item[0]));
+                sum += item[0];
+            }
+            let n = THREADS * ITER;
+            assert_eq!(sum, n * (n - 1) / 2);
+        });
+    }
+
+    #[test]
+    fn send_with_panic_skips_cell() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(1).unwrap());
+        assert_eq!(tx.capacity(), 2);
+
+        for round in 0..3 {
+            tx.send(round);
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                tx.send_with(|_| panic!("boom"));
+            }));
+            assert!(result.is_err());
+
+            assert_eq!(rx.try_recv(), Some(round));
+            // the skipped cell is free again, so the queue still holds two,
+            // one of them a lap ahead of the receiver
+            assert!(tx.try_send(10 + round).is_ok());
+            assert!(tx.try_send(20 + round).is_ok());
+            assert_eq!(tx.try_send(99), Err(99));
+
+            let mut out = std::vec::Vec::new();
+            assert_eq!(rx.try_recv_many(&mut out, 4), 2);
+            assert_eq!(out, [10 + round, 20 + round]);
+            assert_eq!(rx.try_recv(), None);
+        }
+    }
+
+    #[test]
+    fn send_all() {
+        const THREADS: usize = 4;
+        const ITER: usize = 1000;
+
+        let (tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+
+        thread::scope(move |scope| {
+            for thread_id in 0..THREADS {
+                let mut tx = tx.clone();
+                scope.spawn(move || {
+                    let items = thread_id * ITER..(thread_id + 1) * ITER;
+                    match thread_id % 3 {
+                        0 => tx.send_slice(&items.collect::<std::vec::Vec<_>>()),
+                        // no size_hint lower bound: one cell at a time
+                        1 => tx.send_all(items.filter(|_| true)),
+                        _ => tx.send_all(items),
+                    }
+                });
+            }
+
+            let mut next = [0; THREADS];
+            for _ in 0..THREADS * ITER {
+                let item = rx.recv();
+                let thread_id = item / ITER;
+                assert_eq!(item % ITER, next[thread_id]);
+                next[thread_id] += 1;
+            }
+        });
+    }
+
+    #[test]
+    fn try_send_iter_takes_only_what_fits() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        tx.send(0);
+
+        let mut items = 1..10;
+        assert_eq!(tx.try_send_iter(items.by_ref()), 3);
+        assert_eq!(items.next(), Some(4));
+
+        let mut filtered = (10..20).filter(|_| true);
+        assert_eq!(tx.try_send_iter(filtered.by_ref()), 0);
+        assert_eq!(filtered.next(), Some(10));
+
+        for i in 0..4 {
+            assert_eq!(rx.recv(), i);
+        }
+    }
+
+    #[test]
+    fn try_send_iter_skips_unfilled_cells() {
+        /// Promises more items than it yields.
+        struct Short(core::ops::Range<usize>);
+
+        impl Iterator for Short {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                self.0.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (4, None)
+            }
+        }
+
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+        assert_eq!(tx.try_send_iter(Short(0..2)), 2);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tx.try_send_iter((2..6).map(|i| if i == 4 { panic!("boom") } else { i }));
+        }));
+        assert!(result.is_err());
+
+        // no lower bound: the cell claimed to look for a second item is skipped
+        assert_eq!(tx.try_send_iter((4..5).filter(|_| true)), 1);
+        tx.send(5);
+
+        let mut out = std::vec::Vec::new();
+        assert_eq!(rx.try_recv_many(&mut out, 16), 6);
+        assert_eq!(out, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn len() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(3).unwrap());
+        assert!(tx.is_empty() && rx.is_empty());
+        assert_eq!((tx.capacity(), rx.capacity()), (4, 4));
+
+        for i in 0..4 {
+            tx.send(i);
+            assert_eq!((tx.len(), rx.len()), (i + 1, i + 1));
+        }
+        assert!(tx.is_full() && rx.is_full());
+
+        rx.recv();
+        rx.recv();
+        assert_eq!((tx.len(), rx.len()), (2, 2));
+    }
+
+    #[test]
+    fn read_guard_stages_leftovers() {
+        use std::sync::Arc;
+
+        let (mut tx, mut rx) = channel::<Arc<usize>>(NonZeroUsize::new(4).unwrap());
+        for i in 0..4 {
+            tx.send(Arc::new(i));
+        }
+
+        // the guard takes all four cells, so the senders can refill at once
+        let mut guard = rx.read_guard();
+        assert_eq!(guard.len(), 4);
+        assert_eq!(*guard.next().unwrap(), 0);
+        drop(guard);
+        assert!(tx.try_send(Arc::new(4)).is_ok());
+
+        // leftovers come out first, in order, then the queue
+        let mut out = std::vec::Vec::new();
+        assert_eq!(rx.try_recv_many(&mut out, 2), 2);
+        assert_eq!(rx.try_recv().as_deref(), Some(&3));
+        assert_eq!(rx.recv_many(&mut out, 8), 1);
+        assert_eq!(
+            out.iter().map(|v| **v).collect::<std::vec::Vec<_>>(),
+            [1, 2, 4]
+        );
+
+        // values still staged are dropped with the receiver
+        let item = Arc::new(5);
+        tx.send(item.clone());
+        tx.send(item.clone());
+        let mut guard = rx.read_guard();
+        guard.advance(0);
+        drop(guard);
+        assert_eq!(Arc::strong_count(&item), 3);
+        drop(rx);
+        assert_eq!(Arc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn iter_ends_once_every_sender_drops() {
+        let (tx, rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        assert!(!rx.is_disconnected());
+
+        thread::scope(move |scope| {
+            for thread_id in 0..3 {
+                let mut tx = tx.clone();
+                scope.spawn(move || {
+                    for i in 0..100 {
+                        tx.send(thread_id * 100 + i);
+                    }
+                });
+            }
+            drop(tx);
+
+            let mut received: std::vec::Vec<_> = rx.into_iter().collect();
+            received.sort_unstable();
+            assert!(received.into_iter().eq(0..300));
+        });
+    }
 }
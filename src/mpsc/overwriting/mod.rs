@@ -0,0 +1,156 @@
+//! Overwriting (lossy) variant of the MPSC queue.
+//!
+//! [`Sender::send`] never blocks: when the queue is full, the oldest unread item
+//! is evicted to make room and handed back to the sender that evicted it. The
+//! receiver can ask how many items it missed since its last read via
+//! [`Receiver::overwritten`].
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use core::num::NonZeroUsize;
+//! use gil::mpsc::overwriting::channel;
+//!
+//! let (tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+//!
+//! let handles: Vec<_> = (0..4)
+//!     .map(|t| {
+//!         let mut tx = tx.clone();
+//!         thread::spawn(move || {
+//!             for i in 0..100 {
+//!                 tx.send(t * 100 + i);
+//!             }
+//!         })
+//!     })
+//!     .collect();
+//! for handle in handles {
+//!     handle.join().unwrap();
+//! }
+//!
+//! // only the newest four items survive
+//! let mut received = 0;
+//! while rx.try_recv().is_some() {
+//!     received += 1;
+//! }
+//! assert_eq!(received, 4);
+//! ```
+//!
+//! # Gotchas
+//!
+//! - **Cloneability:** [`Sender`] implements `Clone`, but [`Receiver`] does not.
+//! - **Capacity Rounding:** The usable capacity is rounded up to the next power of two,
+//!   with a minimum of 2.
+//! - **Eviction Races:** A send can race other senders for the slot it freed. The
+//!   item it evicted is still returned, but the sender may have to evict again to
+//!   find room, so one `send` can overwrite more than one item under contention.
+//! - **No In-Place Construction:** There is no `send_with`. A send claims its cell before
+//!   writing it, and a constructor that panicked after the claim would leave a hole that
+//!   neither the receiver nor an evicting sender can step over. Build the value, then `send` it.
+
+use core::num::NonZeroUsize;
+
+pub use self::{receiver::Receiver, sender::Sender};
+
+mod receiver;
+mod sender;
+
+/// Creates a new overwriting multi-producer single-consumer queue.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Arguments
+///
+/// * `capacity` - The capacity of the queue, rounded up to the next power of
+///   two, with a minimum of 2.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::overwriting::channel;
+///
+/// let (tx, rx) = channel::<usize>(NonZeroUsize::new(1024).unwrap());
+/// ```
+pub fn channel<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
+    let queue = crate::overwrite::QueuePtr::with_capacity(capacity);
+
+    (Sender::new(queue.clone()), Receiver::new(queue))
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn every_item_is_received_or_evicted() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        const THREADS: usize = 4;
+        const ITER: usize = 10_000;
+
+        /// Counts drops, so an item evicted and dropped inside a racing send
+        /// is accounted for as well as the one `send` hands back.
+        struct Item<'a>(&'a AtomicUsize);
+
+        impl Drop for Item<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let dropped = AtomicUsize::new(0);
+        let finished = AtomicUsize::new(0);
+        {
+            let (tx, mut rx) = channel::<Item<'_>>(NonZeroUsize::new(8).unwrap());
+
+            thread::scope(|scope| {
+                for _ in 0..THREADS {
+                    let mut tx = tx.clone();
+                    let (dropped, finished) = (&dropped, &finished);
+                    scope.spawn(move || {
+                        for _ in 0..ITER {
+                            drop(tx.send(Item(dropped)));
+                        }
+                        finished.fetch_add(1, Ordering::Release);
+                    });
+                }
+                drop(tx);
+
+                while finished.load(Ordering::Acquire) < THREADS {
+                    drop(rx.try_recv());
+                }
+            });
+
+            while rx.try_recv().is_some() {}
+        }
+        assert_eq!(dropped.load(Ordering::Relaxed), THREADS * ITER);
+    }
+
+    #[test]
+    fn test_drop_full_capacity() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+        {
+            // Request size 3. Capacity will be 4.
+            let (mut tx, _rx) = channel::<DropCounter>(NonZeroUsize::new(3).unwrap());
+            for _ in 0..6 {
+                tx.send(DropCounter(dropped.clone()));
+            }
+            assert_eq!(dropped.load(Ordering::SeqCst), 2);
+        }
+        assert_eq!(dropped.load(Ordering::SeqCst), 6);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::overwrite::{Consumer, QueuePtr};
+
+/// The consumer end of the overwriting MPSC queue.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another thread, but cannot
+/// be shared across threads.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::overwriting::channel;
+///
+/// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+/// tx.send(1);
+/// tx.send(2);
+/// assert_eq!(rx.recv(), 1);
+/// assert_eq!(rx.recv(), 2);
+/// ```
+pub struct Receiver<T> {
+    consumer: Consumer<T>,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn new(queue_ptr: QueuePtr<T>) -> Self {
+        Self {
+            consumer: Consumer::new(queue_ptr),
+        }
+    }
+
+    /// Receives a value from the queue, blocking if necessary.
+    ///
+    /// This method uses a spin loop with a default spin count of 128 to wait
+    /// for available data in the queue. For control over the spin count, use
+    /// [`Receiver::recv_with_spin_count`]. For a non-blocking alternative, use
+    /// [`Receiver::try_recv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::overwriting::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(42);
+    /// assert_eq!(rx.recv(), 42);
+    /// ```
+    pub fn recv(&mut self) -> T {
+        self.recv_with_spin_count(128)
+    }
+
+    /// Receives a value from the queue, blocking if necessary, using a custom spin count.
+    ///
+    /// See [`Backoff`](crate::Backoff) for how `spin_count` trades latency for CPU usage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::overwriting::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(42);
+    /// assert_eq!(rx.recv_with_spin_count(32), 42);
+    /// ```
+    pub fn recv_with_spin_count(&mut self, spin_count: u32) -> T {
+        let mut backoff = crate::Backoff::with_spin_count(spin_count);
+        loop {
+            match self.consumer.try_pop() {
+                Some(ret) => return ret,
+                None => backoff.backoff(),
+            }
+        }
+    }
+
+    /// Attempts to receive a value from the queue without blocking.
+    ///
+    /// Returns `Some(value)` with the oldest item that has not been overwritten, or
+    /// `None` if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::overwriting::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    ///
+    /// assert_eq!(rx.try_recv(), None);
+    ///
+    /// tx.send(42);
+    /// assert_eq!(rx.try_recv(), Some(42));
+    /// assert_eq!(rx.try_recv(), None);
+    /// ```
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.consumer.try_pop()
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// every sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Returns `true` once every [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.consumer.is_disconnected()
+    }
+
+    /// Returns how many items the sender has overwritten since this receiver's
+    /// last successful read.
+    ///
+    /// The count is a snapshot: the sender may evict more items before the next
+    /// read. Call it right before receiving to learn how many items the next read
+    /// skips over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::overwriting::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(2).unwrap());
+    /// for i in 0..5 {
+    ///     tx.send(i);
+    /// }
+    ///
+    /// assert_eq!(rx.overwritten(), 3);
+    /// assert_eq!(rx.recv(), 3);
+    /// assert_eq!(rx.overwritten(), 0);
+    /// ```
+    pub fn overwritten(&self) -> usize {
+        self.consumer.overwritten()
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
@@ -0,0 +1,68 @@
+use crate::overwrite::{Producer, QueuePtr};
+
+/// The producer end of the overwriting MPSC queue.
+///
+/// This struct is `Clone` and `Send`. It can be shared across threads by cloning it.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::overwriting::channel;
+///
+/// let (tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+///
+/// let mut tx2 = tx.clone();
+/// thread::spawn(move || tx2.send(1)).join().unwrap();
+///
+/// let mut tx3 = tx.clone();
+/// thread::spawn(move || tx3.send(2)).join().unwrap();
+///
+/// assert_eq!(rx.recv(), 1);
+/// assert_eq!(rx.recv(), 2);
+/// ```
+pub struct Sender<T> {
+    producer: Producer<T>,
+}
+
+// Not derived: cloning a handle must not require `T: Clone`.
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            producer: self.producer.clone(),
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(queue_ptr: QueuePtr<T>) -> Self {
+        Self {
+            producer: Producer::new(queue_ptr),
+        }
+    }
+
+    /// Sends a value into the queue without ever blocking.
+    ///
+    /// If the queue is full, the oldest unread item is evicted to make room and
+    /// returned as `Some(evicted)`. Returns `None` if there was free space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::overwriting::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(2).unwrap());
+    ///
+    /// assert_eq!(tx.send(1), None);
+    /// assert_eq!(tx.send(2), None);
+    /// assert_eq!(tx.send(3), Some(1));
+    /// assert_eq!(rx.recv(), 2);
+    /// ```
+    pub fn send(&mut self, value: T) -> Option<T> {
+        self.producer.push(value)
+    }
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
@@ -0,0 +1,265 @@
+//! Multi-priority multi-producer single-consumer channel.
+//!
+//! The channel is composed of one [MPSC queue](crate::mpsc) per priority level,
+//! all drained by the same receiver. Level `0` is the highest priority. Senders
+//! pick a level per message with [`Sender::send_with_priority`].
+//!
+//! # Examples
+//!
+//! ```
+//! use core::num::NonZeroUsize;
+//! use gil::mpsc::priority::channel;
+//!
+//! let (mut tx, mut rx) = channel::<&str>(
+//!     NonZeroUsize::new(2).unwrap(),   // 2 priority levels
+//!     NonZeroUsize::new(256).unwrap(), // 256 capacity per level
+//! );
+//!
+//! tx.send_with_priority("bulk", 1);
+//! tx.send_with_priority("control", 0);
+//!
+//! // control overtakes bulk
+//! assert_eq!(rx.recv(), "control");
+//! assert_eq!(rx.recv(), "bulk");
+//! ```
+//!
+//! # Modes
+//!
+//! - **Strict** ([`channel`]) — `recv` always drains higher levels first. A busy
+//!   high level can starve the levels below it indefinitely.
+//! - **Weighted** ([`weighted_channel`]) — every level gets a number of credits per
+//!   round, set by its weight. `recv` still prefers higher levels, but a level that
+//!   used up its credits is skipped until every level with pending messages has
+//!   done the same, and then credits are refilled. A level with weight `w` gets at
+//!   least `w` messages through per round whenever it has them.
+//!
+//! # Performance
+//!
+//! - **Per-level Queues:** Each level is a full MPSC queue, so sending costs the
+//!   same as on a plain [`mpsc::channel`](crate::mpsc::channel) and senders on
+//!   different levels never contend.
+//! - **Consumer Overhead:** `recv` polls levels in priority order, so a message on
+//!   the lowest level costs one failed poll per level above it.
+//!
+//! # Gotchas
+//!
+//! - **No Cross-level Ordering:** Messages are FIFO within a level only.
+//! - **Capacity Is Per Level:** A full level blocks senders on that level even if
+//!   the others are empty.
+//! - **Out-of-range Priorities Panic:** Priorities must be less than the number of
+//!   levels the channel was created with.
+
+use core::num::NonZeroUsize;
+
+use alloc_crate::{boxed::Box, vec::Vec};
+
+pub use self::{receiver::Receiver, sender::Sender};
+
+mod receiver;
+mod sender;
+
+/// Creates a new strict-priority MPSC channel.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Arguments
+///
+/// * `levels` - The number of priority levels; priority `0` is the highest.
+/// * `capacity` - The capacity of each level's queue, rounded up to the next
+///   power of two.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::priority::channel;
+///
+/// let (tx, rx) = channel::<usize>(
+///     NonZeroUsize::new(3).unwrap(),
+///     NonZeroUsize::new(1024).unwrap(),
+/// );
+/// ```
+pub fn channel<T>(levels: NonZeroUsize, capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
+    let (senders, receivers) = lanes(levels.get(), capacity);
+    (Sender::new(senders), Receiver::new(receivers, None))
+}
+
+/// Creates a new weighted-priority MPSC channel.
+///
+/// See the [module-level documentation](self#modes) for how weights are applied.
+///
+/// # Arguments
+///
+/// * `weights` - One weight per priority level, highest priority first.
+/// * `capacity` - The capacity of each level's queue, rounded up to the next
+///   power of two.
+///
+/// # Panics
+///
+/// Panics if `weights` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::priority::weighted_channel;
+///
+/// let weight = |w| NonZeroUsize::new(w).unwrap();
+/// let (mut tx, mut rx) = weighted_channel::<usize>(
+///     &[weight(3), weight(1)],
+///     NonZeroUsize::new(64).unwrap(),
+/// );
+///
+/// for i in 0..8 {
+///     tx.send_with_priority(i, 0);
+///     tx.send_with_priority(100 + i, 1);
+/// }
+///
+/// // three high-priority messages per low-priority one
+/// let order: Vec<_> = (0..8).map(|_| rx.recv()).collect();
+/// assert_eq!(order, [0, 1, 2, 100, 3, 4, 5, 101]);
+/// ```
+pub fn weighted_channel<T>(
+    weights: &[NonZeroUsize],
+    capacity: NonZeroUsize,
+) -> (Sender<T>, Receiver<T>) {
+    assert!(
+        !weights.is_empty(),
+        "a priority channel needs at least one level"
+    );
+
+    let (senders, receivers) = lanes(weights.len(), capacity);
+    let weights = weights.iter().map(|w| w.get()).collect();
+    (
+        Sender::new(senders),
+        Receiver::new(receivers, Some(weights)),
+    )
+}
+
+#[allow(clippy::type_complexity)]
+fn lanes<T>(
+    levels: usize,
+    capacity: NonZeroUsize,
+) -> (
+    Box<[crate::mpsc::Sender<T>]>,
+    Box<[crate::mpsc::Receiver<T>]>,
+) {
+    let (senders, receivers): (Vec<_>, Vec<_>) =
+        (0..levels).map(|_| crate::mpsc::channel(capacity)).unzip();
+    (senders.into_boxed_slice(), receivers.into_boxed_slice())
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn test_strict_order() {
+        let (mut tx, mut rx) = channel::<usize>(
+            NonZeroUsize::new(3).unwrap(),
+            NonZeroUsize::new(16).unwrap(),
+        );
+
+        for i in 0..4 {
+            tx.send_with_priority(200 + i, 2);
+            tx.send_with_priority(100 + i, 1);
+            tx.send_with_priority(i, 0);
+        }
+
+        let received: Vec<_> = (0..12).map(|_| rx.recv()).collect();
+        assert_eq!(
+            received,
+            [0, 1, 2, 3, 100, 101, 102, 103, 200, 201, 202, 203]
+        );
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn test_weighted_no_starvation() {
+        let weight = |w| NonZeroUsize::new(w).unwrap();
+        let (mut tx, mut rx) =
+            weighted_channel::<usize>(&[weight(4), weight(1)], NonZeroUsize::new(64).unwrap());
+
+        for i in 0..10 {
+            tx.send_with_priority(100 + i, 1);
+        }
+
+        let mut low = 0;
+        for round in 0..10 {
+            // keep the high level busy
+            for i in 0..5 {
+                tx.send_with_priority(round * 5 + i, 0);
+            }
+            for _ in 0..5 {
+                if rx.recv() >= 100 {
+                    low += 1;
+                }
+            }
+        }
+        assert_eq!(low, 10);
+    }
+
+    #[test]
+    fn test_try_send_full_level() {
+        let (mut tx, mut rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(2).unwrap());
+
+        tx.try_send_with_priority(1, 1).unwrap();
+        tx.try_send_with_priority(2, 1).unwrap();
+        assert_eq!(tx.try_send_with_priority(3, 1), Err(3));
+        tx.try_send_with_priority(0, 0).unwrap();
+
+        assert_eq!(rx.recv(), 0);
+        assert_eq!(rx.recv(), 1);
+    }
+
+    #[test]
+    fn clone_does_not_need_clone_items() {
+        struct NotClone(usize);
+
+        let (tx, mut rx) =
+            channel::<NotClone>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(2).unwrap());
+        let mut tx2 = tx.clone();
+        tx2.send_with_priority(NotClone(1), 1);
+        assert_eq!(rx.recv().0, 1);
+    }
+
+    #[test]
+    fn test_multi_sender() {
+        const THREADS: usize = 4;
+        const ITER: usize = 1000;
+
+        let (tx, mut rx) = channel::<usize>(
+            NonZeroUsize::new(THREADS).unwrap(),
+            NonZeroUsize::new(64).unwrap(),
+        );
+
+        for t in 0..THREADS {
+            let mut tx = tx.clone();
+            thread::spawn(move || {
+                for i in 0..ITER {
+                    tx.send_with_priority(t * ITER + i, t);
+                }
+            });
+        }
+
+        let mut next = [0; THREADS];
+        for _ in 0..THREADS * ITER {
+            let value = rx.recv();
+            // each level is FIFO on its own
+            let level = value / ITER;
+            assert_eq!(value % ITER, next[level]);
+            next[level] += 1;
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_range_priority() {
+        let (mut tx, _rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(2).unwrap());
+        tx.send_with_priority(0, 2);
+    }
+}
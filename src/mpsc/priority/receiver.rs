@@ -0,0 +1,197 @@
+use alloc_crate::boxed::Box;
+
+/// The consumer end of the priority MPSC channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::priority::channel;
+///
+/// let (mut tx, mut rx) = channel::<i32>(
+///     NonZeroUsize::new(2).unwrap(),
+///     NonZeroUsize::new(16).unwrap(),
+/// );
+/// tx.send_with_priority(2, 1);
+/// tx.send_with_priority(1, 0);
+/// assert_eq!(rx.recv(), 1);
+/// assert_eq!(rx.recv(), 2);
+/// ```
+pub struct Receiver<T> {
+    levels: Box<[crate::mpsc::Receiver<T>]>,
+    weighted: Option<Weighted>,
+}
+
+/// Credit state for the weighted mode.
+struct Weighted {
+    weights: Box<[usize]>,
+    credits: Box<[usize]>,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn new(
+        levels: Box<[crate::mpsc::Receiver<T>]>,
+        weights: Option<Box<[usize]>>,
+    ) -> Self {
+        Self {
+            levels,
+            weighted: weights.map(|weights| Weighted {
+                credits: weights.clone(),
+                weights,
+            }),
+        }
+    }
+
+    /// Receives a value, blocking if every level is empty.
+    ///
+    /// Spins in a loop calling [`try_recv`](Receiver::try_recv) with a
+    /// [`Backoff`](crate::Backoff) of 128 spins. For a custom spin count, use
+    /// [`recv_with_spin_count`](Receiver::recv_with_spin_count).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::priority::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(2).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// tx.send_with_priority(42, 1);
+    /// assert_eq!(rx.recv(), 42);
+    /// ```
+    pub fn recv(&mut self) -> T {
+        self.recv_with_spin_count(128)
+    }
+
+    /// Receives a value, blocking if every level is empty, with a custom spin
+    /// count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::priority::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(2).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// tx.send_with_priority(42, 0);
+    /// assert_eq!(rx.recv_with_spin_count(32), 42);
+    /// ```
+    pub fn recv_with_spin_count(&mut self, spin_count: u32) -> T {
+        let mut backoff = crate::Backoff::with_spin_count(spin_count);
+        loop {
+            match self.try_recv() {
+                Some(value) => return value,
+                None => backoff.backoff(),
+            }
+        }
+    }
+
+    /// Attempts to receive a value without blocking.
+    ///
+    /// Takes from the highest non-empty level, subject to credits in weighted
+    /// mode. Returns `None` if every level is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::priority::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(2).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// assert_eq!(rx.try_recv(), None);
+    /// tx.send_with_priority(42, 1);
+    /// assert_eq!(rx.try_recv(), Some(42));
+    /// ```
+    pub fn try_recv(&mut self) -> Option<T> {
+        let Some(weighted) = &mut self.weighted else {
+            return self.levels.iter_mut().find_map(|level| level.try_recv());
+        };
+
+        loop {
+            for (level, credits) in self.levels.iter_mut().zip(weighted.credits.iter_mut()) {
+                if *credits == 0 {
+                    continue;
+                }
+                if let Some(value) = level.try_recv() {
+                    *credits -= 1;
+                    return Some(value);
+                }
+            }
+
+            // every level with credits left is empty; a refill only helps if
+            // some level had run out
+            if weighted.credits == weighted.weights {
+                return None;
+            }
+            weighted.credits.copy_from_slice(&weighted.weights);
+        }
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// every sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Returns `true` once every [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued, at any level.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.levels.iter().all(|level| level.is_disconnected())
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
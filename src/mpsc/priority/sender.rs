@@ -0,0 +1,117 @@
+use alloc_crate::boxed::Box;
+
+/// The producer end of the priority MPSC channel.
+///
+/// This struct is `Clone` and `Send`. It can be shared across threads by cloning it.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::priority::channel;
+///
+/// let (tx, mut rx) = channel::<i32>(
+///     NonZeroUsize::new(2).unwrap(),
+///     NonZeroUsize::new(16).unwrap(),
+/// );
+///
+/// let mut tx2 = tx.clone();
+/// thread::spawn(move || tx2.send_with_priority(1, 1)).join().unwrap();
+///
+/// let mut tx3 = tx.clone();
+/// thread::spawn(move || tx3.send_with_priority(0, 0)).join().unwrap();
+///
+/// assert_eq!(rx.recv(), 0);
+/// assert_eq!(rx.recv(), 1);
+/// ```
+pub struct Sender<T> {
+    levels: Box<[crate::mpsc::Sender<T>]>,
+}
+
+// Not derived: cloning a handle must not require `T: Clone`.
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            levels: self.levels.clone(),
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(levels: Box<[crate::mpsc::Sender<T>]>) -> Self {
+        Self { levels }
+    }
+
+    /// The number of priority levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::priority::channel;
+    ///
+    /// let (tx, _rx) = channel::<i32>(
+    ///     NonZeroUsize::new(3).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// assert_eq!(tx.levels(), 3);
+    /// ```
+    #[inline]
+    pub fn levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Sends a value at the given priority, blocking if that level is full.
+    ///
+    /// Priority `0` is the highest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is not less than [`levels`](Sender::levels).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::priority::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(2).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// tx.send_with_priority(1, 1);
+    /// tx.send_with_priority(0, 0);
+    /// assert_eq!(rx.recv(), 0);
+    /// ```
+    pub fn send_with_priority(&mut self, value: T, priority: usize) {
+        self.levels[priority].send(value);
+    }
+
+    /// Attempts to send a value at the given priority without blocking.
+    ///
+    /// Returns `Err(value)` if that level is full, even if other levels have room.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is not less than [`levels`](Sender::levels).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::priority::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(2).unwrap(),
+    ///     NonZeroUsize::new(2).unwrap(),
+    /// );
+    /// assert_eq!(tx.try_send_with_priority(1, 1), Ok(()));
+    /// assert_eq!(tx.try_send_with_priority(2, 1), Ok(()));
+    /// assert_eq!(tx.try_send_with_priority(3, 1), Err(3));
+    /// assert_eq!(tx.try_send_with_priority(0, 0), Ok(()));
+    /// ```
+    pub fn try_send_with_priority(&mut self, value: T, priority: usize) -> Result<(), T> {
+        self.levels[priority].try_send(value)
+    }
+}
@@ -1,16 +1,31 @@
 use crate::{
     atomic::AtomicUsize,
-    cell::{Cell, DropTailScan},
+    cell::{Cell, Cells, DropTailScan},
     padded::Padded,
-    ring::RingTail,
+    ring::{RingHead, RingTail},
 };
 
+/// The consumer's head. Only the `len()` family reads it: the receiver tracks
+/// its position locally and publishes it here after each pop.
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Head {
+    head: Padded<AtomicUsize>,
+}
+
 #[derive(Default)]
 #[repr(C)]
 pub(crate) struct Tail {
     tail: Padded<AtomicUsize>,
 }
 
+impl RingHead for Head {
+    #[inline(always)]
+    fn head(&self) -> &AtomicUsize {
+        &self.head.value
+    }
+}
+
 impl RingTail for Tail {
     #[inline(always)]
     fn tail(&self) -> &AtomicUsize {
@@ -18,9 +33,14 @@ impl RingTail for Tail {
     }
 }
 
-pub(crate) type QueuePtr<T> = crate::QueuePtr<(), Tail, Cell<T>, DropTailScan>;
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, Tail, Cell<T>, DropTailScan>;
 
 impl<T> QueuePtr<T> {
+    #[inline(always)]
+    pub(crate) fn head(&self) -> &AtomicUsize {
+        self.header().head.head()
+    }
+
     #[inline(always)]
     pub(crate) fn tail(&self) -> &AtomicUsize {
         self.header().tail.tail()
@@ -34,3 +54,22 @@ impl<T> QueuePtr<T> {
         unsafe { self.at(index).as_ref() }
     }
 }
+
+impl<T> Cells for QueuePtr<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        QueuePtr::tail(self)
+    }
+
+    #[inline(always)]
+    fn cell_at(&self, ticket: usize) -> &Cell<T> {
+        QueuePtr::cell_at(self, ticket)
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
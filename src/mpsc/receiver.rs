@@ -1,4 +1,10 @@
-use crate::{atomic::Ordering, mpsc::queue::QueuePtr};
+use core::cmp::Ordering as Cmp;
+
+use alloc_crate::vec::Vec;
+
+use crate::{
+    atomic::Ordering, cell::Staged, mpsc::queue::QueuePtr, read_guard::BatchReader, stats::Counters,
+};
 
 /// The consumer end of the MPSC queue.
 ///
@@ -20,6 +26,8 @@ use crate::{atomic::Ordering, mpsc::queue::QueuePtr};
 pub struct Receiver<T> {
     ptr: QueuePtr<T>,
     local_head: usize,
+    stats: Counters,
+    staged: Staged<T>,
 }
 
 impl<T> Receiver<T> {
@@ -27,6 +35,8 @@ impl<T> Receiver<T> {
         Self {
             ptr: queue_ptr,
             local_head: 0,
+            stats: Counters::default(),
+            staged: Staged::default(),
         }
     }
 
@@ -72,13 +82,24 @@ impl<T> Receiver<T> {
     /// assert_eq!(rx.recv_with_spin_count(64), 42);
     /// ```
     pub fn recv_with_spin_count(&mut self, spin_count: u32) -> T {
-        let next_head = self.local_head.wrapping_add(1);
+        if let Some(ret) = self.staged.pop() {
+            return ret;
+        }
 
-        let cell = self.ptr.cell_at(self.local_head);
         let mut backoff = crate::Backoff::with_spin_count(spin_count);
-        while cell.epoch().load(Ordering::Acquire) < next_head {
+        let (cell, next_head) = loop {
+            let next_head = self.local_head.wrapping_add(1);
+            let cell = self.ptr.cell_at(self.local_head);
+            let epoch = cell.epoch().load(Ordering::Acquire);
+            if epoch == next_head {
+                break (cell, next_head);
+            }
+            if epoch > next_head {
+                self.skip();
+                continue;
+            }
             backoff.backoff();
-        }
+        };
 
         let ret = unsafe { cell.get() };
         cell.epoch().store(
@@ -87,6 +108,11 @@ impl<T> Receiver<T> {
         );
 
         self.local_head = next_head;
+        self.ptr.head().store(next_head, Ordering::Relaxed);
+        self.stats.received(1);
+        self.stats.observe(|| {
+            crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+        });
 
         ret
     }
@@ -110,13 +136,20 @@ impl<T> Receiver<T> {
     /// assert_eq!(rx.try_recv(), None);
     /// ```
     pub fn try_recv(&mut self) -> Option<T> {
-        let next_head = self.local_head.wrapping_add(1);
-
-        let cell = self.ptr.cell_at(self.local_head);
-        if cell.epoch().load(Ordering::Acquire) < next_head {
-            return None;
+        if let Some(ret) = self.staged.pop() {
+            return Some(ret);
         }
 
+        let (cell, next_head) = loop {
+            let next_head = self.local_head.wrapping_add(1);
+            let cell = self.ptr.cell_at(self.local_head);
+            match cell.epoch().load(Ordering::Acquire).cmp(&next_head) {
+                Cmp::Less => return None,
+                Cmp::Equal => break (cell, next_head),
+                Cmp::Greater => self.skip(),
+            }
+        };
+
         let ret = unsafe { cell.get() };
         cell.epoch().store(
             self.local_head.wrapping_add(self.ptr.capacity),
@@ -124,9 +157,283 @@ impl<T> Receiver<T> {
         );
 
         self.local_head = next_head;
+        self.ptr.head().store(next_head, Ordering::Relaxed);
+        self.stats.received(1);
+        self.stats.observe(|| {
+            crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+        });
 
         Some(ret)
     }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// every sender has been dropped. See [`iter`](crate::iter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(1);
+    /// tx.send(2);
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(1);
+    /// tx.send(2);
+    ///
+    /// assert_eq!(rx.try_iter().collect::<Vec<_>>(), [1, 2]);
+    /// assert_eq!(rx.try_recv(), None);
+    /// ```
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Moves up to `max` available values into `dst` without blocking.
+    ///
+    /// Takes the whole run of published cells at the head and publishes the
+    /// new head once, instead of once per value. Returns how many values were
+    /// moved; `0` means the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// for i in 0..5 {
+    ///     tx.send(i);
+    /// }
+    ///
+    /// let mut out = Vec::new();
+    /// assert_eq!(rx.try_recv_many(&mut out, 3), 3);
+    /// assert_eq!(rx.try_recv_many(&mut out, 8), 2);
+    /// assert_eq!(out, [0, 1, 2, 3, 4]);
+    /// ```
+    pub fn try_recv_many(&mut self, dst: &mut Vec<T>, max: usize) -> usize {
+        let n = self.staged.drain_into(dst, max);
+        n + self.claim(max - n, |value| dst.push(value))
+    }
+
+    /// Like [`try_recv_many`](Self::try_recv_many), but blocks until at least
+    /// one value is available (unless `max` is `0`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// std::thread::spawn(move || tx.send(7));
+    ///
+    /// let mut out = Vec::new();
+    /// assert_eq!(rx.recv_many(&mut out, 4), 1);
+    /// assert_eq!(out, [7]);
+    /// ```
+    pub fn recv_many(&mut self, dst: &mut Vec<T>, max: usize) -> usize {
+        let n = self.try_recv_many(dst, max);
+        if n > 0 || max == 0 {
+            return n;
+        }
+        dst.push(self.recv());
+        1 + self.try_recv_many(dst, max - 1)
+    }
+
+    /// Returns a [`ReadGuard`](crate::read_guard::ReadGuard) over the values
+    /// available at the head.
+    ///
+    /// Cells don't form a contiguous `[T]`, so the guard's slice is a buffer
+    /// owned by this receiver: the values are moved out of the queue (and
+    /// their cells handed back to the senders) when the guard is created.
+    /// Values the guard doesn't advance past stay in that buffer and come out
+    /// of the next `recv`, `try_recv` or guard first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+    /// for i in 0..5 {
+    ///     tx.send(i);
+    /// }
+    ///
+    /// let mut guard = rx.read_guard();
+    /// assert_eq!(guard.as_slice(), &[0, 1, 2, 3, 4]);
+    /// guard.advance(3);
+    /// drop(guard);
+    ///
+    /// assert_eq!(rx.try_recv(), Some(3));
+    /// ```
+    pub fn read_guard(&mut self) -> crate::read_guard::ReadGuard<'_, Self> {
+        crate::read_guard::ReadGuard::new(self)
+    }
+
+    /// Hands up to `max` published values from the head to `take`, then
+    /// publishes the new head once.
+    fn claim(&mut self, max: usize, mut take: impl FnMut(T)) -> usize {
+        let mut n = 0;
+        while n < max {
+            let cell = self.ptr.cell_at(self.local_head);
+            let next_head = self.local_head.wrapping_add(1);
+            match cell.epoch().load(Ordering::Acquire).cmp(&next_head) {
+                Cmp::Less => break,
+                Cmp::Equal => {}
+                // skipped: no value, and its producer already freed the cell
+                Cmp::Greater => {
+                    self.local_head = next_head;
+                    continue;
+                }
+            }
+
+            let value = unsafe { cell.get() };
+            cell.epoch().store(
+                self.local_head.wrapping_add(self.ptr.capacity),
+                Ordering::Release,
+            );
+            self.local_head = next_head;
+            n += 1;
+            take(value);
+        }
+
+        self.ptr.head().store(self.local_head, Ordering::Relaxed);
+        if n > 0 {
+            self.stats.received(n);
+            self.stats.observe(|| {
+                crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+            });
+        }
+        n
+    }
+
+    /// Steps over the cell at the head, which its producer published as
+    /// skipped (see [`Unfilled`](crate::cell::Unfilled)). The producer already
+    /// freed the cell, so there is nothing to read or release.
+    #[inline(always)]
+    fn skip(&mut self) {
+        self.local_head = self.local_head.wrapping_add(1);
+        self.ptr.head().store(self.local_head, Ordering::Relaxed);
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// Read from the shared head and tail while other handles may be moving
+    /// them, so this is an approximate snapshot. Items whose slot is claimed
+    /// but not yet written or read count as present.
+    #[inline]
+    pub fn len(&self) -> usize {
+        crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once every [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.ptr.is_disconnected()
+    }
+
+    /// The number of items the queue holds when full: the requested capacity
+    /// rounded up to the next power of two, and to at least 2.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.ptr.capacity
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.stats.snapshot()
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 unsafe impl<T: Send> Send for Receiver<T> {}
+
+/// Lends values out of the receiver's staging buffer; see
+/// [`read_guard`](Receiver::read_guard).
+unsafe impl<T> BatchReader for Receiver<T> {
+    type Item = T;
+
+    fn read_buffer(&mut self) -> &[T] {
+        if self.staged.is_empty() {
+            let mut staged = core::mem::take(&mut self.staged);
+            staged.reset();
+            self.claim(self.ptr.capacity, |value| staged.push(value));
+            self.staged = staged;
+        }
+        self.staged.as_slice()
+    }
+
+    unsafe fn advance(&mut self, n: usize) {
+        self.staged.advance(n);
+    }
+}
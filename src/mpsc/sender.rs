@@ -1,4 +1,11 @@
-use crate::{atomic::Ordering, mpsc::queue::QueuePtr};
+use core::mem::MaybeUninit;
+
+use crate::{
+    atomic::Ordering,
+    cell::{Unfilled, claim, claim_range},
+    mpsc::queue::QueuePtr,
+    stats::Counters,
+};
 
 /// The producer end of the MPSC queue.
 ///
@@ -24,17 +31,37 @@ use crate::{atomic::Ordering, mpsc::queue::QueuePtr};
 /// values.sort();
 /// assert_eq!(values, [1, 2]);
 /// ```
-#[derive(Clone)]
 pub struct Sender<T> {
     ptr: QueuePtr<T>,
     local_tail: usize,
+    stats: Counters,
+}
+
+// Not derived: cloning a handle must not require `T: Clone`.
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.ptr.attach_sender();
+        Self {
+            ptr: self.ptr.clone(),
+            local_tail: self.local_tail,
+            stats: Counters::default(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.ptr.detach_sender();
+    }
 }
 
 impl<T> Sender<T> {
     pub(crate) fn new(queue_ptr: QueuePtr<T>) -> Self {
+        queue_ptr.attach_sender();
         Self {
             ptr: queue_ptr,
             local_tail: 0,
+            stats: Counters::default(),
         }
     }
 
@@ -76,10 +103,14 @@ impl<T> Sender<T> {
     /// tx.send_with_spin_count(42, 4, 8);
     /// assert_eq!(rx.recv(), 42);
     /// ```
-    pub fn send_with_spin_count(&mut self, mut value: T, spin_limit: u32, yield_limit: u32) {
+    pub fn send_with_spin_count(&mut self, value: T, spin_limit: u32, yield_limit: u32) {
+        let Err(mut value) = self.try_send(value) else {
+            return;
+        };
+
         let mut backoff = crate::ExponentialBackoff::new(spin_limit, yield_limit);
         loop {
-            match self.try_send(value) {
+            match self.push(value) {
                 Ok(()) => return,
                 Err(ret) => {
                     value = ret;
@@ -112,47 +143,283 @@ impl<T> Sender<T> {
     /// assert_eq!(rx.recv(), 1);
     /// ```
     pub fn try_send(&mut self, value: T) -> Result<(), T> {
-        use core::cmp::Ordering as Cmp;
+        self.push(value).inspect_err(|_| self.stats.full())
+    }
 
-        let mut backoff = crate::ExponentialBackoff::new(6, 10);
+    /// [`try_send`](Sender::try_send) without the full-queue count, so the
+    /// blocking loops record one full event per send rather than per retry.
+    fn push(&mut self, value: T) -> Result<(), T> {
+        let Some(cell) = claim(&self.ptr, &mut self.local_tail, || self.stats.cas_retry()) else {
+            return Err(value);
+        };
 
-        let cell = loop {
-            let cell = self.ptr.cell_at(self.local_tail);
-            let epoch = cell.epoch().load(Ordering::Acquire);
-
-            match epoch.cmp(&self.local_tail) {
-                // consumer hasn't read the value
-                Cmp::Less => return Err(value),
-
-                // consumer has read the value, cell is free
-                Cmp::Equal => {
-                    let next = self.local_tail.wrapping_add(1);
-                    match self.ptr.tail().compare_exchange_weak(
-                        self.local_tail,
-                        next,
-                        Ordering::Relaxed,
-                        Ordering::Relaxed,
-                    ) {
-                        Ok(_) => {
-                            self.local_tail = next;
-                            break cell;
-                        }
-                        Err(cur_tail) => self.local_tail = cur_tail,
+        cell.set(value);
+        cell.epoch().store(self.local_tail, Ordering::Release);
+        self.published(1);
+
+        Ok(())
+    }
+
+    /// Constructs the next item directly in its cell, blocking while the queue
+    /// is full.
+    ///
+    /// Waits like [`send`](Sender::send), then calls `f` on the claimed cell.
+    /// The item is published when `f` returns.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    ///
+    /// # Panics
+    ///
+    /// The cell is claimed from the other producers before `f` runs and the
+    /// claim cannot be undone. If `f` panics, the cell is published as skipped:
+    /// the panic propagates, nothing is sent, and the receivers step over it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<[u32; 256]>(NonZeroUsize::new(16).unwrap());
+    /// tx.send_with(|slot| slot.write([7; 256]));
+    /// assert_eq!(rx.recv()[255], 7);
+    /// ```
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        let Err(mut f) = self.try_send_with(f) else {
+            return;
+        };
+
+        let mut backoff = crate::ExponentialBackoff::new(6, 10);
+        loop {
+            match self.push_with(f) {
+                Ok(()) => return,
+                Err(ret) => {
+                    f = ret;
+                    if backoff.backoff() {
+                        backoff.reset();
                     }
                 }
+            }
+        }
+    }
 
-                // some other producer has written to this cell before us
-                Cmp::Greater => self.local_tail = self.ptr.tail().load(Ordering::Relaxed),
-            };
+    /// Non-blocking [`send_with`](Sender::send_with). Returns `Err(f)` without
+    /// calling it if the queue is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.push_with(f).inspect_err(|_| self.stats.full())
+    }
 
-            backoff.backoff();
-        };
+    /// [`try_send_with`](Sender::try_send_with) without the full-queue count.
+    fn push_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        let (start, n) = claim_range(&self.ptr, &mut self.local_tail, 1, || {
+            self.stats.cas_retry()
+        });
+        if n == 0 {
+            return Err(f);
+        }
 
-        cell.set(value);
-        cell.epoch().store(self.local_tail, Ordering::Release);
+        // skips the cell if `f` panics
+        Unfilled::new(&self.ptr, start, 1).fill_with(f);
+        self.published(1);
 
         Ok(())
     }
+
+    #[inline(always)]
+    fn published(&mut self, n: usize) {
+        self.stats.sent(n);
+        self.stats.observe(|| {
+            crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+        });
+    }
+
+    /// Sends every item of `values`, blocking while the queue is full.
+    ///
+    /// Claims all the free cells it can fill with one compare-exchange on the
+    /// shared tail, instead of one per item, then copies into them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<u32>(NonZeroUsize::new(4).unwrap());
+    /// tx.send_slice(&[1, 2, 3]);
+    /// assert_eq!(tx.try_send_iter(4..10), 1);
+    /// assert_eq!([rx.recv(), rx.recv(), rx.recv(), rx.recv()], [1, 2, 3, 4]);
+    /// ```
+    pub fn send_slice(&mut self, mut values: &[T])
+    where
+        T: Copy,
+    {
+        loop {
+            values = &values[self.push_slice(values)..];
+            let Some((&first, rest)) = values.split_first() else {
+                return;
+            };
+            // full: wait for a cell the usual way, then go back to ranges
+            self.send(first);
+            values = rest;
+        }
+    }
+
+    /// Sends items from `iter` until it runs dry or the queue fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// Items are only taken from `iter` once there is a cell for them; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest. Cells are claimed a
+    /// range at a time for as many items as `iter`'s
+    /// [`size_hint`](Iterator::size_hint) guarantees, and one at a time
+    /// beyond that.
+    ///
+    /// # Panics
+    ///
+    /// Panics from `iter` propagate, after the items already taken are sent.
+    /// Claimed cells cannot be given back to the other producers, so any that
+    /// `iter` doesn't fill, whether it panics or yields fewer items than its
+    /// `size_hint` promised, are published as skipped and the receivers step
+    /// over them.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let (Ok(sent) | Err(sent)) = self.push_iter(&mut iter);
+        sent
+    }
+
+    /// Sends every item of `iter`, blocking while the queue is full.
+    ///
+    /// Claims cells like [`try_send_iter`](Self::try_send_iter), and skips
+    /// the unfilled ones in the same cases.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        let mut iter = iter.into_iter().peekable();
+        loop {
+            if self.push_iter(&mut iter).is_ok() {
+                return;
+            }
+            let Some(value) = iter.next() else {
+                return;
+            };
+            self.send(value);
+        }
+    }
+
+    /// Copies the head of `values` into one claimed range of cells. Returns
+    /// the number sent; zero if the queue is full.
+    fn push_slice(&mut self, values: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        if values.is_empty() {
+            return 0;
+        }
+
+        let (start, n) = claim_range(&self.ptr, &mut self.local_tail, values.len(), || {
+            self.stats.cas_retry()
+        });
+        for (i, &value) in values[..n].iter().enumerate() {
+            let ticket = start.wrapping_add(i);
+            let cell = self.ptr.cell_at(ticket);
+            cell.set(value);
+            cell.epoch()
+                .store(ticket.wrapping_add(1), Ordering::Release);
+        }
+
+        if n > 0 {
+            self.published(n);
+        }
+        n
+    }
+
+    /// Moves items from `iter` into free cells. Returns `Ok(sent)` once
+    /// `iter` runs dry and `Err(sent)` if the queue fills up first.
+    fn push_iter(&mut self, iter: &mut impl Iterator<Item = T>) -> Result<usize, usize> {
+        let mut sent = 0;
+        loop {
+            let (promised, limit) = iter.size_hint();
+            if limit == Some(0) {
+                return Ok(sent);
+            }
+            // with nothing promised, claim one cell to see whether another
+            // item comes
+            let want = promised.max(1);
+            let (start, n) = claim_range(&self.ptr, &mut self.local_tail, want, || {
+                self.stats.cas_retry()
+            });
+            if n == 0 {
+                return Err(sent);
+            }
+
+            // cells left unfilled, by a short or panicking `iter`, are skipped
+            let mut cells = Unfilled::new(&self.ptr, start, n);
+            while cells.len() > 0 {
+                let Some(value) = iter.next() else {
+                    let filled = n - cells.len();
+                    drop(cells);
+                    if filled > 0 {
+                        self.published(filled);
+                    }
+                    return Ok(sent + filled);
+                };
+                cells.fill(value);
+            }
+            drop(cells);
+
+            self.published(n);
+            sent += n;
+        }
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// Read from the shared head and tail while other handles may be moving
+    /// them, so this is an approximate snapshot. Items whose slot is claimed
+    /// but not yet written or read count as present.
+    #[inline]
+    pub fn len(&self) -> usize {
+        crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: the requested capacity
+    /// rounded up to the next power of two, and to at least 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::channel;
+    ///
+    /// let (tx, _rx) = channel::<i32>(NonZeroUsize::new(3).unwrap());
+    /// assert_eq!(tx.capacity(), 4);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.ptr.capacity
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.stats.snapshot()
+    }
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
@@ -52,6 +52,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 use crate::shard_table::ShardTable;
 
 mod receiver;
@@ -88,7 +90,32 @@ pub fn channel<T>(
     max_shards: NonZeroUsize,
     capacity_per_shard: NonZeroUsize,
 ) -> (sender::Sender<T>, receiver::Receiver<T>) {
-    let table = ShardTable::new(max_shards, capacity_per_shard);
+    channel_in(max_shards, capacity_per_shard, Global)
+}
+
+/// Like [`channel`], but allocates every shard from a clone of `allocator`.
+///
+/// Each shard frees itself through its clone once the last endpoint using it
+/// drops, so the clones may outlive one another.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, mpsc::sharded::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(
+///     NonZeroUsize::new(4).unwrap(),
+///     NonZeroUsize::new(256).unwrap(),
+///     Global,
+/// );
+/// ```
+pub fn channel_in<T, A: Allocator + Clone + Send + 'static>(
+    max_shards: NonZeroUsize,
+    capacity_per_shard: NonZeroUsize,
+    allocator: A,
+) -> (sender::Sender<T>, receiver::Receiver<T>) {
+    let table = ShardTable::new_in(max_shards, capacity_per_shard, allocator);
 
     let receiver = receiver::Receiver::new(&table);
     let sender = sender::Sender::new(table);
@@ -140,6 +167,43 @@ mod test {
         });
     }
 
+    #[test]
+    fn peek_matches_next_recv() {
+        let (mut tx, mut rx) =
+            channel::<u32>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut tx2 = tx.try_clone().unwrap();
+        tx.send(1);
+        tx2.send(2);
+        tx.send(3);
+
+        let mut received = std::vec::Vec::new();
+        while let Some(&next) = rx.peek() {
+            assert_eq!(rx.recv_if(|&v| v != next), None);
+            *rx.peek_mut().unwrap() *= 10;
+            received.push(rx.recv_if(|&v| v == next * 10).unwrap());
+        }
+        received.sort();
+        assert_eq!(received, [10, 20, 30]);
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn try_iter_drains_every_shard() {
+        let (mut tx, mut rx) =
+            channel::<u32>(NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(2).unwrap());
+        let mut tx2 = tx.try_clone().unwrap();
+        tx.send_slice(&[1, 2]);
+        tx2.send(3);
+
+        let mut received: std::vec::Vec<_> = rx.try_iter().collect();
+        received.sort();
+        assert_eq!(received, [1, 2, 3]);
+        assert_eq!(rx.try_iter().next(), None);
+
+        tx2.send(4);
+        assert_eq!(rx.iter().next(), Some(4));
+    }
+
     #[test]
     fn sender_try_clone_reuses_dropped_shard() {
         let (tx0, mut rx) =
@@ -283,4 +347,51 @@ mod test {
             }
         });
     }
+
+    #[cfg(feature = "latency")]
+    #[test]
+    fn latency_sink_covers_every_shard() {
+        use crate::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct Count(AtomicUsize);
+
+        impl crate::LatencySink for Count {
+            fn record(&self, _dwell: std::time::Duration) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let (mut tx, mut rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut tx2 = tx.try_clone().unwrap();
+        let count = crate::Arc::new(Count::default());
+        rx.set_latency_sink(count.clone());
+
+        tx.send(1);
+        tx2.send(2);
+        rx.recv();
+        rx.recv();
+
+        assert_eq!(count.0.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn try_clone_keeps_channel_connected() {
+        let (mut tx, mut rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut tx2 = tx.try_clone().unwrap();
+
+        tx.send(0);
+        drop(tx);
+        assert!(!rx.is_disconnected());
+
+        tx2.send(1);
+        drop(tx2);
+        assert!(rx.is_disconnected());
+
+        let mut received: vec::Vec<_> = rx.iter().collect();
+        received.sort_unstable();
+        assert_eq!(received, [0, 1]);
+    }
 }
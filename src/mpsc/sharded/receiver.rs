@@ -27,6 +27,8 @@ use crate::{
 pub struct Receiver<T> {
     consumers: Box<[Consumer<Shard<T>>]>,
     cursor: Cursor,
+    /// Kept for its sender count.
+    table: ShardTable<T>,
 }
 
 impl<T> Receiver<T> {
@@ -34,6 +36,7 @@ impl<T> Receiver<T> {
         Self {
             consumers: table.claim_all_consumers().map(Consumer::attach).collect(),
             cursor: Cursor::new(table.len()),
+            table: table.clone(),
         }
     }
 
@@ -124,6 +127,55 @@ impl<T> Receiver<T> {
         Some(self.consumers[shard_idx].pop())
     }
 
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// every sender has been dropped. See [`iter`](crate::iter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::sharded::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// tx.send(1);
+    /// tx.send(2);
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, ending once the
+    /// queue is empty. Reads through the batch path, so the head is published
+    /// once per run of items rather than per item. See
+    /// [`Drain`](crate::iter::Drain).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::sharded::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// tx.send(1);
+    /// tx.send(2);
+    ///
+    /// assert_eq!(rx.try_iter().collect::<Vec<_>>(), [1, 2]);
+    /// assert_eq!(rx.try_recv(), None);
+    /// ```
+    pub fn try_iter(&mut self) -> crate::iter::Drain<'_, Self> {
+        crate::iter::Drain::new(self)
+    }
+
     /// Returns a [`ReadGuard`](crate::read_guard::ReadGuard) that provides
     /// batch read access to available items across shards.
     ///
@@ -151,6 +203,149 @@ impl<T> Receiver<T> {
     pub fn read_guard(&mut self) -> crate::read_guard::ReadGuard<'_, Self> {
         crate::read_guard::ReadGuard::new(self)
     }
+
+    /// Returns a reference to the item [`try_recv`](Self::try_recv) would
+    /// return next, or `None` if every shard is empty.
+    ///
+    /// Shards are checked in the receiver's round-robin order, without moving
+    /// on. An item a sender publishes meanwhile to a shard earlier in that
+    /// order can still overtake the peeked one; [`peek_mut`](Self::peek_mut)
+    /// and [`recv_if`](Self::recv_if) settle on the shard they find.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::sharded::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(
+    ///     NonZeroUsize::new(1).unwrap(),
+    ///     NonZeroUsize::new(16).unwrap(),
+    /// );
+    /// tx.send(1);
+    /// tx.send(2);
+    ///
+    /// assert_eq!(rx.peek(), Some(&1));
+    /// assert_eq!(rx.recv_if(|&v| v == 2), None);
+    /// assert_eq!(rx.recv_if(|&v| v == 1), Some(1));
+    /// assert_eq!(rx.peek(), Some(&2));
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        let ptr = self
+            .cursor
+            .indices()
+            .find_map(|shard_idx| self.consumers[shard_idx].peek())?;
+        // SAFETY: the head item stays put until this receiver advances, which
+        // the shared borrow rules out.
+        Some(unsafe { ptr.as_ref() })
+    }
+
+    /// Like [`peek`](Self::peek), but lets the next item be modified in place.
+    /// The receiver stays on the item's shard, so the next receive returns it.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        let consumers = &mut self.consumers;
+        let mut ptr = self
+            .cursor
+            .find(|shard_idx| consumers[shard_idx].peek_mut())?;
+        // SAFETY: as in `peek`, and the sender never touches an unreceived item.
+        Some(unsafe { ptr.as_mut() })
+    }
+
+    /// Receives the next item only if `predicate` accepts it, without blocking.
+    ///
+    /// Returns `None` if every shard is empty or `predicate` returns `false`;
+    /// in the latter case the item stays where it is, and is the one the next
+    /// receive returns.
+    pub fn recv_if(&mut self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if !predicate(self.peek_mut()?) {
+            return None;
+        }
+        self.try_recv()
+    }
+
+    /// Total number of items across all shards.
+    ///
+    /// Each shard is read on its own while other handles keep sending and
+    /// receiving, so the sum is approximate. See [`shard_lens`](Self::shard_lens)
+    /// for the per-shard breakdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::mpsc::sharded::channel;
+    ///
+    /// let (mut tx, rx) = channel::<i32>(
+    ///     NonZeroUsize::new(2).unwrap(),
+    ///     NonZeroUsize::new(4).unwrap(),
+    /// );
+    /// let mut tx2 = tx.try_clone().unwrap();
+    /// tx.send(1);
+    /// tx2.send(2);
+    /// tx2.send(3);
+    ///
+    /// assert_eq!(rx.len(), 3);
+    /// assert_eq!(rx.capacity(), 8);
+    /// let mut lens: Vec<usize> = rx.shard_lens().collect();
+    /// lens.sort();
+    /// assert_eq!(lens, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shard_lens().sum()
+    }
+
+    /// Returns `true` if every shard is empty. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if every shard is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once every [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.table.is_disconnected()
+    }
+
+    /// Total capacity: `max_shards * capacity_per_shard`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.consumers.len() * self.consumers[0].size()
+    }
+
+    /// Number of items in each shard, in shard index order. Each entry is a
+    /// snapshot, as with [`len`](Self::len).
+    pub fn shard_lens(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.consumers.iter().map(|consumer| consumer.len())
+    }
+
+    /// Counters for this receiver, summed over every shard it drains. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.consumers
+            .iter()
+            .map(|consumer| consumer.stats.snapshot())
+            .fold(crate::Stats::default(), crate::Stats::merge)
+    }
+
+    /// Reports how long each item spent in the queue to `sink`, from the
+    /// moment it was published to the moment it is received here, for every
+    /// shard this receiver drains.
+    ///
+    /// Replaces any sink installed earlier. See [`LatencySink`](crate::LatencySink).
+    #[cfg(feature = "latency")]
+    pub fn set_latency_sink(&mut self, sink: crate::Arc<dyn crate::LatencySink>) {
+        for consumer in &mut self.consumers {
+            consumer.tracer.set(sink.clone());
+        }
+    }
 }
 
 /// # Safety
@@ -190,17 +385,23 @@ unsafe impl<T> BatchReader for Receiver<T> {
     /// unsafe { rx.advance(count) };
     /// ```
     fn read_buffer(&mut self) -> &[T] {
+        self.read_slices().0
+    }
+
+    /// Like [`read_buffer`](BatchReader::read_buffer), plus the shard's items
+    /// that wrapped to the start of its ring.
+    fn read_slices(&mut self) -> (&[T], &[T]) {
         let consumers = &mut self.consumers;
         let found = self.cursor.find(|shard_idx| {
-            let (ptr, len) = consumers[shard_idx].read_buffer_raw();
-            (len > 0).then_some((ptr, len))
+            let runs = consumers[shard_idx].read_slices_raw();
+            (runs[0].1 > 0).then_some(runs)
         });
 
         match found {
             // SAFETY: raw parts of the found shard's ring, which `self` keeps
-            // alive; rebuilt here only so the slice outlives the scan closure.
-            Some((ptr, len)) => unsafe { core::slice::from_raw_parts(ptr.as_ptr(), len) },
-            None => &[],
+            // alive; rebuilt here only so the slices outlive the scan closure.
+            Some(runs) => unsafe { crate::ring::slices_from_raw(runs) },
+            None => (&[], &[]),
         }
     }
 
@@ -236,4 +437,41 @@ unsafe impl<T> BatchReader for Receiver<T> {
     }
 }
 
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 unsafe impl<T: Send> Send for Receiver<T> {}
@@ -31,6 +31,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 use crate::shard_table::ShardTable;
 
 mod receiver;
@@ -53,7 +55,32 @@ pub fn channel<T>(
     max_shards: NonZeroUsize,
     capacity_per_shard: NonZeroUsize,
 ) -> (Sender<T>, Receiver<T>) {
-    let table = ShardTable::new(max_shards, capacity_per_shard);
+    channel_in(max_shards, capacity_per_shard, Global)
+}
+
+/// Like [`channel`], but allocates every shard from a clone of `allocator`.
+///
+/// Each shard frees itself through its clone once the last endpoint using it
+/// drops, so the clones may outlive one another.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, mpsc::sharded_parking::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(
+///     NonZeroUsize::new(4).unwrap(),
+///     NonZeroUsize::new(256).unwrap(),
+///     Global,
+/// );
+/// ```
+pub fn channel_in<T, A: Allocator + Clone + Send + 'static>(
+    max_shards: NonZeroUsize,
+    capacity_per_shard: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    let table = ShardTable::new_in(max_shards, capacity_per_shard, allocator);
 
     let receiver = Receiver::new(&table);
     let sender = Sender::new(table);
@@ -105,6 +132,26 @@ mod test {
         });
     }
 
+    #[test]
+    fn peek_matches_next_recv() {
+        let (mut tx, mut rx) =
+            channel::<u32>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut tx2 = tx.try_clone().unwrap();
+        tx.send(1);
+        tx2.send(2);
+        tx.send(3);
+
+        let mut received = std::vec::Vec::new();
+        while let Some(&next) = rx.peek() {
+            assert_eq!(rx.recv_if(|&v| v != next), None);
+            *rx.peek_mut().unwrap() *= 10;
+            received.push(rx.recv_if(|&v| v == next * 10).unwrap());
+        }
+        received.sort();
+        assert_eq!(received, [10, 20, 30]);
+        assert_eq!(rx.try_recv(), None);
+    }
+
     #[test]
     fn sender_try_clone_reuses_dropped_shard() {
         let (tx0, mut rx) =
@@ -0,0 +1,277 @@
+//! Inter-process MPSC queue over shared memory (Linux, `shm` feature).
+//!
+//! The multi-producer counterpart of [`spsc::shm`](crate::spsc::shm): the
+//! [MPSC](crate::mpsc) Vyukov queue, with its header and cells in a shared
+//! mapping of a file descriptor you supply. Senders in any number of processes
+//! can attach to one receiver. Blocked endpoints park on a shared futex.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{fs::File, os::fd::FromRawFd, thread};
+//! use core::num::NonZeroUsize;
+//! use gil::mpsc::shm::{Receiver, Sender};
+//!
+//! let fd = unsafe { libc::memfd_create(c"gil-queue".as_ptr(), 0) };
+//! assert!(fd >= 0);
+//! let file = unsafe { File::from_raw_fd(fd) };
+//!
+//! let mut rx = Receiver::<u64>::create(&file, NonZeroUsize::new(64).unwrap()).unwrap();
+//!
+//! // each thread stands in for a process with its own mapping
+//! for _ in 0..4 {
+//!     let mut tx = Sender::<u64>::open(&file).unwrap();
+//!     thread::spawn(move || {
+//!         for i in 0..100 {
+//!             tx.send(i);
+//!         }
+//!     });
+//! }
+//!
+//! let sum: u64 = (0..400).map(|_| rx.recv()).sum();
+//! assert_eq!(sum, 4 * 4950);
+//! ```
+//!
+//! # Layout
+//!
+//! Same versioned header as [`spsc::shm`](crate::spsc::shm), recorded with the
+//! mpsc kind, so neither variant can open the other's region.
+//!
+//! # Gotchas
+//!
+//! - **Plain Data Only:** Items are copied bit-for-bit between address spaces,
+//!   hence `T: Copy`.
+//! - **One Receiver:** Attaching a second receiver fails with
+//!   [`ResourceBusy`](std::io::ErrorKind::ResourceBusy). Senders are not
+//!   limited.
+//! - **Crashed Senders:** A sender that dies between claiming a cell and
+//!   publishing it stalls the receiver at that cell for good.
+//! - **Panicking Constructors:** A sender claims its cell before [`Sender::send_with`] runs the
+//!   closure. If the closure panics, the cell is published as skipped: the receiver steps over
+//!   it, and nothing is sent. [`Sender::try_send_iter`] and [`Sender::send_all`] do the same
+//!   with cells claimed for an iterator that panics or runs dry early.
+//! - **Capacity Rounding:** The capacity is rounded up to the next power of two,
+//!   with a minimum of 2.
+//! - **Linux Only:** Shared futexes and `memfd` are Linux APIs.
+
+pub use self::{receiver::Receiver, sender::Sender};
+
+mod queue;
+mod receiver;
+mod sender;
+
+#[cfg(test)]
+mod test {
+    use core::num::NonZeroUsize;
+    use std::{fs::File, io, os::fd::FromRawFd};
+
+    use super::*;
+    use crate::thread;
+
+    fn memfd() -> File {
+        let fd = unsafe { libc::memfd_create(c"gil-test".as_ptr(), 0) };
+        assert!(fd >= 0);
+        unsafe { File::from_raw_fd(fd) }
+    }
+
+    #[test]
+    fn separate_mappings() {
+        const SENDERS: usize = 4;
+        const ITER: usize = 25_000;
+
+        let file = memfd();
+        let mut rx =
+            Receiver::<(usize, usize)>::create(&file, NonZeroUsize::new(8).unwrap()).unwrap();
+
+        thread::scope(|scope| {
+            for id in 0..SENDERS {
+                let file = &file;
+                scope.spawn(move || {
+                    let mut tx = Sender::<(usize, usize)>::open(file).unwrap();
+                    for i in 0..ITER {
+                        tx.send((id, i));
+                    }
+                });
+            }
+
+            let mut next = [0; SENDERS];
+            for _ in 0..SENDERS * ITER {
+                let (id, i) = rx.recv();
+                assert_eq!(next[id], i);
+                next[id] += 1;
+            }
+        });
+    }
+
+    #[test]
+    fn single_receiver() {
+        let file = memfd();
+        let rx = Receiver::<u32>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap();
+
+        let err = Receiver::<u32>::open(&file).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::ResourceBusy);
+        assert!(Sender::<u32>::open(&file).is_ok());
+        assert!(Sender::<u32>::open(&file).is_ok());
+
+        drop(rx);
+        assert!(Receiver::<u32>::open(&file).is_ok());
+    }
+
+    #[test]
+    fn reattach_resumes() {
+        let file = memfd();
+        let mut tx = Sender::<u32>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap();
+        let mut rx = Receiver::<u32>::open(&file).unwrap();
+
+        for i in 0..6 {
+            if i == 4 {
+                assert_eq!(rx.recv(), 0);
+                assert_eq!(rx.recv(), 1);
+            }
+            tx.send(i);
+        }
+        drop(rx);
+
+        let mut rx = Receiver::<u32>::open(&file).unwrap();
+        for i in 2..6 {
+            assert_eq!(rx.try_recv(), Some(i));
+        }
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn send_with_panic_skips_cell() {
+        let file = memfd();
+        let mut rx = Receiver::<usize>::create(&file, NonZeroUsize::new(2).unwrap()).unwrap();
+        let mut tx = Sender::<usize>::open(&file).unwrap();
+
+        for round in 0..3 {
+            tx.send(round);
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                tx.send_with(|_| panic!("boom"));
+            }));
+            assert!(result.is_err());
+
+            assert_eq!(rx.try_recv(), Some(round));
+            // the skipped cell is free again, so the queue still holds two,
+            // one of them a lap ahead of the receiver
+            assert!(tx.try_send_with(|slot| slot.write(10 + round)).is_ok());
+            assert!(tx.try_send(20 + round).is_ok());
+            assert_eq!(tx.try_send(99), Err(99));
+
+            assert_eq!(rx.try_recv(), Some(10 + round));
+            assert_eq!(rx.try_recv(), Some(20 + round));
+            assert_eq!(rx.try_recv(), None);
+        }
+    }
+
+    #[test]
+    fn len_and_capacity() {
+        let file = memfd();
+        let mut rx = Receiver::<u32>::create(&file, NonZeroUsize::new(3).unwrap()).unwrap();
+        let mut tx = Sender::<u32>::open(&file).unwrap();
+        assert_eq!((tx.capacity(), rx.capacity()), (4, 4));
+        assert!(tx.is_empty() && rx.is_empty());
+
+        for i in 0..4 {
+            tx.send(i);
+        }
+        assert_eq!((tx.len(), rx.len()), (4, 4));
+        assert!(tx.is_full() && rx.is_full());
+
+        assert_eq!(rx.recv(), 0);
+        // the receiver publishes its head to the shared control block
+        assert_eq!(tx.len(), 3);
+        assert!(!tx.is_full());
+
+        drop(rx);
+        let rx = Receiver::<u32>::open(&file).unwrap();
+        assert_eq!(rx.len(), 3);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats() {
+        let file = memfd();
+        let mut rx = Receiver::<u32>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap();
+        let mut tx = Sender::<u32>::open(&file).unwrap();
+        for i in 0..4 {
+            tx.send(i);
+        }
+        assert!(tx.try_send(4).is_err());
+
+        let mut tx2 = tx.clone();
+        assert_eq!(tx2.stats().sent, 0);
+        for _ in 0..4 {
+            rx.recv();
+        }
+        tx2.send(5);
+
+        let sent = tx.stats();
+        assert_eq!((sent.sent, sent.received, sent.full), (4, 0, 1));
+        assert_eq!(sent.high_water, 4);
+        assert_eq!(tx2.stats().sent, 1);
+
+        let received = rx.stats();
+        assert_eq!((received.sent, received.received, received.full), (0, 4, 0));
+        assert_eq!(received.high_water, 3);
+    }
+
+    #[test]
+    fn bulk_sends() {
+        const SENDERS: usize = 3;
+        const ITER: usize = 10_000;
+
+        let file = memfd();
+        let mut rx = Receiver::<usize>::create(&file, NonZeroUsize::new(16).unwrap()).unwrap();
+
+        thread::scope(|scope| {
+            for id in 0..SENDERS {
+                let file = &file;
+                scope.spawn(move || {
+                    let mut tx = Sender::<usize>::open(file).unwrap();
+                    let items = id * ITER..(id + 1) * ITER;
+                    match id {
+                        0 => tx.send_slice(&items.collect::<std::vec::Vec<_>>()),
+                        // no size_hint lower bound: one cell at a time
+                        1 => tx.send_all(items.filter(|_| true)),
+                        _ => tx.send_all(items),
+                    }
+                });
+            }
+
+            let mut next = [0; SENDERS];
+            for _ in 0..SENDERS * ITER {
+                let item = rx.recv();
+                let id = item / ITER;
+                assert_eq!(item % ITER, next[id]);
+                next[id] += 1;
+            }
+        });
+    }
+
+    #[test]
+    fn try_send_iter_skips_unfilled_cells() {
+        let file = memfd();
+        let mut rx = Receiver::<usize>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap();
+        let mut tx = Sender::<usize>::open(&file).unwrap();
+
+        let mut items = 0..10;
+        assert_eq!(tx.try_send_iter(items.by_ref()), 4);
+        assert_eq!(items.next(), Some(4));
+        assert_eq!(rx.recv(), 0);
+        assert_eq!(rx.recv(), 1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tx.try_send_iter((4..6).map(|i| if i == 5 { panic!("boom") } else { i }));
+        }));
+        assert!(result.is_err());
+
+        for i in 2..5 {
+            assert_eq!(rx.recv(), i);
+        }
+        assert_eq!(rx.try_recv(), None);
+        // the skipped cell is free again
+        assert_eq!(tx.try_send_iter(5..9), 4);
+    }
+}
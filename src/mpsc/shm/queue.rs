@@ -0,0 +1,148 @@
+use core::{num::NonZeroUsize, ptr::NonNull};
+use std::{io, os::fd::BorrowedFd};
+
+use crate::{
+    atomic::{AtomicU32, AtomicUsize, Ordering},
+    cell::{Cell, Cells},
+    futex::Futex,
+    padded::Padded,
+    shm::{Header, Kind, Region, layout},
+};
+
+/// The mpsc control block, right after the header.
+///
+/// The attached receiver keeps its head locally, like
+/// [`mpsc::Receiver`](crate::mpsc::Receiver), and stores it in `head` after
+/// every receive: for the handles' `len`, and for the next receiver to resume
+/// from once it detaches.
+#[repr(C)]
+struct Control {
+    header: Header,
+    head: Padded<AtomicUsize>,
+    tail: Padded<AtomicUsize>,
+    futex: Padded<AtomicU32>,
+}
+
+/// One process's handle to a shared-memory Vyukov queue.
+pub(crate) struct ShmQueue<T> {
+    region: Region,
+    buffer: NonNull<Cell<T>>,
+    pub(crate) capacity: usize,
+    mask: usize,
+}
+
+impl<T: Copy> ShmQueue<T> {
+    pub(crate) fn create(fd: BorrowedFd<'_>, size: NonZeroUsize) -> io::Result<Self> {
+        // the epochs need at least two cells to tell laps apart
+        let capacity = size
+            .get()
+            .checked_next_power_of_two()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "queue too large"))?
+            .max(2);
+        let (offset, len) = layout::<Control, Cell<T>>(capacity)?;
+
+        let region = Region::create(fd, len)?;
+        let queue = Self::new(region, offset, capacity);
+        for i in 0..capacity {
+            queue.cell_at(i).epoch().store(i, Ordering::Relaxed);
+        }
+        // SAFETY: the epochs are initialised above; the cursors and futex word
+        // start at zero.
+        unsafe { Header::publish::<T>(&queue.region, Kind::Mpsc, capacity) };
+        Ok(queue)
+    }
+
+    pub(crate) fn open(fd: BorrowedFd<'_>) -> io::Result<Self> {
+        let region = Region::open(fd)?;
+        let capacity = region.header().validate::<T>(Kind::Mpsc)?;
+        if !capacity.is_power_of_two() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "queue capacity out of range",
+            ));
+        }
+        let (offset, len) = layout::<Control, Cell<T>>(capacity)?;
+        if region.len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "region shorter than its queue",
+            ));
+        }
+        Ok(Self::new(region, offset, capacity))
+    }
+
+    fn new(region: Region, offset: usize, capacity: usize) -> Self {
+        Self {
+            buffer: region.buffer(offset),
+            region,
+            capacity,
+            mask: capacity - 1,
+        }
+    }
+}
+
+impl<T> ShmQueue<T> {
+    #[inline(always)]
+    fn control(&self) -> &Control {
+        // SAFETY: `Control` is `repr(C)` with the header first, and the region
+        // was sized for it by `create`/`open`.
+        unsafe { self.region.control() }
+    }
+
+    #[inline(always)]
+    pub(crate) fn header(&self) -> &Header {
+        &self.control().header
+    }
+
+    #[inline(always)]
+    pub(crate) fn head(&self) -> &AtomicUsize {
+        &self.control().head.value
+    }
+
+    #[inline(always)]
+    pub(crate) fn tail(&self) -> &AtomicUsize {
+        &self.control().tail.value
+    }
+
+    #[inline(always)]
+    pub(crate) fn futex(&self) -> Futex {
+        Futex::new(&self.control().futex.value)
+    }
+
+    #[inline(always)]
+    pub(crate) fn cell_at(&self, index: usize) -> &Cell<T> {
+        // SAFETY: masked into the buffer, and every cell's epoch was initialised
+        // by the creator.
+        unsafe { self.buffer.add(index & self.mask).as_ref() }
+    }
+}
+
+impl<T> Cells for ShmQueue<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        ShmQueue::tail(self)
+    }
+
+    #[inline(always)]
+    fn cell_at(&self, ticket: usize) -> &Cell<T> {
+        ShmQueue::cell_at(self, ticket)
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// A receiver parked on the skipped cell must wake to step over it.
+    #[inline(always)]
+    fn skipped(&self) {
+        self.futex().wake_shared();
+    }
+}
+
+// SAFETY: all shared state lives in the region and is accessed through atomics
+// or the epoch protocol.
+unsafe impl<T: Send> Send for ShmQueue<T> {}
+unsafe impl<T: Send> Sync for ShmQueue<T> {}
@@ -0,0 +1,232 @@
+use core::{cmp::Ordering as Cmp, num::NonZeroUsize};
+use std::{io, os::fd::AsFd};
+
+use super::queue::ShmQueue;
+use crate::{atomic::Ordering, futex::RECEIVER_WAITING, stats::Counters};
+
+/// The consumer end of a shared-memory MPSC queue.
+///
+/// Each queue accepts one attached receiver at a time, across all processes.
+/// This struct is `Send` but not `Sync` or `Clone`.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::shm::{Receiver, Sender};
+///
+/// let file = memfd();
+/// let mut tx = Sender::<u32>::create(&file, NonZeroUsize::new(16).unwrap()).unwrap();
+/// let mut rx = Receiver::<u32>::open(&file).unwrap();
+///
+/// assert_eq!(rx.try_recv(), None);
+/// tx.send(1);
+/// assert_eq!(rx.try_recv(), Some(1));
+/// # fn memfd() -> std::fs::File {
+/// #     use std::os::fd::FromRawFd;
+/// #     let fd = unsafe { libc::memfd_create(c"gil".as_ptr(), 0) };
+/// #     assert!(fd >= 0);
+/// #     unsafe { std::fs::File::from_raw_fd(fd) }
+/// # }
+/// ```
+pub struct Receiver<T: Copy> {
+    queue: ShmQueue<T>,
+    local_head: usize,
+    stats: Counters,
+}
+
+impl<T: Copy> Receiver<T> {
+    /// Creates a new queue in `fd` and attaches to it as the receiver.
+    ///
+    /// The file is truncated and resized to fit the queue; anything in it is
+    /// lost. `capacity` is rounded up to the next power of two, with a minimum
+    /// of 2.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from resizing or mapping the file.
+    pub fn create(fd: &impl AsFd, capacity: NonZeroUsize) -> io::Result<Self> {
+        Self::attach(ShmQueue::create(fd.as_fd(), capacity)?)
+    }
+
+    /// Attaches as the receiver to a queue another handle created in `fd`.
+    ///
+    /// # Errors
+    ///
+    /// * [`WouldBlock`](io::ErrorKind::WouldBlock) if the creator has not finished
+    ///   initialising the queue.
+    /// * [`InvalidData`](io::ErrorKind::InvalidData) if `fd` does not hold an
+    ///   mpsc queue of `T` with this crate's layout version.
+    /// * [`ResourceBusy`](io::ErrorKind::ResourceBusy) if a receiver is already
+    ///   attached.
+    pub fn open(fd: &impl AsFd) -> io::Result<Self> {
+        Self::attach(ShmQueue::open(fd.as_fd())?)
+    }
+
+    fn attach(queue: ShmQueue<T>) -> io::Result<Self> {
+        queue.header().attach_receiver()?;
+        let local_head = queue.head().load(Ordering::Relaxed);
+        Ok(Self {
+            queue,
+            local_head,
+            stats: Counters::default(),
+        })
+    }
+
+    /// Attempts to receive a value from the queue without blocking.
+    ///
+    /// Returns `None` if the queue is empty.
+    pub fn try_recv(&mut self) -> Option<T> {
+        let (cell, next_head) = loop {
+            let next_head = self.local_head.wrapping_add(1);
+            let cell = self.queue.cell_at(self.local_head);
+            match cell.epoch().load(Ordering::Acquire).cmp(&next_head) {
+                Cmp::Less => return None,
+                Cmp::Equal => break (cell, next_head),
+                // skipped: no value, and its sender already freed the cell
+                Cmp::Greater => {
+                    self.local_head = next_head;
+                    self.queue.head().store(next_head, Ordering::Relaxed);
+                }
+            }
+        };
+
+        let ret = unsafe { cell.get() };
+        cell.epoch().store(
+            self.local_head.wrapping_add(self.queue.capacity),
+            Ordering::Release,
+        );
+        self.local_head = next_head;
+        self.queue.head().store(next_head, Ordering::Relaxed);
+        self.stats.received(1);
+        self.stats.observe(|| {
+            crate::ring::occupancy(self.queue.head(), self.queue.tail(), self.queue.capacity)
+        });
+
+        self.queue.futex().wake_all_shared();
+
+        Some(ret)
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop. The senders live in other processes,
+    /// so the receiver cannot see them disconnect and this never ends on its
+    /// own. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Receives a value from the queue, blocking if necessary.
+    ///
+    /// Spins briefly, then yields, and finally parks on the queue's shared
+    /// futex until a sender, in whichever process, publishes an item.
+    pub fn recv(&mut self) -> T {
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        loop {
+            if let Some(value) = self.try_recv() {
+                return value;
+            }
+            if backoff.backoff() {
+                let futex = self.queue.futex();
+                if futex.announce(RECEIVER_WAITING) && self.head_unpublished() {
+                    self.stats.park();
+                    futex.sleep_shared(RECEIVER_WAITING);
+                }
+            }
+        }
+    }
+
+    /// Whether the cell at the head is still unpublished.
+    fn head_unpublished(&self) -> bool {
+        let cell = self.queue.cell_at(self.local_head);
+        cell.epoch().load(Ordering::Acquire) < self.local_head.wrapping_add(1)
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// Read from the shared head and tail while handles in other processes may
+    /// be moving them, so this is an approximate snapshot. Items whose cell is
+    /// claimed but not yet written count as present.
+    #[inline]
+    pub fn len(&self) -> usize {
+        crate::ring::occupancy(self.queue.head(), self.queue.tail(), self.queue.capacity)
+    }
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: the requested capacity
+    /// rounded up to the next power of two, and to at least 2.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.stats.snapshot()
+    }
+}
+
+impl<T: Copy> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        // `Release` in the detach orders the last head store before it
+        self.queue.header().detach_receiver();
+    }
+}
+
+impl<T: Copy> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    /// Always `false`: senders in other processes can attach at any time and
+    /// can exit without detaching. See [`iter`](crate::iter).
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        false
+    }
+}
+
+impl<T: Copy> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T: Copy> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+unsafe impl<T: Copy + Send> Send for Receiver<T> {}
@@ -0,0 +1,369 @@
+use core::{mem::MaybeUninit, num::NonZeroUsize};
+use std::{io, os::fd::AsFd};
+
+use super::queue::ShmQueue;
+use crate::{
+    atomic::Ordering,
+    cell::{Unfilled, claim, claim_range},
+    futex::SENDER_WAITING,
+    stats::Counters,
+};
+
+/// The producer end of a shared-memory MPSC queue.
+///
+/// Any number of senders may attach, in any number of processes. This struct is
+/// `Clone` and `Send`; clones share one mapping of the queue.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use core::num::NonZeroUsize;
+/// use gil::mpsc::shm::{Receiver, Sender};
+///
+/// let file = memfd();
+/// let mut rx = Receiver::<u32>::create(&file, NonZeroUsize::new(16).unwrap()).unwrap();
+/// let tx = Sender::<u32>::open(&file).unwrap();
+///
+/// let mut tx2 = tx.clone();
+/// thread::spawn(move || tx2.send(1)).join().unwrap();
+/// let mut tx3 = tx.clone();
+/// thread::spawn(move || tx3.send(2)).join().unwrap();
+///
+/// assert_eq!(rx.recv() + rx.recv(), 3);
+/// # fn memfd() -> std::fs::File {
+/// #     use std::os::fd::FromRawFd;
+/// #     let fd = unsafe { libc::memfd_create(c"gil".as_ptr(), 0) };
+/// #     assert!(fd >= 0);
+/// #     unsafe { std::fs::File::from_raw_fd(fd) }
+/// # }
+/// ```
+pub struct Sender<T: Copy> {
+    queue: crate::Arc<ShmQueue<T>>,
+    local_tail: usize,
+    stats: Counters,
+}
+
+impl<T: Copy> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+            local_tail: self.local_tail,
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+impl<T: Copy> Sender<T> {
+    /// Creates a new queue in `fd` and attaches to it as a sender.
+    ///
+    /// The file is truncated and resized to fit the queue; anything in it is
+    /// lost. `capacity` is rounded up to the next power of two, with a minimum
+    /// of 2.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from resizing or mapping the file.
+    pub fn create(fd: &impl AsFd, capacity: NonZeroUsize) -> io::Result<Self> {
+        Ok(Self::attach(ShmQueue::create(fd.as_fd(), capacity)?))
+    }
+
+    /// Attaches as a sender to a queue another handle created in `fd`.
+    ///
+    /// # Errors
+    ///
+    /// * [`WouldBlock`](io::ErrorKind::WouldBlock) if the creator has not finished
+    ///   initialising the queue.
+    /// * [`InvalidData`](io::ErrorKind::InvalidData) if `fd` does not hold an
+    ///   mpsc queue of `T` with this crate's layout version.
+    pub fn open(fd: &impl AsFd) -> io::Result<Self> {
+        Ok(Self::attach(ShmQueue::open(fd.as_fd())?))
+    }
+
+    fn attach(queue: ShmQueue<T>) -> Self {
+        let local_tail = queue.tail().load(Ordering::Relaxed);
+        Self {
+            queue: crate::Arc::new(queue),
+            local_tail,
+            stats: Counters::default(),
+        }
+    }
+
+    /// Attempts to send a value into the queue without blocking.
+    ///
+    /// Returns `Err(value)` if the queue is full.
+    pub fn try_send(&mut self, value: T) -> Result<(), T> {
+        self.push(value).inspect_err(|_| self.stats.full())
+    }
+
+    /// [`try_send`](Self::try_send) without the full-queue count, so the
+    /// blocking loops record one full event per send rather than per retry.
+    fn push(&mut self, value: T) -> Result<(), T> {
+        let Some(cell) = claim(&*self.queue, &mut self.local_tail, || {
+            self.stats.cas_retry()
+        }) else {
+            return Err(value);
+        };
+
+        cell.set(value);
+        cell.epoch().store(self.local_tail, Ordering::Release);
+        self.published(1);
+
+        Ok(())
+    }
+
+    /// Sends a value into the queue, blocking if necessary.
+    ///
+    /// Spins briefly, then yields, and finally parks on the queue's shared
+    /// futex until the receiver, in whichever process, frees a slot.
+    pub fn send(&mut self, value: T) {
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        let mut sent = self.try_send(value);
+        while sent.is_err() {
+            self.wait(&mut backoff);
+            sent = self.push(value);
+        }
+    }
+
+    /// Constructs the next item directly in its cell, blocking while the queue
+    /// is full.
+    ///
+    /// Waits like [`send`](Self::send), then calls `f` on the claimed cell,
+    /// which saves the copy into shared memory for large `T`. The item is
+    /// published when `f` returns.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    ///
+    /// # Panics
+    ///
+    /// The cell is claimed from the other senders before `f` runs and the
+    /// claim cannot be undone. If `f` panics, the cell is published as skipped:
+    /// the panic propagates, nothing is sent, and the receiver steps over it.
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        let Err(mut f) = self.try_send_with(f) else {
+            return;
+        };
+
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        loop {
+            self.wait(&mut backoff);
+            match self.push_with(f) {
+                Ok(()) => return,
+                Err(ret) => f = ret,
+            }
+        }
+    }
+
+    /// Non-blocking [`send_with`](Self::send_with). Returns `Err(f)` without
+    /// calling it if the queue is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.push_with(f).inspect_err(|_| self.stats.full())
+    }
+
+    /// [`try_send_with`](Self::try_send_with) without the full-queue count.
+    fn push_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        let (start, n) = claim_range(&*self.queue, &mut self.local_tail, 1, || {
+            self.stats.cas_retry()
+        });
+        if n == 0 {
+            return Err(f);
+        }
+
+        // skips the cell if `f` panics
+        Unfilled::new(&*self.queue, start, 1).fill_with(f);
+        self.published(1);
+
+        Ok(())
+    }
+
+    /// Counts `n` newly published items and wakes the receiver.
+    #[inline(always)]
+    fn published(&mut self, n: usize) {
+        self.stats.sent(n);
+        self.stats.observe(|| {
+            crate::ring::occupancy(self.queue.head(), self.queue.tail(), self.queue.capacity)
+        });
+        self.queue.futex().wake_shared();
+    }
+
+    /// Sends every item of `values`, blocking while the queue is full.
+    ///
+    /// Claims all the free cells it can fill with one compare-exchange on the
+    /// shared tail, instead of one per item, then copies into them.
+    pub fn send_slice(&mut self, mut values: &[T]) {
+        loop {
+            values = &values[self.push_slice(values)..];
+            let Some((&first, rest)) = values.split_first() else {
+                return;
+            };
+            // full: wait for a cell the usual way, then go back to ranges
+            self.send(first);
+            values = rest;
+        }
+    }
+
+    /// Sends items from `iter` until it runs dry or the queue fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// Items are only taken from `iter` once there is a cell for them; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest. Cells are claimed a
+    /// range at a time for as many items as `iter`'s
+    /// [`size_hint`](Iterator::size_hint) guarantees, and one at a time
+    /// beyond that.
+    ///
+    /// # Panics
+    ///
+    /// Panics from `iter` propagate, after the items already taken are sent.
+    /// Claimed cells cannot be given back to the other senders, so any that
+    /// `iter` doesn't fill, whether it panics or yields fewer items than its
+    /// `size_hint` promised, are published as skipped and the receiver steps
+    /// over them.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let (Ok(sent) | Err(sent)) = self.push_iter(&mut iter);
+        sent
+    }
+
+    /// Sends every item of `iter`, blocking while the queue is full.
+    ///
+    /// Claims cells like [`try_send_iter`](Self::try_send_iter), and skips
+    /// the unfilled ones in the same cases.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        let mut iter = iter.into_iter().peekable();
+        loop {
+            if self.push_iter(&mut iter).is_ok() {
+                return;
+            }
+            let Some(value) = iter.next() else {
+                return;
+            };
+            self.send(value);
+        }
+    }
+
+    /// Copies the head of `values` into one claimed range of cells. Returns
+    /// the number sent; zero if the queue is full.
+    fn push_slice(&mut self, values: &[T]) -> usize {
+        if values.is_empty() {
+            return 0;
+        }
+
+        let (start, n) = claim_range(&*self.queue, &mut self.local_tail, values.len(), || {
+            self.stats.cas_retry()
+        });
+        for (i, &value) in values[..n].iter().enumerate() {
+            let ticket = start.wrapping_add(i);
+            let cell = self.queue.cell_at(ticket);
+            cell.set(value);
+            cell.epoch()
+                .store(ticket.wrapping_add(1), Ordering::Release);
+        }
+
+        if n > 0 {
+            self.published(n);
+        }
+        n
+    }
+
+    /// Moves items from `iter` into free cells. Returns `Ok(sent)` once
+    /// `iter` runs dry and `Err(sent)` if the queue fills up first.
+    fn push_iter(&mut self, iter: &mut impl Iterator<Item = T>) -> Result<usize, usize> {
+        let mut sent = 0;
+        loop {
+            let (promised, limit) = iter.size_hint();
+            if limit == Some(0) {
+                return Ok(sent);
+            }
+            // with nothing promised, claim one cell to see whether another
+            // item comes
+            let want = promised.max(1);
+            let (start, n) = claim_range(&*self.queue, &mut self.local_tail, want, || {
+                self.stats.cas_retry()
+            });
+            if n == 0 {
+                return Err(sent);
+            }
+
+            // cells left unfilled, by a short or panicking `iter`, are skipped
+            let mut cells = Unfilled::new(&*self.queue, start, n);
+            while cells.len() > 0 {
+                let Some(value) = iter.next() else {
+                    let filled = n - cells.len();
+                    drop(cells);
+                    if filled > 0 {
+                        self.published(filled);
+                    }
+                    return Ok(sent + filled);
+                };
+                cells.fill(value);
+            }
+            drop(cells);
+
+            self.published(n);
+            sent += n;
+        }
+    }
+
+    /// One step of the blocking sends' wait for a free cell: backs off, and
+    /// parks on the shared futex once the backoff runs out.
+    fn wait(&mut self, backoff: &mut crate::ParkingBackoff) {
+        if backoff.backoff() {
+            let futex = self.queue.futex();
+            if futex.announce(SENDER_WAITING) && self.next_cell_busy() {
+                self.stats.park();
+                futex.sleep_shared(SENDER_WAITING);
+            }
+        }
+    }
+
+    /// Whether the next cell is still unread, against a fresh tail.
+    fn next_cell_busy(&mut self) -> bool {
+        self.local_tail = self.queue.tail().load(Ordering::Relaxed);
+        let cell = self.queue.cell_at(self.local_tail);
+        cell.epoch().load(Ordering::Acquire) < self.local_tail
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// Read from the shared head and tail while handles in other processes may
+    /// be moving them, so this is an approximate snapshot. Items whose cell is
+    /// claimed but not yet written count as present.
+    #[inline]
+    pub fn len(&self) -> usize {
+        crate::ring::occupancy(self.queue.head(), self.queue.tail(), self.queue.capacity)
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: the requested capacity
+    /// rounded up to the next power of two, and to at least 2.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.queue.capacity
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.stats.snapshot()
+    }
+}
+
+unsafe impl<T: Copy + Send> Send for Sender<T> {}
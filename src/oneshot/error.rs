@@ -0,0 +1,41 @@
+use core::fmt;
+
+/// Error returned by [`Receiver::recv`](super::Receiver::recv) when the sender was
+/// dropped without sending a value, or the value was already received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("oneshot sender dropped without sending")
+    }
+}
+
+impl core::error::Error for RecvError {}
+
+/// Error returned by [`Receiver::try_recv`](super::Receiver::try_recv).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// The value has not been sent yet.
+    Empty,
+    /// The sender was dropped without sending a value, or the value was already
+    /// received.
+    Disconnected,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("oneshot channel is empty"),
+            Self::Disconnected => f.write_str("oneshot sender dropped without sending"),
+        }
+    }
+}
+
+impl core::error::Error for TryRecvError {}
+
+impl From<RecvError> for TryRecvError {
+    fn from(_: RecvError) -> Self {
+        Self::Disconnected
+    }
+}
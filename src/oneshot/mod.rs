@@ -0,0 +1,190 @@
+//! Oneshot channel: a single value sent from one thread to another.
+//!
+//! Built for request/response patterns, where a whole [`spsc::channel`] with its
+//! padded indices and waker slots would be a heavy way to carry one reply.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use gil::oneshot::channel;
+//!
+//! let (tx, rx) = channel::<usize>();
+//!
+//! thread::spawn(move || {
+//!     tx.send(42).unwrap();
+//! });
+//!
+//! assert_eq!(rx.recv(), Ok(42));
+//! ```
+//!
+//! ## Async
+//!
+//! With the `async` feature, [`Receiver`] implements [`Future`](core::future::Future).
+//!
+//! ```rust,ignore
+//! use gil::oneshot::channel;
+//!
+//! // In an async context:
+//! let (tx, rx) = channel::<usize>();
+//! tx.send(42).unwrap();
+//! assert_eq!(rx.await, Ok(42));
+//! ```
+//!
+//! # Performance
+//!
+//! - **Single Allocation:** A small state word and the value's slot share one
+//!   allocation; there are no indices and no cache-line padding, since only one
+//!   value ever crosses the channel.
+//! - **Non-blocking Send:** [`Sender::send`] is a write and a single CAS.
+//! - **Parking:** Under `std`, a blocked [`Receiver::recv`] parks on a futex after
+//!   a short spin. Without `std` it spins.
+//!
+//! # Gotchas
+//!
+//! - **Disconnection:** Dropping the [`Sender`] without sending makes the receiver
+//!   return [`RecvError`]. Dropping the [`Receiver`] makes [`Sender::send`] hand the
+//!   value back.
+//!
+//! [`spsc::channel`]: crate::spsc::channel
+
+pub use self::{
+    error::{RecvError, TryRecvError},
+    receiver::Receiver,
+    sender::Sender,
+};
+
+mod error;
+mod queue;
+mod receiver;
+mod sender;
+
+/// Creates a new oneshot channel.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Examples
+///
+/// ```
+/// use gil::oneshot::channel;
+///
+/// let (tx, rx) = channel::<usize>();
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let queue = queue::QueuePtr::with_size(core::num::NonZeroUsize::MIN);
+
+    (Sender::new(queue.clone()), Receiver::new(queue))
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use crate::{Box, thread};
+
+    #[test]
+    fn test_send_recv() {
+        let (tx, rx) = channel::<Box<usize>>();
+
+        thread::spawn(move || tx.send(Box::new(7)).unwrap());
+        assert_eq!(rx.recv(), Ok(Box::new(7)));
+    }
+
+    #[test]
+    fn test_disconnect() {
+        let (tx, mut rx) = channel::<usize>();
+        drop(tx);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+
+        let (tx, rx) = channel::<usize>();
+        let handle = thread::spawn(move || rx.recv());
+        thread::sleep(std::time::Duration::from_millis(10));
+        drop(tx);
+        assert_eq!(handle.join().unwrap(), Err(RecvError));
+    }
+
+    #[test]
+    fn test_drop_unreceived() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Debug)]
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        let (tx, rx) = channel();
+        tx.send(DropCounter(dropped.clone())).unwrap();
+        drop(rx);
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+
+        let (tx, rx) = channel();
+        drop(rx);
+        let value = tx.send(DropCounter(dropped.clone())).unwrap_err();
+        assert_eq!(dropped.load(Ordering::SeqCst), 1);
+        drop(value);
+        assert_eq!(dropped.load(Ordering::SeqCst), 2);
+
+        let (tx, rx) = channel();
+        tx.send(DropCounter(dropped.clone())).unwrap();
+        drop(rx.recv().unwrap());
+        assert_eq!(dropped.load(Ordering::SeqCst), 3);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_recv() {
+        let (tx, rx) = channel::<usize>();
+
+        thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(10));
+            tx.send(42).unwrap();
+        });
+        assert_eq!(futures::executor::block_on(rx), Ok(42));
+
+        let (tx, rx) = channel::<usize>();
+        thread::spawn(move || drop(tx));
+        assert_eq!(futures::executor::block_on(rx), Err(RecvError));
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn send_races_receiver_drop_loom() {
+        loom::model(|| {
+            let (tx, rx) = channel::<loom::sync::Arc<usize>>();
+            let value = loom::sync::Arc::new(1);
+            let probe = value.clone();
+
+            let handle = thread::spawn(move || tx.send(value).is_ok());
+            drop(rx);
+            handle.join().unwrap();
+
+            assert_eq!(loom::sync::Arc::strong_count(&probe), 1);
+        })
+    }
+
+    #[test]
+    fn recv_sees_send_loom() {
+        loom::model(|| {
+            let (tx, rx) = channel::<usize>();
+
+            let handle = thread::spawn(move || {
+                let _ = tx.send(42);
+            });
+            assert_eq!(rx.recv(), Ok(42));
+            handle.join().unwrap();
+        })
+    }
+}
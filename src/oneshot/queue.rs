@@ -0,0 +1,91 @@
+//! Single-slot layout for the oneshot channel.
+//!
+//! A oneshot moves exactly one value, so the header is a small state machine
+//! instead of head/tail indices: no padding, and one slot of buffer. The state
+//! word is the only thing both endpoints write; the futex word and the waker are
+//! touched only by a blocked receiver and the sender waking it.
+
+use core::ptr::NonNull;
+#[cfg(feature = "async")]
+use core::task::Waker;
+
+#[cfg(feature = "async")]
+use futures::task::AtomicWaker;
+
+use crate::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use crate::{atomic::AtomicU32, futex::HasFutex};
+
+/// No value yet, both endpoints alive.
+pub(crate) const EMPTY: usize = 0;
+/// The sender has written the value and published it.
+pub(crate) const FULL: usize = 1;
+/// The receiver has moved the value out.
+pub(crate) const TAKEN: usize = 2;
+/// One endpoint dropped before a value was exchanged.
+pub(crate) const CLOSED: usize = 3;
+
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Head {
+    state: AtomicUsize,
+    #[cfg(feature = "std")]
+    futex: AtomicU32,
+    #[cfg(feature = "async")]
+    receiver_waker: AtomicWaker,
+}
+
+#[cfg(feature = "std")]
+impl HasFutex for Head {
+    #[inline(always)]
+    fn futex(&self) -> &AtomicU32 {
+        &self.futex
+    }
+}
+
+/// Drops the value if it was sent but never received.
+pub(crate) struct DropUnreceived;
+
+impl<T> crate::DropInFlight<Head, (), T> for DropUnreceived {
+    unsafe fn drop_in_flight(
+        head: &Head,
+        _tail: &(),
+        _capacity: usize,
+        at: impl Fn(usize) -> NonNull<T>,
+    ) {
+        if core::mem::needs_drop::<T>() && head.state.load(Ordering::Acquire) == FULL {
+            unsafe { at(0).drop_in_place() };
+        }
+    }
+}
+
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, (), T, DropUnreceived>;
+
+impl<T> QueuePtr<T> {
+    #[inline(always)]
+    pub(crate) fn state(&self) -> &AtomicUsize {
+        &self.header().head.state
+    }
+
+    #[inline(always)]
+    pub(crate) fn slot(&self) -> NonNull<T> {
+        // SAFETY: the buffer always has exactly one slot.
+        unsafe { self.exact_at(0) }
+    }
+
+    /// Wakes a receiver blocked on this channel, however it is blocked. Call
+    /// after publishing a new state.
+    #[inline(always)]
+    pub(crate) fn wake_receiver(&self) {
+        #[cfg(feature = "std")]
+        self.futex().wake();
+        #[cfg(feature = "async")]
+        self.header().head.receiver_waker.wake();
+    }
+
+    #[cfg(feature = "async")]
+    #[inline(always)]
+    pub(crate) fn register_receiver_waker(&self, waker: &Waker) {
+        self.header().head.receiver_waker.register(waker);
+    }
+}
@@ -0,0 +1,173 @@
+#[cfg(feature = "async")]
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::atomic::Ordering;
+#[cfg(feature = "std")]
+use crate::futex::RECEIVER_WAITING;
+
+use super::{
+    RecvError, TryRecvError,
+    queue::{CLOSED, EMPTY, FULL, QueuePtr, TAKEN},
+};
+
+/// The receiving end of a oneshot channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. With the `async` feature it is
+/// also a [`Future`] resolving to the sent value.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use gil::oneshot::channel;
+///
+/// let (tx, rx) = channel::<i32>();
+/// thread::spawn(move || tx.send(42).unwrap());
+/// assert_eq!(rx.recv(), Ok(42));
+/// ```
+pub struct Receiver<T> {
+    ptr: QueuePtr<T>,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self { ptr }
+    }
+
+    /// Attempts to receive the value without blocking.
+    ///
+    /// Returns [`TryRecvError::Empty`] if the value has not been sent yet, and
+    /// [`TryRecvError::Disconnected`] if the sender was dropped without sending or
+    /// the value was already received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::oneshot::{TryRecvError, channel};
+    ///
+    /// let (tx, mut rx) = channel::<i32>();
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// tx.send(42).unwrap();
+    /// assert_eq!(rx.try_recv(), Ok(42));
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        match self.ptr.state().load(Ordering::Acquire) {
+            FULL => {
+                // SAFETY: `FULL` was published by the sender after writing the
+                // slot, and the `Acquire` load synchronised with it. Moving to
+                // `TAKEN` below keeps the slot from being read or dropped again;
+                // no other endpoint writes the state once it is `FULL`.
+                let value = unsafe { self.ptr.slot().read() };
+                self.ptr.state().store(TAKEN, Ordering::Relaxed);
+                Ok(value)
+            }
+            EMPTY => Err(TryRecvError::Empty),
+            _ => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    /// Receives the value, blocking until it is sent.
+    ///
+    /// Returns [`RecvError`] if the sender is dropped without sending. Under `std`
+    /// the receiver parks on a futex after a short spin; without `std` it spins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use gil::oneshot::{RecvError, channel};
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    /// thread::spawn(move || tx.send(42).unwrap());
+    /// assert_eq!(rx.recv(), Ok(42));
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    /// thread::spawn(move || drop(tx));
+    /// assert_eq!(rx.recv(), Err(RecvError));
+    /// ```
+    pub fn recv(mut self) -> Result<T, RecvError> {
+        #[cfg(feature = "std")]
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        #[cfg(not(feature = "std"))]
+        let mut backoff = crate::Backoff::with_spin_count(128);
+
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            #[cfg(feature = "std")]
+            if backoff.backoff() {
+                let futex = self.ptr.futex();
+                // catch lost wakes: recheck the state before parking
+                if futex.announce(RECEIVER_WAITING)
+                    && self.ptr.state().load(Ordering::Relaxed) == EMPTY
+                {
+                    futex.sleep(RECEIVER_WAITING);
+                }
+            }
+
+            #[cfg(not(feature = "std"))]
+            backoff.backoff();
+        }
+    }
+
+    /// Returns `true` if the value has been sent and not yet received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::oneshot::channel;
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    /// assert!(!rx.is_ready());
+    /// tx.send(1).unwrap();
+    /// assert!(rx.is_ready());
+    /// ```
+    pub fn is_ready(&self) -> bool {
+        self.ptr.state().load(Ordering::Relaxed) == FULL
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.try_recv() {
+            Ok(value) => return Poll::Ready(Ok(value)),
+            Err(TryRecvError::Disconnected) => return Poll::Ready(Err(RecvError)),
+            Err(TryRecvError::Empty) => {}
+        }
+
+        self.ptr.register_receiver_waker(cx.waker());
+
+        // recheck after registering so a send racing the registration is not lost
+        match self.try_recv() {
+            Ok(value) => Poll::Ready(Ok(value)),
+            Err(TryRecvError::Disconnected) => Poll::Ready(Err(RecvError)),
+            Err(TryRecvError::Empty) => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        // tell a sender that has not sent yet that nobody is listening; a value
+        // already sent is dropped with the allocation
+        _ = self
+            .ptr
+            .state()
+            .compare_exchange(EMPTY, CLOSED, Ordering::Relaxed, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
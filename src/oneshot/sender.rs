@@ -0,0 +1,99 @@
+use crate::atomic::Ordering;
+
+use super::queue::{CLOSED, EMPTY, FULL, QueuePtr};
+
+/// The sending end of a oneshot channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. Dropping it without calling
+/// [`send`](Sender::send) disconnects the channel, waking the receiver with an
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use gil::oneshot::channel;
+///
+/// let (tx, rx) = channel::<i32>();
+/// thread::spawn(move || tx.send(42).unwrap());
+/// assert_eq!(rx.recv(), Ok(42));
+/// ```
+pub struct Sender<T> {
+    ptr: QueuePtr<T>,
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self { ptr }
+    }
+
+    /// Sends the value, consuming the sender.
+    ///
+    /// Never blocks. Returns `Err(value)` if the receiver was already dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::oneshot::channel;
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    /// assert_eq!(tx.send(1), Ok(()));
+    /// assert_eq!(rx.recv(), Ok(1));
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    /// drop(rx);
+    /// assert_eq!(tx.send(1), Err(1));
+    /// ```
+    pub fn send(self, value: T) -> Result<(), T> {
+        let slot = self.ptr.slot();
+        // SAFETY: the slot is only written here, and `self` is consumed so this
+        // runs at most once. The receiver does not read it until it sees `FULL`.
+        unsafe { slot.write(value) };
+
+        match self
+            .ptr
+            .state()
+            .compare_exchange(EMPTY, FULL, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => {
+                self.ptr.wake_receiver();
+                Ok(())
+            }
+            // the receiver is gone; it never saw `FULL`, so the value is still ours
+            Err(_) => Err(unsafe { slot.read() }),
+        }
+    }
+
+    /// Returns `true` if the receiver has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::oneshot::channel;
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    /// assert!(!tx.is_closed());
+    /// drop(rx);
+    /// assert!(tx.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        self.ptr.state().load(Ordering::Relaxed) == CLOSED
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        // a no-op if `send` already ran: the state is then `FULL`, `TAKEN`, or
+        // `CLOSED` by the receiver
+        if self
+            .ptr
+            .state()
+            .compare_exchange(EMPTY, CLOSED, Ordering::Release, Ordering::Relaxed)
+            .is_ok()
+        {
+            self.ptr.wake_receiver();
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
@@ -0,0 +1,271 @@
+//! The overwriting (lossy) ring core.
+//!
+//! Unlike [`crate::ring`], where only the consumer ever advances the head, a full
+//! overwriting ring lets the *producer* advance the head too: it evicts the oldest
+//! unread item to make room instead of waiting. That needs a head both sides can
+//! race on, so this core runs on Vyukov [`Cell`]s rather than the plain SPSC
+//! buffer:
+//!
+//! * The consumer claims the item at `head` with a CAS on the head, exactly like
+//!   the MPMC receiver, and frees the cell for the next lap once it has moved the
+//!   value out.
+//! * A producer that finds its cell still holding last lap's item evicts it with
+//!   the *same* CAS on the head. Whichever side wins the CAS owns the item; the
+//!   loser moves on. A producer that loses to the consumer only waits for the
+//!   consumer to finish moving the value out of that one cell, never for the
+//!   consumer to make further progress.
+//!
+//! Because the head only moves by consumer reads and producer evictions, the
+//! number of items overwritten since the consumer's last read is simply how far
+//! the head has moved past it — no extra counter on the hot path.
+//!
+//! The producer side claims the tail with a CAS, so the same [`Producer`] serves
+//! both the single- and multi-producer channels.
+
+use core::{cmp::Ordering as Cmp, num::NonZeroUsize};
+
+use crate::{
+    atomic::{AtomicUsize, Ordering},
+    cell::{Cell, DropTailScan},
+    padded::Padded,
+    ring::{RingHead, RingTail},
+};
+
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Head {
+    head: Padded<AtomicUsize>,
+}
+
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Tail {
+    tail: Padded<AtomicUsize>,
+}
+
+impl RingHead for Head {
+    #[inline(always)]
+    fn head(&self) -> &AtomicUsize {
+        &self.head.value
+    }
+}
+
+impl RingTail for Tail {
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        &self.tail.value
+    }
+}
+
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, Tail, Cell<T>, DropTailScan>;
+
+impl<T> QueuePtr<T> {
+    /// Allocates and initialises an overwriting ring of at least `capacity` items.
+    ///
+    /// The capacity is clamped to at least 2: with a single cell, "written on this
+    /// lap" and "free for the next lap" would share the same epoch.
+    pub(crate) fn with_capacity(capacity: NonZeroUsize) -> Self {
+        let capacity = capacity.max(NonZeroUsize::new(2).unwrap());
+        let queue = Self::with_size(capacity);
+        queue.initialize::<crate::cell::CellInit<T>>();
+        queue
+    }
+
+    #[inline(always)]
+    fn head(&self) -> &AtomicUsize {
+        self.header().head.head()
+    }
+
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        self.header().tail.tail()
+    }
+
+    #[inline(always)]
+    fn cell_at(&self, index: usize) -> &Cell<T> {
+        // SAFETY: `at` masks the index into the buffer, and every cell is valid
+        // for shared access (epoch initialised at construction, payload behind
+        // `UnsafeCell<MaybeUninit<_>>`).
+        unsafe { self.at(index).as_ref() }
+    }
+}
+
+/// The producer cursor over an overwriting ring.
+pub(crate) struct Producer<T> {
+    ptr: QueuePtr<T>,
+    local_tail: usize,
+}
+
+impl<T> Clone for Producer<T> {
+    fn clone(&self) -> Self {
+        self.ptr.attach_sender();
+        Self {
+            ptr: self.ptr.clone(),
+            local_tail: self.local_tail,
+        }
+    }
+}
+
+impl<T> Drop for Producer<T> {
+    fn drop(&mut self) {
+        self.ptr.detach_sender();
+    }
+}
+
+impl<T> Producer<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        ptr.attach_sender();
+        Self { ptr, local_tail: 0 }
+    }
+
+    /// Publishes `value`, evicting the oldest unread item if the ring is full.
+    ///
+    /// Returns the evicted item, if any. Under multiple producers a push can race
+    /// other producers for the slot it freed and end up evicting again; only the
+    /// last evicted item is returned, earlier ones are dropped here.
+    pub(crate) fn push(&mut self, value: T) -> Option<T> {
+        let mut evicted = None;
+        let mut backoff = crate::ExponentialBackoff::new(6, 10);
+
+        let cell = loop {
+            let cell = self.ptr.cell_at(self.local_tail);
+            let epoch = cell.epoch().load(Ordering::Acquire);
+
+            match epoch.cmp(&self.local_tail) {
+                // the cell still belongs to the previous lap
+                Cmp::Less => {
+                    let victim = self.local_tail.wrapping_sub(self.ptr.capacity);
+                    // written and unread: race the consumer for it on the head. if
+                    // the consumer wins it frees the cell as soon as the value is
+                    // moved out; otherwise a previous-lap write is still in flight
+                    if epoch == victim.wrapping_add(1)
+                        && self
+                            .ptr
+                            .head()
+                            .compare_exchange(
+                                victim,
+                                victim.wrapping_add(1),
+                                Ordering::Relaxed,
+                                Ordering::Relaxed,
+                            )
+                            .is_ok()
+                    {
+                        // SAFETY: winning the head CAS makes us the only owner of
+                        // the item, and the `Acquire` epoch load above synchronised
+                        // with its producer.
+                        evicted = Some(unsafe { cell.get() });
+                        cell.epoch().store(self.local_tail, Ordering::Release);
+                        continue;
+                    }
+                }
+
+                // cell is free for this lap
+                Cmp::Equal => {
+                    let next = self.local_tail.wrapping_add(1);
+                    match self.ptr.tail().compare_exchange_weak(
+                        self.local_tail,
+                        next,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => {
+                            self.local_tail = next;
+                            break cell;
+                        }
+                        Err(cur_tail) => self.local_tail = cur_tail,
+                    }
+                }
+
+                // some other producer has written to this cell before us
+                Cmp::Greater => self.local_tail = self.ptr.tail().load(Ordering::Relaxed),
+            }
+
+            backoff.backoff();
+        };
+
+        cell.set(value);
+        cell.epoch().store(self.local_tail, Ordering::Release);
+
+        evicted
+    }
+}
+
+/// The consumer cursor over an overwriting ring.
+pub(crate) struct Consumer<T> {
+    ptr: QueuePtr<T>,
+    /// Best guess of the head; corrected on a lost CAS.
+    local_head: usize,
+    /// One past the index of the last item this consumer read.
+    read: usize,
+}
+
+impl<T> Consumer<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self {
+            ptr,
+            local_head: 0,
+            read: 0,
+        }
+    }
+
+    /// Items evicted by producers since the last successful pop.
+    #[inline]
+    pub(crate) fn overwritten(&self) -> usize {
+        self.ptr
+            .head()
+            .load(Ordering::Relaxed)
+            .wrapping_sub(self.read)
+    }
+
+    /// Whether every producer has been dropped.
+    #[inline]
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.ptr.is_disconnected()
+    }
+
+    pub(crate) fn try_pop(&mut self) -> Option<T> {
+        let mut backoff = crate::ExponentialBackoff::new(6, 10);
+
+        loop {
+            let cell = self.ptr.cell_at(self.local_head);
+            let epoch = cell.epoch().load(Ordering::Acquire);
+            let next_head = self.local_head.wrapping_add(1);
+
+            match epoch.cmp(&next_head) {
+                // nothing written since the last read
+                Cmp::Less => return None,
+
+                // written: claim it against an evicting producer
+                Cmp::Equal => {
+                    match self.ptr.head().compare_exchange_weak(
+                        self.local_head,
+                        next_head,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => {
+                            // SAFETY: the head CAS made this consumer the only owner
+                            // of the item.
+                            let ret = unsafe { cell.get() };
+                            cell.epoch().store(
+                                self.local_head.wrapping_add(self.ptr.capacity),
+                                Ordering::Release,
+                            );
+
+                            self.local_head = next_head;
+                            self.read = next_head;
+                            return Some(ret);
+                        }
+                        // a producer evicted this item before we could claim it
+                        Err(cur_head) => self.local_head = cur_head,
+                    }
+                }
+
+                // the cell was evicted and rewritten on a later lap
+                Cmp::Greater => self.local_head = self.ptr.head().load(Ordering::Relaxed),
+            }
+
+            backoff.backoff();
+        }
+    }
+}
@@ -1,12 +1,12 @@
 #[derive(Default)]
-#[repr(align(64))]
+#[repr(C, align(64))]
 #[cfg_attr(all(target_arch = "aarch64", target_os = "macos"), repr(align(128)))]
 pub(crate) struct Padded<T> {
     pub(crate) value: T,
 }
 
 impl<T> Padded<T> {
-    pub(crate) fn new(value: T) -> Self {
+    pub(crate) const fn new(value: T) -> Self {
         Self { value }
     }
 }
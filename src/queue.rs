@@ -2,6 +2,7 @@ use core::{marker::PhantomData, num::NonZeroUsize, ptr::NonNull};
 
 use crate::{
     alloc,
+    allocator::{Allocator, Global, Release},
     atomic::{AtomicUsize, Ordering},
 };
 
@@ -91,6 +92,9 @@ pub(crate) struct Queue<H, T, O: Ownership = RefCounted> {
     pub(crate) head: H,
     pub(crate) tail: T,
     ownership: O::State,
+    /// Live sender handles; see [`QueuePtr::attach_sender`].
+    senders: AtomicUsize,
+    release: Release,
 }
 
 /// Teardown policy: drops the in-flight items (sent but not yet received) when
@@ -110,6 +114,9 @@ pub struct QueuePtr<H, T, I, G: DropInFlight<H, T, I>, O: Ownership = RefCounted
     pub(crate) size: usize,
     pub(crate) mask: usize,
     pub(crate) capacity: usize,
+    /// Slots readable past the buffer start: `capacity`, or twice that for a
+    /// mirrored buffer.
+    pub(crate) span: usize,
     owner: O::Handle,
     _marker: PhantomData<G>,
 }
@@ -128,30 +135,58 @@ where
     O: Ownership,
 {
     pub(crate) fn with_size(size: NonZeroUsize) -> Self {
+        Self::with_size_in(size, Global)
+    }
+
+    /// Like [`with_size`](Self::with_size), allocating from `allocator`.
+    pub(crate) fn with_size_in<A: Allocator + Send + 'static>(
+        size: NonZeroUsize,
+        allocator: A,
+    ) -> Self {
+        Self::with_header_in(size, H::default(), T::default(), allocator)
+    }
+}
+
+impl<H, T, I, G, O> QueuePtr<H, T, I, G, O>
+where
+    G: DropInFlight<H, T, I>,
+    O: Ownership,
+{
+    /// Like [`with_size`](Self::with_size), for headers that need runtime state.
+    pub(crate) fn with_header(size: NonZeroUsize, head: H, tail: T) -> Self {
+        Self::with_header_in(size, head, tail, Global)
+    }
+
+    /// Like [`with_header`](Self::with_header), allocating from `allocator`.
+    ///
+    /// The allocator moves into the allocation and frees it on teardown.
+    pub(crate) fn with_header_in<A: Allocator + Send + 'static>(
+        size: NonZeroUsize,
+        head: H,
+        tail: T,
+        allocator: A,
+    ) -> Self {
         // Round up to power of 2 so we can use mask
         let size = size.get();
         let capacity = size.next_power_of_two();
 
         let (layout, buffer_offset) = Self::layout(capacity);
-
-        // SAFETY: capacity > 0, so layout is non-zero too
-        let Some(ptr) = NonNull::new(unsafe { alloc::alloc(layout) }) else {
-            alloc::handle_alloc_error(layout);
-        };
+        let (ptr, release) = Release::allocate(layout, allocator);
         let ptr = ptr.cast::<Queue<H, T, O>>();
 
         // calculate buffer pointer
-        // SAFETY: `ptr` is already checked by NonNull::new above, so this is guaranteed to be
-        // valid ptr too
+        // SAFETY: `ptr` is non-null, so this is guaranteed to be valid ptr too
         let buffer =
             unsafe { NonNull::new_unchecked(ptr.as_ptr().byte_add(buffer_offset).cast::<I>()) };
 
         unsafe {
             ptr.write(Queue {
-                head: H::default(),
-                tail: T::default(),
+                head,
+                tail,
 
                 ownership: O::initial_state(),
+                senders: AtomicUsize::new(0),
+                release,
             });
         };
 
@@ -161,12 +196,65 @@ where
             size,
             capacity,
             mask: capacity - 1,
+            span: capacity,
             owner: O::initial_handle(),
             _marker: PhantomData,
         }
     }
 }
 
+#[cfg(all(feature = "mirror", target_os = "linux", not(feature = "loom")))]
+impl<H, T, I, G, O> QueuePtr<H, T, I, G, O>
+where
+    G: DropInFlight<H, T, I>,
+    O: Ownership,
+{
+    /// Like [`with_header`](Self::with_header), but with the buffer mapped
+    /// twice back-to-back, so every run of slots is contiguous.
+    ///
+    /// Returns `None` if the buffer is not a whole number of pages or the
+    /// mapping fails.
+    pub(crate) fn with_header_mirrored(size: NonZeroUsize, head: H, tail: T) -> Option<Self> {
+        let size = size.get();
+        let capacity = size.next_power_of_two();
+
+        let page = crate::mirror::page_size();
+        let buffer_len = size_of::<I>().checked_mul(capacity)?;
+        if buffer_len == 0 || buffer_len % page != 0 || align_of::<Queue<H, T, O>>() > page {
+            return None;
+        }
+        // the buffer starts on a page boundary so the mirror can follow it
+        let buffer_offset = size_of::<Queue<H, T, O>>().next_multiple_of(page);
+
+        let (ptr, release) = crate::mirror::map(buffer_offset, buffer_len)?;
+        let ptr = ptr.cast::<Queue<H, T, O>>();
+        // SAFETY: in bounds of the mapping.
+        let buffer = unsafe { ptr.byte_add(buffer_offset).cast::<I>() };
+
+        unsafe {
+            ptr.write(Queue {
+                head,
+                tail,
+
+                ownership: O::initial_state(),
+                senders: AtomicUsize::new(0),
+                release,
+            });
+        };
+
+        Some(Self {
+            ptr,
+            buffer,
+            size,
+            capacity,
+            mask: capacity - 1,
+            span: 2 * capacity,
+            owner: O::initial_handle(),
+            _marker: PhantomData,
+        })
+    }
+}
+
 pub(crate) trait Initializer {
     type Item;
 
@@ -192,6 +280,31 @@ where
         &self.header().ownership
     }
 
+    /// Counts one more sender handle. Every sender registers when it is
+    /// created and [`detach_sender`](Self::detach_sender)s when it drops, so
+    /// receivers can tell when no more items can arrive.
+    #[inline]
+    pub(crate) fn attach_sender(&self) {
+        self.header().senders.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Uncounts a sender handle. Returns `true` if it was the last one; the
+    /// caller then wakes any parked receiver.
+    ///
+    /// `Release`, so a receiver that sees the count reach zero also sees every
+    /// item the senders published.
+    #[inline]
+    pub(crate) fn detach_sender(&self) -> bool {
+        self.header().senders.fetch_sub(1, Ordering::Release) == 1
+    }
+
+    /// Whether every sender has been dropped. Pairs with
+    /// [`detach_sender`](Self::detach_sender).
+    #[inline]
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.header().senders.load(Ordering::Acquire) == 0
+    }
+
     pub(crate) fn try_clone_as(&self, owner: O::Handle) -> Option<Self> {
         if O::try_acquire(self.ownership(), owner) {
             Some(Self {
@@ -200,6 +313,7 @@ where
                 size: self.size,
                 mask: self.mask,
                 capacity: self.capacity,
+                span: self.span,
                 owner,
                 _marker: PhantomData,
             })
@@ -243,14 +357,13 @@ where
 {
     fn drop(&mut self) {
         if O::release(self.ownership(), self.owner) {
-            let (layout, _) = Self::layout(self.capacity);
-
             let header = self.header();
+            let release = header.release;
             unsafe { G::drop_in_flight(&header.head, &header.tail, self.capacity, |i| self.at(i)) };
 
             unsafe {
                 self.ptr.drop_in_place();
-                alloc::dealloc(self.ptr.cast().as_ptr(), layout);
+                release.release(self.ptr.cast());
             }
         }
     }
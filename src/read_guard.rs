@@ -1,5 +1,6 @@
 use core::ptr::NonNull;
 
+#[cfg(feature = "alloc")]
 use alloc_crate::vec::Vec;
 
 /// Trait for queue receivers that support batch read operations.
@@ -7,8 +8,8 @@ use alloc_crate::vec::Vec;
 /// # Safety
 ///
 /// Implementations must ensure:
-/// - `read_buffer` returns a valid slice of initialized items that remain
-///   valid until `advance` is called
+/// - `read_buffer` and `read_slices` return valid slices of initialized items
+///   that remain valid until `advance` is called
 /// - `advance` publishes the new head to the producer (atomic store + wake)
 /// - `release` releases any held resources (e.g., shard locks),
 ///   and is a no-op when no resources are held
@@ -31,6 +32,16 @@ pub unsafe trait BatchReader {
     /// transferred. See the [trait-level ownership note](BatchReader#ownership).
     fn read_buffer(&mut self) -> &[Self::Item];
 
+    /// Returns the available items as two slices, like `VecDeque::as_slices`:
+    /// the run up to the end of the ring, then the items that wrapped to its
+    /// start. Advancing past both takes a single head update.
+    ///
+    /// The default returns [`read_buffer`](BatchReader::read_buffer) and an
+    /// empty second slice.
+    fn read_slices(&mut self) -> (&[Self::Item], &[Self::Item]) {
+        (self.read_buffer(), &[])
+    }
+
     /// Advance head by `n` and publish to the producer (atomic store + wake).
     ///
     /// Does **not** drop the items being advanced past. The producer may
@@ -56,30 +67,39 @@ pub unsafe trait BatchReader {
 ///
 /// Accumulates consumed items and advances the head in a single atomic
 /// store on drop.
+///
+/// The guard covers every item the receiver's
+/// [`read_slices`](BatchReader::read_slices) returned, including those that
+/// wrapped to the start of the ring. [`as_slice`](Self::as_slice) is the
+/// contiguous run at the front, which moves on to the wrapped items once the
+/// first run has been advanced past; [`as_slices`](Self::as_slices) and
+/// [`len`](Self::len) describe everything left.
 pub struct ReadGuard<'a, R: BatchReader> {
     receiver: &'a mut R,
     data: NonNull<[R::Item]>,
+    wrapped: NonNull<[R::Item]>,
     consumed: usize,
 }
 
 impl<'a, R: BatchReader> ReadGuard<'a, R> {
-    /// Calls the receiver's [`read_buffer`](BatchReader::read_buffer) and
-    /// converts the returned slice to [`NonNull`]. This is safe because the
+    /// Calls the receiver's [`read_slices`](BatchReader::read_slices) and
+    /// converts the returned slices to [`NonNull`]. This is safe because the
     /// receiver keeps the underlying queue allocation alive.
     pub(crate) fn new(receiver: &'a mut R) -> Self {
-        let slice = receiver.read_buffer();
-        let data = NonNull::from_ref(slice);
+        let (data, wrapped) = receiver.read_slices();
+        let (data, wrapped) = (NonNull::from_ref(data), NonNull::from_ref(wrapped));
         Self {
             receiver,
             data,
+            wrapped,
             consumed: 0,
         }
     }
 
-    /// Number of remaining unconsumed items.
+    /// Number of remaining unconsumed items, across both slices.
     #[inline]
     pub fn len(&self) -> usize {
-        unsafe { self.data.as_ref() }.len() - self.consumed
+        self.data.len() + self.wrapped.len() - self.consumed
     }
 
     /// Returns `true` if no items remain.
@@ -88,13 +108,32 @@ impl<'a, R: BatchReader> ReadGuard<'a, R> {
         self.len() == 0
     }
 
-    /// Zero-copy view of remaining unconsumed items.
+    /// Same as [`len`](Self::len); the counterpart of
+    /// [`WriteGuard::remaining`](crate::write_guard::WriteGuard::remaining).
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    /// Zero-copy view of the remaining unconsumed items in the current
+    /// contiguous run. See [`as_slices`](Self::as_slices) for all of them.
     #[inline]
     pub fn as_slice(&self) -> &[R::Item] {
-        unsafe { &self.data.as_ref()[self.consumed..] }
+        self.as_slices().0
     }
 
-    /// Mark `n` items as consumed.
+    /// Zero-copy view of all remaining unconsumed items: the current run, then
+    /// any items after it that wrapped to the start of the ring.
+    #[inline]
+    pub fn as_slices(&self) -> (&[R::Item], &[R::Item]) {
+        let (data, wrapped) = unsafe { (self.data.as_ref(), self.wrapped.as_ref()) };
+        match data.get(self.consumed..) {
+            Some(rest) if !rest.is_empty() => (rest, wrapped),
+            _ => (&wrapped[self.consumed - data.len()..], &[]),
+        }
+    }
+
+    /// Mark `n` items as consumed. `n` may reach into the wrapped items.
     ///
     /// Does not publish to the producer until the guard is dropped.
     ///
@@ -108,18 +147,21 @@ impl<'a, R: BatchReader> ReadGuard<'a, R> {
 
     /// Move remaining items into the user's vec.
     ///
-    /// Copies items in bulk via `memcpy`. After this call the guard's view
-    /// is empty.
+    /// Copies items in bulk via `memcpy`, both slices included. After this
+    /// call the guard's view is empty.
+    #[cfg(feature = "alloc")]
     pub fn drain_into(&mut self, dst: &mut Vec<R::Item>) -> usize {
-        let slice = self.as_slice();
-        let len = slice.len();
+        let len = self.len();
         if len == 0 {
             return 0;
         }
         dst.reserve(len);
+        let (front, back) = self.as_slices();
         let dst_len = dst.len();
         unsafe {
-            core::ptr::copy_nonoverlapping(slice.as_ptr(), dst.as_mut_ptr().add(dst_len), len);
+            let dst_ptr = dst.as_mut_ptr().add(dst_len);
+            core::ptr::copy_nonoverlapping(front.as_ptr(), dst_ptr, front.len());
+            core::ptr::copy_nonoverlapping(back.as_ptr(), dst_ptr.add(front.len()), back.len());
             dst.set_len(dst_len + len);
         }
         self.consumed += len;
@@ -129,8 +171,9 @@ impl<'a, R: BatchReader> ReadGuard<'a, R> {
     /// Move remaining items into the user's raw pointer. This allows writing
     /// to any container not limited to Vec.
     ///
-    /// Copies all unconsumed items (i.e. [`len`](Self::len) items) in bulk via
-    /// `memcpy`. After this call the guard's view is empty.
+    /// Copies all unconsumed items (i.e. [`len`](Self::len) items), both
+    /// slices included, in bulk via `memcpy`. After this call the guard's view
+    /// is empty.
     ///
     /// # Safety
     ///
@@ -141,13 +184,14 @@ impl<'a, R: BatchReader> ReadGuard<'a, R> {
     /// * The caller is responsible for any post-processing required on their
     ///   container, e.g. calling `set_len` or similar.
     pub unsafe fn drain_into_ptr(&mut self, dst: *mut R::Item) -> usize {
-        let slice = self.as_slice();
-        let len = slice.len();
+        let len = self.len();
         if len == 0 {
             return 0;
         }
+        let (front, back) = self.as_slices();
         unsafe {
-            core::ptr::copy_nonoverlapping(slice.as_ptr(), dst, len);
+            core::ptr::copy_nonoverlapping(front.as_ptr(), dst, front.len());
+            core::ptr::copy_nonoverlapping(back.as_ptr(), dst.add(front.len()), back.len());
         }
         self.consumed += len;
         len
@@ -155,18 +199,44 @@ impl<'a, R: BatchReader> ReadGuard<'a, R> {
 
     /// Copy remaining items into the user's slice.
     ///
-    /// Returns how many items were copied (min of available and `dst.len()`).
+    /// Returns how many items were copied (min of [`len`](Self::len)
+    /// and `dst.len()`).
     pub fn copy_into(&mut self, dst: &mut [R::Item]) -> usize
     where
         R::Item: Copy,
     {
-        let slice = self.as_slice();
-        let n = slice.len().min(dst.len());
-        if n > 0 {
-            dst[..n].copy_from_slice(&slice[..n]);
-            self.consumed += n;
+        let (front, back) = self.as_slices();
+        let n = front.len().min(dst.len());
+        let m = back.len().min(dst.len() - n);
+        dst[..n].copy_from_slice(&front[..n]);
+        dst[n..n + m].copy_from_slice(&back[..m]);
+        self.consumed += n + m;
+        n + m
+    }
+
+    /// Publishes the items consumed so far and takes a fresh view of the
+    /// receiver: whatever arrived since, or another shard's items. Returns
+    /// `false` if there are none.
+    pub(crate) fn refill(&mut self) -> bool {
+        self.publish();
+        let (data, wrapped) = self.receiver.read_slices();
+        (self.data, self.wrapped) = (NonNull::from_ref(data), NonNull::from_ref(wrapped));
+        self.consumed = 0;
+        self.remaining() > 0
+    }
+
+    fn publish(&mut self) {
+        // If the original buffer was empty, no resources were acquired
+        // (e.g., no shard lock for MPMC). Skip advance and release.
+        if self.data.is_empty() && self.wrapped.is_empty() {
+            return;
+        }
+        unsafe {
+            if self.consumed > 0 {
+                self.receiver.advance(self.consumed);
+            }
+            self.receiver.release();
         }
-        n
     }
 
     /// Advance without bounds checking.
@@ -186,26 +256,23 @@ impl<'a, R: BatchReader> ReadGuard<'a, R> {
 /// of the item. For bulk operations, prefer [`drain_into`](ReadGuard::drain_into)
 /// or [`copy_into`](ReadGuard::copy_into) which use `memcpy` and are
 /// significantly more efficient. For zero-copy access, use
-/// [`as_slice`](ReadGuard::as_slice).
+/// [`as_slices`](ReadGuard::as_slices).
 impl<R: BatchReader> Iterator for ReadGuard<'_, R> {
     type Item = R::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let slice = unsafe { self.data.as_ref() };
-        if self.consumed >= slice.len() {
-            return None;
-        }
-        // SAFETY: consumed < slice.len(), pointer is valid and initialized.
+        let item = self.as_slice().first()?;
+        // SAFETY: the item is initialized and behind `consumed` from here on.
         // The ring buffer does not drop items on advance, so this ptr::read
         // is the sole owner of the value.
-        let item = unsafe { core::ptr::read(&slice[self.consumed]) };
+        let item = unsafe { core::ptr::read(item) };
         self.consumed += 1;
         Some(item)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.len();
-        (remaining, Some(remaining))
+        let len = self.len();
+        (len, Some(len))
     }
 }
 
@@ -213,16 +280,6 @@ impl<R: BatchReader> ExactSizeIterator for ReadGuard<'_, R> {}
 
 impl<R: BatchReader> Drop for ReadGuard<'_, R> {
     fn drop(&mut self) {
-        // If the original buffer was empty, no resources were acquired
-        // (e.g., no shard lock for MPMC). Skip advance and release.
-        if unsafe { self.data.as_ref() }.is_empty() {
-            return;
-        }
-        unsafe {
-            if self.consumed > 0 {
-                self.receiver.advance(self.consumed);
-            }
-            self.receiver.release();
-        }
+        self.publish();
     }
 }
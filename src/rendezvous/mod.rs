@@ -0,0 +1,213 @@
+//! Zero-capacity rendezvous channel.
+//!
+//! A [`Sender::send`] returns only after a receiver has taken the value, so the
+//! channel never holds a backlog: a pipeline stage can be at most one item ahead
+//! of the next. Both ends can be cloned, covering single- and multi-party
+//! hand-off with the same type.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use gil::rendezvous::channel;
+//!
+//! let (mut tx, mut rx) = channel::<usize>();
+//!
+//! thread::spawn(move || {
+//!     for i in 0..100 {
+//!         tx.send(i);
+//!     }
+//! });
+//!
+//! for i in 0..100 {
+//!     assert_eq!(rx.recv(), i);
+//! }
+//! ```
+//!
+//! # Performance
+//!
+//! - **Single Slot:** Every hand-off goes through one slot, so concurrent senders
+//!   (and receivers) take turns. Throughput is bounded by the round trip between
+//!   the two threads of each hand-off, not by the number of parties.
+//! - **Parking:** Under `std`, blocked senders and receivers park on separate
+//!   futex words after a short spin. Without `std` they spin.
+//!
+//! # Gotchas
+//!
+//! - **`try_send` Needs a Blocked Receiver:** [`Sender::try_send`] only succeeds if
+//!   a receiver is already inside [`Receiver::recv`]. A receiver polling with
+//!   [`Receiver::try_recv`] never counts, so two non-blocking ends never meet.
+//! - **One-Way Disconnection:** Dropping every sender ends the receivers'
+//!   blocking iterators, but dropping every receiver does not fail a blocked
+//!   `send`; it blocks forever.
+
+pub use self::{receiver::Receiver, sender::Sender};
+
+mod queue;
+mod receiver;
+mod sender;
+
+/// Creates a new rendezvous channel.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Examples
+///
+/// ```
+/// use gil::rendezvous::channel;
+///
+/// let (tx, rx) = channel::<usize>();
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let queue = queue::QueuePtr::with_size(core::num::NonZeroUsize::MIN);
+
+    (Sender::new(queue.clone()), Receiver::new(queue))
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use alloc_crate::vec::Vec;
+
+    use crate::thread;
+
+    #[test]
+    fn clone_does_not_need_clone_items() {
+        struct NotClone(usize);
+
+        let (tx, rx) = channel::<NotClone>();
+        let (mut tx, mut rx) = (tx.clone(), rx.clone());
+        thread::scope(|scope| {
+            scope.spawn(move || tx.send(NotClone(7)));
+            assert_eq!(rx.recv().0, 7);
+        });
+    }
+
+    #[test]
+    fn test_multi_party() {
+        const THREADS: usize = 4;
+        const ITER: usize = 1000;
+
+        let (tx, rx) = channel::<usize>();
+        let mut sum = 0;
+
+        thread::scope(|scope| {
+            for t in 0..THREADS {
+                let mut tx = tx.clone();
+                scope.spawn(move || {
+                    for i in 0..ITER {
+                        tx.send(t * ITER + i);
+                    }
+                });
+            }
+
+            let receivers: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let mut rx = rx.clone();
+                    scope.spawn(move || (0..ITER).map(|_| rx.recv()).sum::<usize>())
+                })
+                .collect();
+            sum = receivers.into_iter().map(|h| h.join().unwrap()).sum();
+        });
+
+        let n = THREADS * ITER;
+        assert_eq!(sum, n * (n - 1) / 2);
+    }
+
+    #[test]
+    fn test_send_waits_for_receiver() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let (mut tx, mut rx) = channel::<usize>();
+        let sent = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                tx.send(1);
+                sent.store(true, Ordering::SeqCst);
+            });
+
+            thread::sleep(std::time::Duration::from_millis(20));
+            assert!(!sent.load(Ordering::SeqCst));
+            assert_eq!(rx.recv(), 1);
+        });
+        assert!(sent.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_try_send_needs_waiting_receiver() {
+        let (mut tx, mut rx) = channel::<usize>();
+        assert_eq!(tx.try_send(1), Err(1));
+
+        thread::scope(|scope| {
+            let handle = scope.spawn(move || rx.recv());
+
+            let mut value = 2;
+            while let Err(v) = tx.try_send(value) {
+                value = v;
+                thread::yield_now();
+            }
+            assert_eq!(handle.join().unwrap(), 2);
+        });
+    }
+
+    #[test]
+    fn iter_ends_once_every_sender_drops() {
+        let (tx, rx) = channel::<usize>();
+
+        thread::scope(|scope| {
+            for thread_id in 0..2 {
+                let mut tx = tx.clone();
+                scope.spawn(move || {
+                    for i in 0..50 {
+                        tx.send(thread_id * 50 + i);
+                    }
+                });
+            }
+            drop(tx);
+
+            let mut received: Vec<_> = rx.into_iter().collect();
+            received.sort_unstable();
+            assert!(received.into_iter().eq(0..100));
+        });
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn hand_off_loom() {
+        loom::model(|| {
+            let (mut tx, mut rx) = channel::<usize>();
+
+            let handle = thread::spawn(move || {
+                tx.send(1);
+                tx.send(2);
+            });
+
+            assert_eq!(rx.recv(), 1);
+            assert_eq!(rx.recv(), 2);
+            handle.join().unwrap();
+        })
+    }
+
+    #[test]
+    fn try_send_loom() {
+        loom::model(|| {
+            let (mut tx, mut rx) = channel::<usize>();
+
+            let handle = thread::spawn(move || rx.recv());
+
+            if tx.try_send(1).is_err() {
+                tx.send(2);
+            }
+            let value = handle.join().unwrap();
+            assert!(value == 1 || value == 2);
+        })
+    }
+}
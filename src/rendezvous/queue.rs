@@ -0,0 +1,173 @@
+//! Slot layout for the rendezvous channel.
+//!
+//! There is no buffer to speak of: one slot carries the value from the sender to
+//! the receiver while both are inside the hand-off. The slot's state word drives
+//! the exchange:
+//!
+//! ```text
+//! EMPTY --sender--> WRITING --sender--> FULL --receiver--> READING --receiver--> TAKEN
+//!   ^                                                                              |
+//!   +----------------------------------- sender -----------------------------------+
+//! ```
+//!
+//! The offering sender is the one that moves `TAKEN` back to `EMPTY`, which is how
+//! it learns its own value, not a later sender's, was taken.
+//!
+//! `waiting` counts receivers blocked in `recv` minus offers not yet taken. A
+//! `try_send` only offers after decrementing it from a positive value, so some
+//! receiver already inside `recv` is bound to take the offer.
+
+use core::ptr::NonNull;
+
+#[cfg(feature = "std")]
+use crate::{
+    atomic::AtomicU32,
+    futex::{Futex, RECEIVER_WAITING, SENDER_WAITING},
+};
+use crate::{
+    atomic::{AtomicIsize, AtomicUsize, Ordering},
+    padded::Padded,
+};
+
+pub(crate) const EMPTY: usize = 0;
+pub(crate) const WRITING: usize = 1;
+pub(crate) const FULL: usize = 2;
+pub(crate) const READING: usize = 3;
+pub(crate) const TAKEN: usize = 4;
+
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Head {
+    state: Padded<AtomicUsize>,
+    waiting: AtomicIsize,
+    #[cfg(feature = "std")]
+    sender_futex: AtomicU32,
+    #[cfg(feature = "std")]
+    receiver_futex: AtomicU32,
+}
+
+/// Which side of the hand-off a blocked thread is on.
+#[derive(Clone, Copy)]
+pub(crate) enum Party {
+    Sender,
+    Receiver,
+}
+
+/// The slot only holds a value while a sender is blocked inside the hand-off,
+/// so nothing is ever left behind on teardown.
+pub(crate) struct DropNothing;
+
+impl<T> crate::DropInFlight<Head, (), T> for DropNothing {
+    unsafe fn drop_in_flight(
+        _head: &Head,
+        _tail: &(),
+        _capacity: usize,
+        _at: impl Fn(usize) -> NonNull<T>,
+    ) {
+    }
+}
+
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, (), T, DropNothing>;
+
+impl<T> QueuePtr<T> {
+    #[inline(always)]
+    pub(crate) fn state(&self) -> &AtomicUsize {
+        &self.header().head.state.value
+    }
+
+    #[inline(always)]
+    pub(crate) fn waiting(&self) -> &AtomicIsize {
+        &self.header().head.waiting
+    }
+
+    #[inline(always)]
+    pub(crate) fn slot(&self) -> NonNull<T> {
+        // SAFETY: the buffer always has exactly one slot.
+        unsafe { self.exact_at(0) }
+    }
+
+    /// Moves the state from `from` to `to`, for the thread that wins the race.
+    #[inline(always)]
+    pub(crate) fn claim(&self, from: usize, to: usize) -> bool {
+        self.state()
+            .compare_exchange(from, to, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Publishes `state` and wakes the side that waits on it.
+    #[inline(always)]
+    pub(crate) fn publish(&self, state: usize, wake: Party) {
+        self.state().store(state, Ordering::Release);
+        #[cfg(feature = "std")]
+        self.futex(wake).wake_all();
+        #[cfg(not(feature = "std"))]
+        let _ = wake;
+    }
+
+    /// Blocks until the state reads `state`. Under `std` the thread parks on its
+    /// side's futex after a short spin; without `std` it spins.
+    pub(crate) fn wait_for(&self, state: usize, party: Party) {
+        self.wait_for_or(state, party, || false);
+    }
+
+    /// Like [`wait_for`](Self::wait_for), but gives up once `give_up` returns
+    /// `true`. Returns whether the state was reached.
+    pub(crate) fn wait_for_or(
+        &self,
+        state: usize,
+        party: Party,
+        give_up: impl Fn() -> bool,
+    ) -> bool {
+        #[cfg(feature = "std")]
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        #[cfg(not(feature = "std"))]
+        let mut backoff = crate::Backoff::with_spin_count(128);
+
+        while self.state().load(Ordering::Acquire) != state {
+            if give_up() {
+                return false;
+            }
+
+            #[cfg(feature = "std")]
+            if backoff.backoff() {
+                let futex = self.futex(party);
+                let who = match party {
+                    Party::Sender => SENDER_WAITING,
+                    Party::Receiver => RECEIVER_WAITING,
+                };
+                // catch lost wakes: recheck the state before parking
+                if futex.announce(who)
+                    && self.state().load(Ordering::Relaxed) != state
+                    && !give_up()
+                {
+                    futex.sleep(who);
+                }
+            }
+
+            #[cfg(not(feature = "std"))]
+            {
+                let _ = party;
+                backoff.backoff();
+            }
+        }
+        true
+    }
+
+    /// Wakes every receiver parked in [`wait_for`](Self::wait_for), so they
+    /// recheck the sender count. Called when the last sender drops.
+    #[inline(always)]
+    pub(crate) fn disconnected(&self) {
+        #[cfg(feature = "std")]
+        self.futex(Party::Receiver).wake_all();
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn futex(&self, party: Party) -> Futex {
+        let head = &self.header().head;
+        match party {
+            Party::Sender => Futex::new(&head.sender_futex),
+            Party::Receiver => Futex::new(&head.receiver_futex),
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::atomic::Ordering;
+
+use super::queue::{FULL, Party, QueuePtr, READING, TAKEN};
+
+/// The receiving end of a rendezvous channel.
+///
+/// This struct is `Clone` and `Send`. It can be shared across threads by cloning it.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use gil::rendezvous::channel;
+///
+/// let (mut tx, rx) = channel::<i32>();
+///
+/// let mut rx2 = rx.clone();
+/// let handle = thread::spawn(move || rx2.recv());
+///
+/// tx.send(1);
+/// assert_eq!(handle.join().unwrap(), 1);
+/// ```
+pub struct Receiver<T> {
+    ptr: QueuePtr<T>,
+}
+
+// Not derived: cloning a handle must not require `T: Clone`.
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            ptr: self.ptr.clone(),
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self { ptr }
+    }
+
+    /// Receives a value, blocking until a sender hands one off.
+    ///
+    /// While blocked, the receiver counts as waiting for
+    /// [`Sender::try_send`](super::Sender::try_send). Under `std` it parks on a
+    /// futex after a short spin; without `std` it spins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use gil::rendezvous::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>();
+    ///
+    /// thread::spawn(move || tx.send(42));
+    /// assert_eq!(rx.recv(), 42);
+    /// ```
+    pub fn recv(&mut self) -> T {
+        self.ptr.waiting().fetch_add(1, Ordering::AcqRel);
+
+        loop {
+            self.ptr.wait_for(FULL, Party::Receiver);
+            if self.ptr.claim(FULL, READING) {
+                break;
+            }
+        }
+
+        self.take()
+    }
+
+    /// Takes a value only if a sender is currently offering one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use gil::rendezvous::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>();
+    /// assert_eq!(rx.try_recv(), None);
+    ///
+    /// let handle = thread::spawn(move || tx.send(42));
+    /// let value = loop {
+    ///     if let Some(value) = rx.try_recv() {
+    ///         break value;
+    ///     }
+    /// };
+    /// assert_eq!(value, 42);
+    /// handle.join().unwrap();
+    /// ```
+    pub fn try_recv(&mut self) -> Option<T> {
+        if !self.ptr.claim(FULL, READING) {
+            return None;
+        }
+        // the offer we took was counted against the receivers blocked in `recv`;
+        // we were not one of them, so give the count back
+        self.ptr.waiting().fetch_add(1, Ordering::AcqRel);
+
+        Some(self.take())
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// every sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Returns `true` once every [`Sender`](super::Sender) has been dropped.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.ptr.is_disconnected()
+    }
+
+    /// Moves the value out of the claimed slot and lets its sender return.
+    fn take(&mut self) -> T {
+        // SAFETY: the slot is `READING`, which only this receiver can leave, and
+        // the `Acquire` claim synchronised with the sender's `FULL` publish.
+        let value = unsafe { self.ptr.slot().read() };
+        self.ptr.publish(TAKEN, Party::Sender);
+        value
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+
+    fn recv_until_disconnected(&mut self) -> Option<T> {
+        self.ptr.waiting().fetch_add(1, Ordering::AcqRel);
+
+        loop {
+            // senders offer only inside `send`/`try_send`, which wait for the
+            // offer to be taken, so with none left no offer can be pending
+            if !self
+                .ptr
+                .wait_for_or(FULL, Party::Receiver, || self.ptr.is_disconnected())
+            {
+                self.ptr.waiting().fetch_sub(1, Ordering::AcqRel);
+                return None;
+            }
+            if self.ptr.claim(FULL, READING) {
+                return Some(self.take());
+            }
+        }
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
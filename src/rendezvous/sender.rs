@@ -0,0 +1,143 @@
+use crate::atomic::Ordering;
+
+use super::queue::{EMPTY, FULL, Party, QueuePtr, TAKEN, WRITING};
+
+/// The sending end of a rendezvous channel.
+///
+/// This struct is `Clone` and `Send`. It can be shared across threads by cloning it.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use gil::rendezvous::channel;
+///
+/// let (tx, mut rx) = channel::<i32>();
+///
+/// let mut tx2 = tx.clone();
+/// thread::spawn(move || tx2.send(1));
+///
+/// let mut tx3 = tx.clone();
+/// thread::spawn(move || tx3.send(2));
+///
+/// let mut values = [rx.recv(), rx.recv()];
+/// values.sort();
+/// assert_eq!(values, [1, 2]);
+/// ```
+pub struct Sender<T> {
+    ptr: QueuePtr<T>,
+}
+
+// Not derived: cloning a handle must not require `T: Clone`.
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.ptr.attach_sender();
+        Self {
+            ptr: self.ptr.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.ptr.detach_sender() {
+            self.ptr.disconnected();
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        ptr.attach_sender();
+        Self { ptr }
+    }
+
+    /// Sends a value, blocking until a receiver has taken it.
+    ///
+    /// Under `std` the sender parks on a futex after a short spin; without `std`
+    /// it spins.
+    ///
+    /// The channel does not track its receivers: if every [`Receiver`](super::Receiver)
+    /// has been dropped, this blocks forever. Use [`try_send`](Self::try_send) when
+    /// the receivers may be gone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use gil::rendezvous::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>();
+    ///
+    /// let handle = thread::spawn(move || rx.recv());
+    /// tx.send(42);
+    /// // `send` has returned, so the value is already with the receiver
+    /// assert_eq!(handle.join().unwrap(), 42);
+    /// ```
+    pub fn send(&mut self, value: T) {
+        loop {
+            self.ptr.wait_for(EMPTY, Party::Sender);
+            if self.ptr.claim(EMPTY, WRITING) {
+                break;
+            }
+        }
+        self.ptr.waiting().fetch_sub(1, Ordering::AcqRel);
+
+        self.hand_off(value);
+    }
+
+    /// Sends a value only if a receiver is already blocked in
+    /// [`recv`](super::Receiver::recv), returning `Err(value)` otherwise.
+    ///
+    /// On success this returns once that receiver has taken the value. It also
+    /// fails if another sender is mid-hand-off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::rendezvous::channel;
+    ///
+    /// let (mut tx, _rx) = channel::<i32>();
+    ///
+    /// // nobody is waiting to receive
+    /// assert_eq!(tx.try_send(1), Err(1));
+    /// ```
+    pub fn try_send(&mut self, value: T) -> Result<(), T> {
+        let waiting = self.ptr.waiting();
+        let mut current = waiting.load(Ordering::Acquire);
+        loop {
+            if current <= 0 {
+                return Err(value);
+            }
+            match waiting.compare_exchange_weak(
+                current,
+                current - 1,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+
+        if !self.ptr.claim(EMPTY, WRITING) {
+            waiting.fetch_add(1, Ordering::AcqRel);
+            return Err(value);
+        }
+
+        self.hand_off(value);
+        Ok(())
+    }
+
+    /// Offers `value` from the claimed slot and waits until it is taken.
+    fn hand_off(&mut self, value: T) {
+        // SAFETY: the slot is `WRITING`, which only this sender can leave.
+        unsafe { self.ptr.slot().write(value) };
+        self.ptr.publish(FULL, Party::Receiver);
+
+        self.ptr.wait_for(TAKEN, Party::Sender);
+        self.ptr.publish(EMPTY, Party::Sender);
+    }
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
@@ -26,21 +26,41 @@
 
 use core::{mem::MaybeUninit, ptr::NonNull};
 
+#[cfg(feature = "latency")]
+use crate::latency::Stamps;
+#[cfg(feature = "alloc")]
+use crate::queue::{DropInFlight, Ownership, QueuePtr};
 use crate::{
     atomic::{AtomicUsize, Ordering},
-    queue::{DropInFlight, Ownership, QueuePtr},
+    latency::Tracer,
+    stats::Counters,
 };
 
 /// The default ring handle: the full SPSC layout (head/tail atomics plus optional
 /// async wakers and futex) with its geometry and refcount.
+#[cfg(feature = "alloc")]
 pub(crate) type Ring<T> = crate::spsc::queue::QueuePtr<T>;
 
 /// Implemented by header head types: projects out the head index atomic.
+#[cfg(feature = "alloc")]
 pub(crate) trait RingHead {
     fn head(&self) -> &AtomicUsize;
+
+    /// Called once the producer has detached. Layouts with a futex wake the
+    /// receiver parked on it, so it rechecks and sees the disconnection.
+    #[inline(always)]
+    fn disconnected(&self) {}
+
+    /// The per-slot publish times, for layouts that trace latency.
+    #[cfg(feature = "latency")]
+    #[inline(always)]
+    fn stamps(&self) -> Option<&Stamps> {
+        None
+    }
 }
 
 /// Implemented by header tail types: projects out the tail index atomic.
+#[cfg(feature = "alloc")]
 pub(crate) trait RingTail {
     fn tail(&self) -> &AtomicUsize;
 }
@@ -67,6 +87,13 @@ pub(crate) trait RingPtr {
     /// Allocated buffer length (`size` rounded up to a power of two).
     fn capacity(&self) -> usize;
 
+    /// Slots addressable from the buffer start without wrapping: the capacity,
+    /// or twice it when the buffer is mapped a second time right after itself.
+    #[inline(always)]
+    fn span(&self) -> usize {
+        self.capacity()
+    }
+
     /// # Safety
     ///
     /// `index` must be already masked (`< capacity`).
@@ -85,8 +112,63 @@ pub(crate) trait RingPtr {
     ///
     /// The slot must be free (outside the head..tail window).
     unsafe fn set(&self, index: usize, value: Self::Item);
+
+    /// Counts the producer in; see [`QueuePtr::attach_sender`].
+    fn attach_sender(&self);
+
+    /// Counts the producer out, waking a receiver parked on the ring when it
+    /// was the last one.
+    fn detach_sender(&self);
+
+    /// Whether the producer has detached; see [`QueuePtr::is_disconnected`].
+    fn is_disconnected(&self) -> bool;
+
+    /// Items in the ring as seen by a third party holding neither cursor.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn occupancy(&self) -> usize {
+        occupancy(self.head(), self.tail(), self.size())
+    }
+
+    /// The side array of publish times, if this ring was built with one.
+    #[cfg(feature = "latency")]
+    #[inline(always)]
+    fn stamps(&self) -> Option<&Stamps> {
+        None
+    }
 }
 
+/// Rebuilds the two runs of [`Consumer::read_slices_raw`] as slices. The
+/// sharded receivers need this to return a shard's runs out of their scan.
+///
+/// # Safety
+///
+/// Both runs must hold initialised items that stay untouched for `'a`.
+#[inline(always)]
+pub(crate) unsafe fn slices_from_raw<'a, T>(runs: [(NonNull<T>, usize); 2]) -> (&'a [T], &'a [T]) {
+    let [(ptr, len), (wrapped, wrapped_len)] = runs;
+    unsafe {
+        (
+            core::slice::from_raw_parts(ptr.as_ptr(), len),
+            core::slice::from_raw_parts(wrapped.as_ptr(), wrapped_len),
+        )
+    }
+}
+
+/// Item count from a pair of shared cursors, backing the `len()` methods.
+///
+/// The two loads are independent, so under concurrency the result is only a
+/// snapshot. Reading the cursors in either order can observe a head newer than
+/// the tail; the signed clamp turns that into `0` instead of a wrapped count.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn occupancy(head: &AtomicUsize, tail: &AtomicUsize, capacity: usize) -> usize {
+    let head = head.load(Ordering::Relaxed);
+    let tail = tail.load(Ordering::Relaxed);
+    (tail.wrapping_sub(head) as isize).clamp(0, capacity as isize) as usize
+}
+
+#[cfg(feature = "alloc")]
 impl<H, T, I, G, O> RingPtr for QueuePtr<H, T, I, G, O>
 where
     H: RingHead,
@@ -121,6 +203,28 @@ where
         self.capacity
     }
 
+    #[inline(always)]
+    fn span(&self) -> usize {
+        self.span
+    }
+
+    #[inline]
+    fn attach_sender(&self) {
+        QueuePtr::attach_sender(self);
+    }
+
+    #[inline]
+    fn detach_sender(&self) {
+        if QueuePtr::detach_sender(self) {
+            self.header().head.disconnected();
+        }
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        QueuePtr::is_disconnected(self)
+    }
+
     #[inline(always)]
     unsafe fn exact_at(&self, index: usize) -> NonNull<I> {
         unsafe { self.buffer.add(index) }
@@ -135,6 +239,12 @@ where
     unsafe fn set(&self, index: usize, value: I) {
         unsafe { self.buffer.add(index & self.mask).write(value) }
     }
+
+    #[cfg(feature = "latency")]
+    #[inline(always)]
+    fn stamps(&self) -> Option<&Stamps> {
+        self.header().head.stamps()
+    }
 }
 
 /// The producer cursor over a ring. Holds the one copy of the push algorithm.
@@ -144,6 +254,9 @@ pub(crate) struct Producer<R: RingPtr> {
     tail: usize,
     /// Stale snapshot of the consumer's head, refreshed lazily.
     head_cache: usize,
+    /// This endpoint's counters. The cursor records items, full rings and the
+    /// high-water mark; the endpoint adds parks and wakes.
+    pub(crate) stats: Counters,
 }
 
 impl<R: RingPtr> Producer<R> {
@@ -152,12 +265,14 @@ impl<R: RingPtr> Producer<R> {
     /// shard it picks up where the previous owner left off.
     #[inline(always)]
     pub(crate) fn attach(ring: R) -> Self {
+        ring.attach_sender();
         let tail = ring.tail().load(Ordering::Acquire);
         let head_cache = ring.head().load(Ordering::Acquire);
         Self {
             ring,
             tail,
             head_cache,
+            stats: Counters::default(),
         }
     }
 
@@ -167,6 +282,20 @@ impl<R: RingPtr> Producer<R> {
         &self.ring
     }
 
+    /// Usable capacity of the ring.
+    #[inline(always)]
+    pub(crate) fn size(&self) -> usize {
+        self.ring.size()
+    }
+
+    /// Items in the ring: our own tail against a fresh load of the head. The
+    /// consumer may pop concurrently, so this is an upper bound.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.tail
+            .wrapping_sub(self.ring.head().load(Ordering::Relaxed))
+    }
+
     /// Full according to the cached head only. no refresh.
     #[inline(always)]
     pub(crate) fn is_full(&self) -> bool {
@@ -193,55 +322,243 @@ impl<R: RingPtr> Producer<R> {
     pub(crate) fn push(&mut self, value: R::Item) {
         let new_tail = self.tail.wrapping_add(1);
         unsafe { self.ring.set(self.tail, value) };
+        self.stamp(1);
         self.ring.tail().store(new_tail, Ordering::Release);
         self.tail = new_tail;
+        self.record_sent(1);
     }
 
-    /// The one copy of the non-blocking push. Returns the value back on a full ring.
+    /// Stamps the `n` slots about to be published with the current time.
+    #[cfg_attr(not(feature = "latency"), allow(unused_variables))]
     #[inline(always)]
-    pub(crate) fn try_push(&mut self, value: R::Item) -> Result<(), R::Item> {
+    fn stamp(&self, n: usize) {
+        #[cfg(feature = "latency")]
+        if let Some(stamps) = self.ring.stamps() {
+            stamps.publish(self.tail, n);
+        }
+    }
+
+    #[inline(always)]
+    fn record_sent(&mut self, n: usize) {
+        self.stats.sent(n);
+        // `head_cache` can be laps behind, which would overstate the depth
+        self.stats.observe(|| {
+            self.tail
+                .wrapping_sub(self.ring.head().load(Ordering::Relaxed))
+        });
+    }
+
+    /// Like [`push`](Self::push), but lets `f` build the item in the tail slot.
+    ///
+    /// Nothing is published until `f` returns, so a panic in `f` (or returning
+    /// the wrong slot, see [`construct`](crate::emplace::construct)) leaves the
+    /// ring as it was.
+    ///
+    /// # Safety
+    ///
+    /// The ring must not be full.
+    #[inline(always)]
+    pub(crate) unsafe fn push_with(
+        &mut self,
+        f: impl FnOnce(&mut MaybeUninit<R::Item>) -> &mut R::Item,
+    ) {
+        // SAFETY: not full, so the tail slot is outside the consumer's window.
+        let slot = unsafe {
+            self.ring
+                .exact_at(self.tail & self.ring.mask())
+                .cast::<MaybeUninit<R::Item>>()
+                .as_mut()
+        };
+        crate::emplace::construct(slot, f);
+
+        let new_tail = self.tail.wrapping_add(1);
+        self.stamp(1);
+        self.ring.tail().store(new_tail, Ordering::Release);
+        self.tail = new_tail;
+        self.record_sent(1);
+    }
+
+    /// `try_push`'s fullness check without the push: refreshes the cached head
+    /// once on the slow branch. The mirror of [`Consumer::has_items`].
+    #[inline(always)]
+    pub(crate) fn has_space(&mut self) -> bool {
         if self.is_full() {
             self.refresh_head();
             if self.is_full() {
-                return Err(value);
+                self.stats.full();
+                return false;
             }
         }
+        true
+    }
+
+    /// The one copy of the non-blocking push. Returns the value back on a full ring.
+    #[inline(always)]
+    pub(crate) fn try_push(&mut self, value: R::Item) -> Result<(), R::Item> {
+        if !self.has_space() {
+            return Err(value);
+        }
         self.push(value);
         Ok(())
     }
 
-    /// Contiguous free slots starting at the current tail (uninitialised).
-    pub(crate) fn write_buffer(&mut self) -> &mut [MaybeUninit<R::Item>] {
+    /// Non-blocking [`push_with`](Self::push_with). Returns `f` back, uncalled,
+    /// on a full ring.
+    #[inline(always)]
+    pub(crate) fn try_push_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<R::Item>) -> &mut R::Item,
+    {
+        if !self.has_space() {
+            return Err(f);
+        }
+        // SAFETY: just checked for space.
+        unsafe { self.push_with(f) };
+        Ok(())
+    }
+
+    /// Free slots after the tail, refreshing the cached head only if fewer than
+    /// `needed` are known free.
+    #[inline(always)]
+    pub(crate) fn free_slots(&mut self, needed: usize) -> usize {
         let mut available = self.ring.size() - self.tail.wrapping_sub(self.head_cache);
-        if available == 0 {
+        if available < needed {
             self.refresh_head();
             available = self.ring.size() - self.tail.wrapping_sub(self.head_cache);
         }
+        available
+    }
+
+    /// Copies as much of `values` as there is room for and publishes it: one
+    /// publish per contiguous run of free slots, so at most two. Returns the
+    /// number of items sent.
+    pub(crate) fn push_slice(&mut self, values: &[R::Item]) -> usize
+    where
+        R::Item: Copy,
+    {
+        let mut sent = 0;
+        while sent < values.len() {
+            let buf = self.write_buffer();
+            let n = buf.len().min(values.len() - sent);
+            if n == 0 {
+                break;
+            }
+            // SAFETY: `buf` holds at least `n` free slots, and a `Copy` item
+            // needs no drop bookkeeping.
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    values[sent..].as_ptr(),
+                    buf.as_mut_ptr().cast::<R::Item>(),
+                    n,
+                );
+                self.commit(n);
+            }
+            sent += n;
+        }
+        sent
+    }
+
+    /// Moves items out of `iter` into the free slots, pulling only as many as
+    /// there is room for. Returns `Ok(sent)` once `iter` runs dry and
+    /// `Err(sent)` if the ring fills up first; in the latter case `iter` may
+    /// or may not have more.
+    ///
+    /// Items are published per contiguous run of free slots. If `iter` panics,
+    /// the items it already yielded are published before the unwind continues.
+    pub(crate) fn push_iter(
+        &mut self,
+        iter: &mut impl Iterator<Item = R::Item>,
+    ) -> Result<usize, usize> {
+        let mut sent = 0;
+        loop {
+            let buf = self.write_buffer();
+            let (slots, free) = (buf.as_mut_ptr().cast::<R::Item>(), buf.len());
+            if free == 0 {
+                return Err(sent);
+            }
+
+            let mut run = Run {
+                producer: self,
+                len: 0,
+            };
+            while run.len < free {
+                let Some(value) = iter.next() else { break };
+                // SAFETY: `slots` has `free` free slots.
+                unsafe { slots.add(run.len).write(value) };
+                run.len += 1;
+            }
+            let written = run.len;
+            drop(run);
+
+            sent += written;
+            if written < free {
+                return Ok(sent);
+            }
+        }
+    }
+
+    /// Slots from the tail to the end of the buffer, free or not.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub(crate) fn contiguous(&self) -> usize {
+        self.ring.capacity() - (self.tail & self.ring.mask())
+    }
+
+    /// Contiguous free slots starting at the current tail (uninitialised).
+    #[inline]
+    pub(crate) fn write_buffer(&mut self) -> &mut [MaybeUninit<R::Item>] {
+        let available = self.free_slots(1);
+        self.free_runs(available).0
+    }
+
+    /// All free slots, split where they wrap: the run from the tail to the end
+    /// of the buffer, then the rest from its start. Reloads the head unless the
+    /// whole ring is already known to be free.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub(crate) fn write_slices(
+        &mut self,
+    ) -> (&mut [MaybeUninit<R::Item>], &mut [MaybeUninit<R::Item>]) {
+        let available = self.free_slots(self.ring.size());
+        self.free_runs(available)
+    }
 
+    /// Splits the first `available` free slots at the end of the buffer.
+    #[allow(clippy::type_complexity)]
+    fn free_runs(
+        &mut self,
+        available: usize,
+    ) -> (&mut [MaybeUninit<R::Item>], &mut [MaybeUninit<R::Item>]) {
         let start = self.tail & self.ring.mask();
-        let contiguous = self.ring.capacity() - start;
+        let contiguous = self.ring.span() - start;
         let len = available.min(contiguous);
 
+        // SAFETY: both runs are free slots only this producer writes. They are
+        // disjoint: the wrapped run ends at `available - len`, which is at most
+        // `start` because `available` never exceeds the capacity. On a mirrored
+        // ring the first run covers everything and the wrapped one is empty.
         unsafe {
             let ptr = self.ring.exact_at(start).cast();
-            core::slice::from_raw_parts_mut(ptr.as_ptr(), len)
+            let wrapped = self.ring.exact_at(0).cast();
+            (
+                core::slice::from_raw_parts_mut(ptr.as_ptr(), len),
+                core::slice::from_raw_parts_mut(wrapped.as_ptr(), available - len),
+            )
         }
     }
 
-    /// Publish `len` items written via [`write_buffer`](Self::write_buffer).
+    /// Publish `len` items written via [`write_buffer`](Self::write_buffer) or
+    /// [`write_slices`](Self::write_slices).
     ///
     /// # Safety
     ///
-    /// `len` must not exceed the most recent `write_buffer` slice length, and all
-    /// `len` slots must be initialised.
+    /// `len` must not exceed the free slots last handed out, and the first
+    /// `len` of them, in order, must be initialised.
     #[inline(always)]
     pub(crate) unsafe fn commit(&mut self, len: usize) {
         #[cfg(debug_assertions)]
         {
-            let start = self.tail & self.ring.mask();
-            let contiguous = self.ring.capacity() - start;
-            let available =
-                contiguous.min(self.ring.size() - self.tail.wrapping_sub(self.head_cache));
+            let available = self.ring.size() - self.tail.wrapping_sub(self.head_cache);
             assert!(
                 len <= available,
                 "advancing ({len}) more than available space ({available})"
@@ -250,8 +567,33 @@ impl<R: RingPtr> Producer<R> {
 
         // the len can be just right at the edge of buffer, so we wrap just in case
         let new_tail = self.tail.wrapping_add(len);
+        self.stamp(len);
         self.ring.tail().store(new_tail, Ordering::Release);
         self.tail = new_tail;
+        self.record_sent(len);
+    }
+}
+
+/// Items written into a run of free slots by [`Producer::push_iter`],
+/// committed on drop so a panicking iterator still publishes what it yielded.
+struct Run<'a, R: RingPtr> {
+    producer: &'a mut Producer<R>,
+    len: usize,
+}
+
+impl<R: RingPtr> Drop for Run<'_, R> {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            // SAFETY: the first `len` slots of the last `write_buffer` are
+            // initialised.
+            unsafe { self.producer.commit(self.len) };
+        }
+    }
+}
+
+impl<R: RingPtr> Drop for Producer<R> {
+    fn drop(&mut self) {
+        self.ring.detach_sender();
     }
 }
 
@@ -262,6 +604,10 @@ pub(crate) struct Consumer<R: RingPtr> {
     head: usize,
     /// Stale snapshot of the producer's tail, refreshed lazily.
     tail_cache: usize,
+    /// This endpoint's counters. See [`Producer::stats`].
+    pub(crate) stats: Counters,
+    /// Where dwell times go, if the receiver installed a sink.
+    pub(crate) tracer: Tracer,
 }
 
 impl<R: RingPtr> Consumer<R> {
@@ -274,6 +620,8 @@ impl<R: RingPtr> Consumer<R> {
             ring,
             head,
             tail_cache,
+            stats: Counters::default(),
+            tracer: Tracer::default(),
         }
     }
 
@@ -283,6 +631,28 @@ impl<R: RingPtr> Consumer<R> {
         &self.ring
     }
 
+    /// Whether the producer has detached from the ring.
+    #[inline]
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.ring.is_disconnected()
+    }
+
+    /// Usable capacity of the ring.
+    #[inline(always)]
+    pub(crate) fn size(&self) -> usize {
+        self.ring.size()
+    }
+
+    /// Items in the ring: a fresh load of the tail against our own head. The
+    /// producer may push concurrently, so this is a lower bound.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.ring
+            .tail()
+            .load(Ordering::Relaxed)
+            .wrapping_sub(self.head)
+    }
+
     /// Empty according to the cached tail only. no refresh.
     #[inline(always)]
     pub(crate) fn is_empty(&self) -> bool {
@@ -293,6 +663,8 @@ impl<R: RingPtr> Consumer<R> {
     #[inline(always)]
     pub(crate) fn refresh_tail(&mut self) {
         self.tail_cache = self.ring.tail().load(Ordering::Acquire);
+        self.stats
+            .observe(|| self.tail_cache.wrapping_sub(self.head));
     }
 
     /// Refresh the cached tail with `SeqCst`. See [`Producer::refresh_head_seqcst`].
@@ -308,9 +680,11 @@ impl<R: RingPtr> Consumer<R> {
     pub(crate) fn pop(&mut self) -> R::Item {
         // SAFETY: head != tail means the slot at head holds a valid initialised value.
         let value = unsafe { self.ring.get(self.head) };
+        self.tracer.receive(&self.ring, self.head, 1);
         let new_head = self.head.wrapping_add(1);
         self.ring.head().store(new_head, Ordering::Release);
         self.head = new_head;
+        self.stats.received(1);
         value
     }
 
@@ -330,6 +704,31 @@ impl<R: RingPtr> Consumer<R> {
         true
     }
 
+    /// The item at the head, without consuming it. Falls back to a fresh load
+    /// of the tail when the cached one says empty, but does not store it, so
+    /// it works through `&self`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn peek(&self) -> Option<NonNull<R::Item>> {
+        if self.is_empty() && self.ring.tail().load(Ordering::Acquire) == self.head {
+            return None;
+        }
+        // SAFETY: masked, and head != tail, so the slot holds an initialised item
+        // that only this consumer touches until it advances.
+        Some(unsafe { self.ring.exact_at(self.head & self.ring.mask()) })
+    }
+
+    /// [`peek`](Self::peek), refreshing the cached tail on the slow branch.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn peek_mut(&mut self) -> Option<NonNull<R::Item>> {
+        if !self.has_items() {
+            return None;
+        }
+        // SAFETY: as in `peek`.
+        Some(unsafe { self.ring.exact_at(self.head & self.ring.mask()) })
+    }
+
     /// The one copy of the non-blocking pop.
     #[inline(always)]
     pub(crate) fn try_pop(&mut self) -> Option<R::Item> {
@@ -341,6 +740,7 @@ impl<R: RingPtr> Consumer<R> {
 
     /// Re-sync both cursors from the atomics. Used when the head may have advanced
     /// underneath this cursor i.e. a sharded receiver sharing a shard with peers.
+    #[cfg(feature = "alloc")]
     #[inline(always)]
     pub(crate) fn resync(&mut self) {
         self.head = self.ring.head().load(Ordering::Acquire);
@@ -364,13 +764,25 @@ impl<R: RingPtr> Consumer<R> {
         }
 
         let start = self.head & self.ring.mask();
-        let contiguous = self.ring.capacity() - start;
+        let contiguous = self.ring.span() - start;
         let len = available.min(contiguous);
 
         // SAFETY: `start` is masked.
         (unsafe { self.ring.exact_at(start) }, len)
     }
 
+    /// [`read_buffer_raw`](Self::read_buffer_raw) plus the items that wrapped
+    /// to the start of the buffer. Always reloads the tail: a caller asking for
+    /// both runs wants everything published so far, not just what was cached.
+    #[inline]
+    pub(crate) fn read_slices_raw(&mut self) -> [(NonNull<R::Item>, usize); 2] {
+        self.refresh_tail();
+        let (ptr, len) = self.read_buffer_raw();
+        let wrapped = self.tail_cache.wrapping_sub(self.head) - len;
+        // SAFETY: index 0 is in bounds.
+        [(ptr, len), (unsafe { self.ring.exact_at(0) }, wrapped)]
+    }
+
     /// Contiguous available items starting at the current head.
     #[inline]
     pub(crate) fn read_buffer(&mut self) -> &[R::Item] {
@@ -380,26 +792,35 @@ impl<R: RingPtr> Consumer<R> {
         unsafe { core::slice::from_raw_parts(ptr.as_ptr(), len) }
     }
 
+    /// All available items, split where they wrap.
+    #[inline]
+    pub(crate) fn read_slices(&mut self) -> (&[R::Item], &[R::Item]) {
+        let runs = self.read_slices_raw();
+        // SAFETY: as in `read_buffer`, for each run.
+        unsafe { slices_from_raw(runs) }
+    }
+
     /// Advance the head past `n` consumed items.
     ///
     /// # Safety
     ///
-    /// `n` must not exceed the most recent [`read_buffer`](Self::read_buffer) length.
+    /// `n` must not exceed the items last handed out by
+    /// [`read_buffer`](Self::read_buffer) or [`read_slices`](Self::read_slices).
     #[inline(always)]
     pub(crate) unsafe fn advance(&mut self, n: usize) {
         #[cfg(debug_assertions)]
         {
-            let start = self.head & self.ring.mask();
-            let contiguous = self.ring.capacity() - start;
-            let available = contiguous.min(self.tail_cache.wrapping_sub(self.head));
+            let available = self.tail_cache.wrapping_sub(self.head);
             assert!(
                 n <= available,
                 "advancing ({n}) more than available space ({available})"
             );
         }
 
+        self.tracer.receive(&self.ring, self.head, n);
         let new_head = self.head.wrapping_add(n);
         self.ring.head().store(new_head, Ordering::Release);
         self.head = new_head;
+        self.stats.received(n);
     }
 }
@@ -9,56 +9,112 @@
 //! table-owner handle of every shard; endpoints claim the producer or consumer
 //! slot of individual shards and keep the rings alive through their own handles,
 //! so the table allocation itself only needs to live while someone may still
-//! claim from it (i.e. while a cloneable endpoint exists).
+//! claim from it (i.e. while a cloneable endpoint exists) or read its sender
+//! count.
 
 use core::num::NonZeroUsize;
 
-use crate::queue::ShardOwnership;
 pub(crate) use crate::spsc::queue::Shard;
-
-#[cfg(not(feature = "loom"))]
-type Table<T> = crate::Arc<[Shard<T>]>;
-// loom's `Arc` cannot hold unsized slices; box the slice under loom.
-#[cfg(feature = "loom")]
-type Table<T> = crate::Arc<crate::Box<[Shard<T>]>>;
+use crate::{
+    Box,
+    allocator::Allocator,
+    atomic::{AtomicUsize, Ordering},
+    queue::ShardOwnership,
+    ring::RingPtr,
+    spsc::queue::{ShardHead, ShardTail},
+};
+
+/// The shared allocation: the shards, plus a sender count for the channels
+/// whose senders each claim a shard of their own.
+struct Inner<T> {
+    shards: Box<[Shard<T>]>,
+    senders: AtomicUsize,
+}
 
 /// A shared, refcounted table of [`Shard`]s. Cloning shares the same table;
 /// the final drop releases every shard's table-owner role.
 pub(crate) struct ShardTable<T> {
-    shards: Table<T>,
+    inner: crate::Arc<Inner<T>>,
 }
 
 impl<T> Clone for ShardTable<T> {
     fn clone(&self) -> Self {
         Self {
-            shards: self.shards.clone(),
+            inner: self.inner.clone(),
         }
     }
 }
 
 impl<T> ShardTable<T> {
-    pub(crate) fn new(max_shards: NonZeroUsize, capacity_per_shard: NonZeroUsize) -> Self {
-        let shards = (0..max_shards.get()).map(|_| Shard::with_size(capacity_per_shard));
+    /// Builds `max_shards` shards, each allocated from its own clone of
+    /// `allocator`.
+    pub(crate) fn new_in<A: Allocator + Clone + Send + 'static>(
+        max_shards: NonZeroUsize,
+        capacity_per_shard: NonZeroUsize,
+        allocator: A,
+    ) -> Self {
+        let shards = (0..max_shards.get()).map(|_| {
+            Shard::with_header_in(
+                capacity_per_shard,
+                ShardHead::traced(capacity_per_shard),
+                ShardTail::default(),
+                allocator.clone(),
+            )
+        });
+
+        Self {
+            inner: crate::Arc::new(Inner {
+                shards: shards.collect(),
+                senders: AtomicUsize::new(0),
+            }),
+        }
+    }
 
-        #[cfg(not(feature = "loom"))]
-        let shards = shards.collect();
-        #[cfg(feature = "loom")]
-        let shards = crate::Arc::new(shards.collect());
+    #[inline(always)]
+    fn shards(&self) -> &[Shard<T>] {
+        &self.inner.shards
+    }
+
+    /// Counts one more sender. The MPSC and MPMC sharded senders count here
+    /// rather than on their shard: a clone claims a different shard than its
+    /// parent, so no one shard sees every sender come and go.
+    pub(crate) fn attach_sender(&self) {
+        self.inner.senders.fetch_add(1, Ordering::Relaxed);
+    }
 
-        Self { shards }
+    /// Uncounts a sender. `Release`, so a receiver that sees the count reach
+    /// zero also sees every item the senders pushed.
+    pub(crate) fn detach_sender(&self) {
+        self.inner.senders.fetch_sub(1, Ordering::Release);
+    }
+
+    /// Whether every sender counted with
+    /// [`attach_sender`](Self::attach_sender) has been dropped.
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.inner.senders.load(Ordering::Acquire) == 0
     }
 
     pub(crate) fn len(&self) -> usize {
-        self.shards.len()
+        self.shards().len()
+    }
+
+    /// Usable capacity of one shard.
+    pub(crate) fn shard_capacity(&self) -> usize {
+        self.shards()[0].size()
+    }
+
+    /// Items in each shard, read through the table-owner handles.
+    pub(crate) fn shard_lens(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.shards().iter().map(RingPtr::occupancy)
     }
 
     /// Scans every shard once, starting at `start` (wrapping), and claims the
     /// first whose `role` slot is free. Returns the claimed handle and its index.
     fn claim(&self, role: usize, start: usize) -> Option<(usize, Shard<T>)> {
-        let len = self.shards.len();
+        let len = self.shards().len();
         for offset in 0..len {
             let index = start.wrapping_add(offset) % len;
-            if let Some(shard) = self.shards[index].try_clone_as(role) {
+            if let Some(shard) = self.shards()[index].try_clone_as(role) {
                 return Some((index, shard));
             }
         }
@@ -70,7 +126,7 @@ impl<T> ShardTable<T> {
     /// claimant of `role` exists (channel construction); panics on an occupied
     /// slot.
     fn claim_all(&self, role: usize) -> impl Iterator<Item = Shard<T>> + '_ {
-        self.shards.iter().map(move |shard| {
+        self.shards().iter().map(move |shard| {
             shard
                 .try_clone_as(role)
                 .expect("shard role already claimed")
@@ -120,6 +176,13 @@ impl Cursor {
         }
     }
 
+    /// Every index once, starting at the current position, without moving.
+    /// The order [`find`](Self::find) would visit them in.
+    #[inline]
+    pub(crate) fn indices(&self) -> impl Iterator<Item = usize> {
+        (self.next..self.len).chain(0..self.next)
+    }
+
     /// Visits every index once, starting at the current position, until `f`
     /// returns `Some`. The cursor stays on the index that produced the value,
     /// so follow-ups like `advance`/`release` target the same shard; after a
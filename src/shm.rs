@@ -0,0 +1,276 @@
+//! Shared-memory regions for the inter-process queues.
+//!
+//! An inter-process queue lives entirely inside one `MAP_SHARED` mapping of a
+//! caller-supplied file descriptor: a memfd, an `shm_open` object, or a regular
+//! file. The region starts with a [`Header`] describing the layout, followed by
+//! the variant's control block (cursors and futex word), then the buffer:
+//!
+//! ```text
+//! | Header | control block | padding | buffer[capacity] |
+//! ```
+//!
+//! Every process maps the region at its own address, so nothing in it may be a
+//! pointer, and the queues only carry `T: Copy` plain data. Parking uses shared
+//! (non-private) futex ops, which match waiters by physical page rather than by
+//! address space.
+//!
+//! The creator zeroes the region, writes the layout fields, and publishes the
+//! header with a `Release` store to `state`. Openers check `state` first and then
+//! reject any mismatch in layout, so a queue is never opened by a process that
+//! disagrees about its shape.
+
+use core::{
+    mem::{align_of, size_of},
+    ptr::NonNull,
+};
+use std::{
+    fs::File,
+    io,
+    os::fd::{AsRawFd, BorrowedFd},
+};
+
+use crate::atomic::{AtomicU32, Ordering};
+
+/// Identifies a gil queue region.
+const MAGIC: [u8; 8] = *b"gil-shm\0";
+/// Bumped whenever the layout of any inter-process queue changes.
+const VERSION: u32 = 1;
+
+const INITIALISING: u32 = 0;
+const READY: u32 = 1;
+
+/// Which queue a region holds. Stored in the header so an mpsc region cannot be
+/// opened as an spsc one.
+#[derive(Clone, Copy)]
+#[repr(u32)]
+pub(crate) enum Kind {
+    Spsc = 1,
+    Mpsc = 2,
+}
+
+/// The versioned layout header at the start of every region.
+#[repr(C, align(64))]
+pub(crate) struct Header {
+    magic: [u8; 8],
+    version: u32,
+    kind: u32,
+    word_size: u32,
+    elem_align: u32,
+    elem_size: u64,
+    /// Usable capacity, as requested by the creator.
+    size: u64,
+    state: AtomicU32,
+    /// Set while a receiver (or, for spsc, a sender) is attached.
+    sender: AtomicU32,
+    receiver: AtomicU32,
+}
+
+/// Where the buffer of a `C`-controlled region of `capacity` slots of `S` starts,
+/// and how long the whole region is.
+pub(crate) fn layout<C, S>(capacity: usize) -> io::Result<(usize, usize)> {
+    let offset = size_of::<C>().next_multiple_of(align_of::<S>());
+    let len = size_of::<S>()
+        .checked_mul(capacity)
+        .and_then(|buffer| buffer.checked_add(offset))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "queue too large"))?;
+    Ok((offset, len))
+}
+
+/// One process's mapping of a queue region. Unmapped on drop.
+pub(crate) struct Region {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+impl Region {
+    /// Resizes `fd` to `len` zeroed bytes and maps it.
+    ///
+    /// Anything already in the file is discarded, including a live queue.
+    pub(crate) fn create(fd: BorrowedFd<'_>, len: usize) -> io::Result<Self> {
+        let file = File::from(fd.try_clone_to_owned()?);
+        // shrink first so every byte reads back as zero
+        file.set_len(0)?;
+        file.set_len(len as u64)?;
+        Self::map(fd, len)
+    }
+
+    /// Maps the whole of an existing region.
+    pub(crate) fn open(fd: BorrowedFd<'_>) -> io::Result<Self> {
+        let file = File::from(fd.try_clone_to_owned()?);
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "region too large"))?;
+        if len < size_of::<Header>() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "region too small for a queue header",
+            ));
+        }
+        Self::map(fd, len)
+    }
+
+    fn map(fd: BorrowedFd<'_>, len: usize) -> io::Result<Self> {
+        // SAFETY: a fresh shared mapping; the kernel picks the address.
+        let ptr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            ptr: NonNull::new(ptr.cast()).unwrap(),
+            len,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    pub(crate) fn header(&self) -> &Header {
+        // SAFETY: the mapping is page-aligned and at least a header long.
+        unsafe { self.ptr.cast().as_ref() }
+    }
+
+    /// The region, viewed as a `C` control block.
+    ///
+    /// # Safety
+    ///
+    /// The region must be at least `size_of::<C>()` long, and `C` must be
+    /// `repr(C)` with a [`Header`] first and only atomics after it.
+    #[inline(always)]
+    pub(crate) unsafe fn control<C>(&self) -> &C {
+        unsafe { self.ptr.cast().as_ref() }
+    }
+
+    /// The buffer of `S` slots starting at `offset`.
+    #[inline(always)]
+    pub(crate) fn buffer<S>(&self, offset: usize) -> NonNull<S> {
+        debug_assert!(offset <= self.len);
+        // SAFETY: in bounds of the mapping.
+        unsafe { self.ptr.add(offset).cast() }
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        // SAFETY: mapped in `map`, and every borrow of it is tied to `self`.
+        unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.len) };
+    }
+}
+
+// SAFETY: the mapping is plain memory; what lives in it is synchronised by the
+// queue protocols.
+unsafe impl Send for Region {}
+unsafe impl Sync for Region {}
+
+impl Header {
+    /// Writes the layout fields of a freshly zeroed region and publishes it.
+    ///
+    /// # Safety
+    ///
+    /// `region` must come from [`Region::create`], with everything the variant
+    /// needs beyond zeroes already initialised.
+    pub(crate) unsafe fn publish<T>(region: &Region, kind: Kind, size: usize) {
+        let header = region.ptr.cast::<Header>().as_ptr();
+        // SAFETY: no one reads these before `state` turns `READY`.
+        unsafe {
+            (*header).magic = MAGIC;
+            (*header).version = VERSION;
+            (*header).kind = kind as u32;
+            (*header).word_size = size_of::<usize>() as u32;
+            (*header).elem_align = align_of::<T>() as u32;
+            (*header).elem_size = size_of::<T>() as u64;
+            (*header).size = size as u64;
+        }
+        region.header().state.store(READY, Ordering::Release);
+    }
+
+    /// Checks that the region holds a `kind` queue of `T` and returns its usable
+    /// capacity.
+    pub(crate) fn validate<T>(&self, kind: Kind) -> io::Result<usize> {
+        if self.state.load(Ordering::Acquire) == INITIALISING {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "queue is still being created",
+            ));
+        }
+
+        let invalid = |what| Err(io::Error::new(io::ErrorKind::InvalidData, what));
+        if self.magic != MAGIC {
+            return invalid("not a gil queue");
+        }
+        if self.version != VERSION {
+            return invalid("queue layout version mismatch");
+        }
+        if self.kind != kind as u32 {
+            return invalid("queue kind mismatch");
+        }
+        if self.word_size != size_of::<usize>() as u32 {
+            return invalid("queue created with a different pointer width");
+        }
+        if self.elem_size != size_of::<T>() as u64 || self.elem_align != align_of::<T>() as u32 {
+            return invalid("queue element layout mismatch");
+        }
+        match usize::try_from(self.size) {
+            Ok(size) if size > 0 => Ok(size),
+            _ => invalid("queue capacity out of range"),
+        }
+    }
+
+    /// Claims the sender slot; fails if another sender holds it.
+    pub(crate) fn attach_sender(&self) -> io::Result<()> {
+        attach(&self.sender, "queue already has a sender")
+    }
+
+    pub(crate) fn detach_sender(&self) {
+        self.sender.store(0, Ordering::Release);
+    }
+
+    /// Claims the receiver slot; fails if another receiver holds it.
+    pub(crate) fn attach_receiver(&self) -> io::Result<()> {
+        attach(&self.receiver, "queue already has a receiver")
+    }
+
+    pub(crate) fn detach_receiver(&self) {
+        self.receiver.store(0, Ordering::Release);
+    }
+}
+
+fn attach(flag: &AtomicU32, busy: &'static str) -> io::Result<()> {
+    // `Acquire` pairs with the previous holder's detach, so its last cursor
+    // store is visible to the new one
+    flag.compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed)
+        .map(|_| ())
+        .map_err(|_| io::Error::new(io::ErrorKind::ResourceBusy, busy))
+}
+
+/// Blocks while `word` reads `expected`, across processes.
+pub(crate) fn futex_wait(word: &AtomicU32, expected: u32) {
+    // SAFETY: `word` is a valid, aligned futex word for the duration of the call.
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            word.as_ptr(),
+            libc::FUTEX_WAIT,
+            expected,
+            core::ptr::null::<libc::timespec>(),
+        );
+    }
+}
+
+/// Wakes up to `count` waiters on `word`, in any process.
+pub(crate) fn futex_wake(word: &AtomicU32, count: i32) {
+    // SAFETY: as above.
+    unsafe {
+        libc::syscall(libc::SYS_futex, word.as_ptr(), libc::FUTEX_WAKE, count);
+    }
+}
@@ -36,7 +36,9 @@
 //! - **Cloneability:** [`Receiver`] implements `Clone`, but [`Sender`] does not. This is the
 //!   opposite of MPSC. Clone receivers to distribute to multiple consumer threads.
 //! - **No Async:** Unlike SPSC, this queue does not have async support.
-//! - **No Batch Operations:** This queue does not support batch operations.
+//! - **Copying Batch Reads:** [`Receiver::recv_many`] and [`Receiver::read_guard`] take a
+//!   whole run of ready items at once, but cells aren't contiguous, so the items are moved
+//!   into a receiver-owned buffer rather than read in place.
 //! - **Capacity Rounding:** The usable capacity is rounded up to the next power of two
 //!   (e.g. requesting 1000 yields a queue that holds 1024 items). This differs from the SPSC
 //!   queue, which holds exactly the requested capacity.
@@ -47,6 +49,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 pub use self::{receiver::Receiver, sender::Sender};
 
 mod queue;
@@ -77,7 +81,26 @@ pub mod sharded_parking;
 /// let (tx, rx) = channel::<usize>(NonZeroUsize::new(1024).unwrap());
 /// ```
 pub fn channel<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
-    let queue = queue::QueuePtr::with_size(capacity);
+    channel_in(capacity, Global)
+}
+
+/// Like [`channel`], but allocates the queue from `allocator`.
+///
+/// The allocator moves into the queue and frees it once the last handle drops.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, spmc::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(NonZeroUsize::new(1024).unwrap(), Global);
+/// ```
+pub fn channel_in<T, A: Allocator + Send + 'static>(
+    capacity: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    let queue = queue::QueuePtr::with_size_in(capacity, allocator);
     queue.initialize::<crate::cell::CellInit<T>>();
 
     (Sender::new(queue.clone()), Receiver::new(queue))
@@ -133,4 +156,61 @@ mod test {
         }
         assert!(rx.try_recv().is_none());
     }
+
+    #[test]
+    fn send_with_try_full() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(2).unwrap());
+        tx.send_with(|slot| slot.write(1));
+        assert!(tx.try_send_with(|slot| slot.write(2)).is_ok());
+        assert!(tx.try_send_with(|_| unreachable!()).is_err());
+        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv(), 2);
+    }
+
+    #[test]
+    fn len() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(3).unwrap());
+        assert!(tx.is_empty() && rx.is_empty());
+        assert_eq!((tx.capacity(), rx.capacity()), (4, 4));
+
+        for i in 0..4 {
+            tx.send(i);
+            assert_eq!((tx.len(), rx.len()), (i + 1, i + 1));
+        }
+        assert!(tx.is_full() && rx.is_full());
+
+        rx.recv();
+        rx.recv();
+        assert_eq!((tx.len(), rx.len()), (2, 2));
+    }
+
+    #[test]
+    fn recv_many_wraps() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        for i in 0..3 {
+            tx.send(i);
+        }
+        assert_eq!(rx.recv(), 0);
+        tx.send(3);
+        tx.send(4);
+
+        // one claim across the wrap point
+        let mut out = std::vec::Vec::new();
+        assert_eq!(rx.try_recv_many(&mut out, 8), 4);
+        assert_eq!(out, [1, 2, 3, 4]);
+        assert_eq!(rx.try_recv_many(&mut out, 8), 0);
+
+        // a clone doesn't see what the original has staged
+        for i in 5..8 {
+            tx.send(i);
+        }
+        let mut guard = rx.read_guard();
+        assert_eq!(guard.as_slice(), [5, 6, 7]);
+        guard.advance(1);
+        drop(guard);
+        let mut rx2 = rx.clone();
+        assert_eq!(rx2.try_recv(), None);
+        assert_eq!(rx.recv_many(&mut out, 8), 2);
+        assert_eq!(out[4..], [6, 7]);
+    }
 }
@@ -2,7 +2,7 @@ use crate::{
     atomic::AtomicUsize,
     cell::{Cell, DropHeadScan},
     padded::Padded,
-    ring::RingHead,
+    ring::{RingHead, RingTail},
 };
 
 #[derive(Default)]
@@ -11,6 +11,14 @@ pub(crate) struct Head {
     head: Padded<AtomicUsize>,
 }
 
+/// The producer's tail. Only the `len()` family reads it: the sender tracks
+/// its position locally and publishes it here after each push.
+#[derive(Default)]
+#[repr(C)]
+pub(crate) struct Tail {
+    tail: Padded<AtomicUsize>,
+}
+
 impl RingHead for Head {
     #[inline(always)]
     fn head(&self) -> &AtomicUsize {
@@ -18,7 +26,14 @@ impl RingHead for Head {
     }
 }
 
-pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, (), Cell<T>, DropHeadScan>;
+impl RingTail for Tail {
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        &self.tail.value
+    }
+}
+
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, Tail, Cell<T>, DropHeadScan>;
 
 impl<T> QueuePtr<T> {
     #[inline(always)]
@@ -26,6 +41,11 @@ impl<T> QueuePtr<T> {
         self.header().head.head()
     }
 
+    #[inline(always)]
+    pub(crate) fn tail(&self) -> &AtomicUsize {
+        self.header().tail.tail()
+    }
+
     #[inline(always)]
     pub(crate) fn cell_at(&self, index: usize) -> &Cell<T> {
         // SAFETY: `at` masks the index into the buffer, and every cell is valid
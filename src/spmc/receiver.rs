@@ -1,4 +1,8 @@
-use crate::{atomic::Ordering, spmc::queue::QueuePtr};
+use alloc_crate::vec::Vec;
+
+use crate::{
+    atomic::Ordering, cell::Staged, read_guard::BatchReader, spmc::queue::QueuePtr, stats::Counters,
+};
 
 /// The consumer end of the SPMC queue.
 ///
@@ -28,6 +32,8 @@ use crate::{atomic::Ordering, spmc::queue::QueuePtr};
 pub struct Receiver<T> {
     ptr: QueuePtr<T>,
     local_head: usize,
+    stats: Counters,
+    staged: Staged<T>,
 }
 
 impl<T> Receiver<T> {
@@ -35,6 +41,8 @@ impl<T> Receiver<T> {
         Self {
             ptr: queue_ptr,
             local_head: 0,
+            stats: Counters::default(),
+            staged: Staged::default(),
         }
     }
 
@@ -116,6 +124,10 @@ impl<T> Receiver<T> {
     pub fn try_recv(&mut self) -> Option<T> {
         use core::cmp::Ordering as Cmp;
 
+        if let Some(ret) = self.staged.pop() {
+            return Some(ret);
+        }
+
         let mut backoff = crate::ExponentialBackoff::new(6, 10);
         loop {
             let cell = self.ptr.cell_at(self.local_head);
@@ -138,9 +150,20 @@ impl<T> Receiver<T> {
                                 Ordering::Release,
                             );
                             self.local_head = next_head;
+                            self.stats.received(1);
+                            self.stats.observe(|| {
+                                crate::ring::occupancy(
+                                    self.ptr.head(),
+                                    self.ptr.tail(),
+                                    self.ptr.capacity,
+                                )
+                            });
                             return Some(ret);
                         }
-                        Err(cur_head) => self.local_head = cur_head,
+                        Err(cur_head) => {
+                            self.stats.cas_retry();
+                            self.local_head = cur_head;
+                        }
                     }
                 }
                 Cmp::Greater => self.local_head = self.ptr.head().load(Ordering::Relaxed),
@@ -149,6 +172,221 @@ impl<T> Receiver<T> {
             backoff.backoff();
         }
     }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// the sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Moves up to `max` available values into `dst` without blocking.
+    ///
+    /// Claims the whole run of published cells at the head with a single CAS,
+    /// instead of one per value. Returns how many values were moved; `0` means
+    /// the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spmc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// for i in 0..5 {
+    ///     tx.send(i);
+    /// }
+    ///
+    /// let mut out = Vec::new();
+    /// assert_eq!(rx.try_recv_many(&mut out, 3), 3);
+    /// assert_eq!(rx.try_recv_many(&mut out, 8), 2);
+    /// assert_eq!(out, [0, 1, 2, 3, 4]);
+    /// ```
+    pub fn try_recv_many(&mut self, dst: &mut Vec<T>, max: usize) -> usize {
+        let n = self.staged.drain_into(dst, max);
+        n + self.claim(max - n, |value| dst.push(value))
+    }
+
+    /// Like [`try_recv_many`](Self::try_recv_many), but blocks until at least
+    /// one value is available (unless `max` is `0`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spmc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// std::thread::spawn(move || tx.send(7));
+    ///
+    /// let mut out = Vec::new();
+    /// assert_eq!(rx.recv_many(&mut out, 4), 1);
+    /// assert_eq!(out, [7]);
+    /// ```
+    pub fn recv_many(&mut self, dst: &mut Vec<T>, max: usize) -> usize {
+        let n = self.try_recv_many(dst, max);
+        if n > 0 || max == 0 {
+            return n;
+        }
+        dst.push(self.recv());
+        1 + self.try_recv_many(dst, max - 1)
+    }
+
+    /// Returns a [`ReadGuard`](crate::read_guard::ReadGuard) over the values
+    /// available at the head.
+    ///
+    /// Cells don't form a contiguous `[T]`, so the guard's slice is a buffer
+    /// owned by this receiver: the run is claimed, moved out and its cells
+    /// handed back to the senders when the guard is created. Values the guard
+    /// doesn't advance past stay with this receiver, not its clones, and come
+    /// out of its next `recv`, `try_recv` or guard first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spmc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+    /// for i in 0..5 {
+    ///     tx.send(i);
+    /// }
+    ///
+    /// let mut guard = rx.read_guard();
+    /// assert_eq!(guard.as_slice(), &[0, 1, 2, 3, 4]);
+    /// guard.advance(3);
+    /// drop(guard);
+    ///
+    /// assert_eq!(rx.try_recv(), Some(3));
+    /// ```
+    pub fn read_guard(&mut self) -> crate::read_guard::ReadGuard<'_, Self> {
+        crate::read_guard::ReadGuard::new(self)
+    }
+
+    /// Claims up to `max` published cells from the head with one CAS and hands
+    /// their values to `take`.
+    fn claim(&mut self, max: usize, mut take: impl FnMut(T)) -> usize {
+        use core::cmp::Ordering as Cmp;
+
+        if max == 0 {
+            return 0;
+        }
+
+        let max = max.min(self.ptr.capacity);
+        let mut backoff = crate::ExponentialBackoff::new(6, 10);
+        let start = loop {
+            let epoch = self
+                .ptr
+                .cell_at(self.local_head)
+                .epoch()
+                .load(Ordering::Acquire);
+
+            match epoch.cmp(&self.local_head.wrapping_add(1)) {
+                Cmp::Less => return 0,
+                Cmp::Equal => {
+                    // extend over the published cells behind it; each stays
+                    // published until whoever claims its ticket releases it
+                    let mut n = 1;
+                    while n < max {
+                        let ticket = self.local_head.wrapping_add(n);
+                        let epoch = self.ptr.cell_at(ticket).epoch().load(Ordering::Acquire);
+                        if epoch != ticket.wrapping_add(1) {
+                            break;
+                        }
+                        n += 1;
+                    }
+
+                    let end = self.local_head.wrapping_add(n);
+                    match self.ptr.head().compare_exchange_weak(
+                        self.local_head,
+                        end,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(start) => {
+                            self.local_head = end;
+                            break start;
+                        }
+                        Err(cur_head) => {
+                            self.stats.cas_retry();
+                            self.local_head = cur_head;
+                        }
+                    }
+                }
+                Cmp::Greater => self.local_head = self.ptr.head().load(Ordering::Relaxed),
+            }
+
+            backoff.backoff();
+        };
+
+        // the claim can't be undone, and a cell left unreleased stalls the
+        // senders a lap later
+        let guard = crate::cell::AbortOnUnwind("consumer panicked holding claimed cells");
+        let n = self.local_head.wrapping_sub(start);
+        for i in 0..n {
+            let ticket = start.wrapping_add(i);
+            let cell = self.ptr.cell_at(ticket);
+            let value = unsafe { cell.get() };
+            cell.epoch()
+                .store(ticket.wrapping_add(self.ptr.capacity), Ordering::Release);
+            take(value);
+        }
+        guard.disarm();
+
+        self.stats.received(n);
+        self.stats.observe(|| {
+            crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+        });
+        n
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// Read from the shared head and tail while other handles may be moving
+    /// them, so this is an approximate snapshot. Items whose slot is claimed
+    /// but not yet written or read count as present.
+    #[inline]
+    pub fn len(&self) -> usize {
+        crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once the [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.ptr.is_disconnected()
+    }
+
+    /// The number of items the queue holds when full: the requested capacity
+    /// rounded up to the next power of two.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.ptr.capacity
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.stats.snapshot()
+    }
 }
 
 impl<T> Clone for Receiver<T> {
@@ -156,8 +394,67 @@ impl<T> Clone for Receiver<T> {
         Self {
             ptr: self.ptr.clone(),
             local_head: self.ptr.head().load(Ordering::Relaxed),
+            stats: Counters::default(),
+            staged: Staged::default(),
         }
     }
 }
 
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 unsafe impl<T: Send> Send for Receiver<T> {}
+
+/// Lends values out of the receiver's staging buffer; see
+/// [`read_guard`](Receiver::read_guard).
+unsafe impl<T> BatchReader for Receiver<T> {
+    type Item = T;
+
+    fn read_buffer(&mut self) -> &[T] {
+        if self.staged.is_empty() {
+            let mut staged = core::mem::take(&mut self.staged);
+            staged.reset();
+            self.claim(self.ptr.capacity, |value| staged.push(value));
+            self.staged = staged;
+        }
+        self.staged.as_slice()
+    }
+
+    unsafe fn advance(&mut self, n: usize) {
+        self.staged.advance(n);
+    }
+}
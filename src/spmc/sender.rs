@@ -1,4 +1,6 @@
-use crate::{atomic::Ordering, spmc::queue::QueuePtr};
+use core::mem::MaybeUninit;
+
+use crate::{atomic::Ordering, cell::Cell, spmc::queue::QueuePtr, stats::Counters};
 
 /// The producer end of the SPMC queue.
 ///
@@ -20,13 +22,22 @@ use crate::{atomic::Ordering, spmc::queue::QueuePtr};
 pub struct Sender<T> {
     ptr: QueuePtr<T>,
     local_tail: usize,
+    stats: Counters,
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.ptr.detach_sender();
+    }
 }
 
 impl<T> Sender<T> {
     pub(crate) fn new(queue_ptr: QueuePtr<T>) -> Self {
+        queue_ptr.attach_sender();
         Self {
             ptr: queue_ptr,
             local_tail: 0,
+            stats: Counters::default(),
         }
     }
 
@@ -72,16 +83,12 @@ impl<T> Sender<T> {
     /// assert_eq!(rx.recv(), 42);
     /// ```
     pub fn send_with_spin_count(&mut self, value: T, spin_count: u32) {
-        let cell = self.ptr.cell_at(self.local_tail);
-        let mut backoff = crate::Backoff::with_spin_count(spin_count);
-        while cell.epoch().load(Ordering::Acquire) != self.local_tail {
-            backoff.backoff();
-        }
-
         let next = self.local_tail.wrapping_add(1);
+        let cell = self.wait_for_cell(spin_count);
+
         cell.set(value);
         cell.epoch().store(next, Ordering::Release);
-        self.local_tail = next;
+        self.published(next);
     }
 
     /// Attempts to send a value into the queue without blocking.
@@ -106,16 +113,191 @@ impl<T> Sender<T> {
     pub fn try_send(&mut self, value: T) -> Result<(), T> {
         let cell = self.ptr.cell_at(self.local_tail);
         if cell.epoch().load(Ordering::Acquire) != self.local_tail {
+            self.stats.full();
             return Err(value);
         }
 
         let next = self.local_tail.wrapping_add(1);
         cell.set(value);
         cell.epoch().store(next, Ordering::Release);
-        self.local_tail = next;
+        self.published(next);
 
         Ok(())
     }
+
+    /// Constructs the next item directly in its cell, spinning while the queue
+    /// is full.
+    ///
+    /// `f` is called once the cell is free. The item is published when `f`
+    /// returns; if `f` panics, nothing is sent.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spmc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<[u32; 256]>(NonZeroUsize::new(16).unwrap());
+    /// tx.send_with(|slot| slot.write([7; 256]));
+    /// assert_eq!(rx.recv()[255], 7);
+    /// ```
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        let next = self.local_tail.wrapping_add(1);
+        let cell = self.wait_for_cell(128);
+
+        // the epoch says the consumers are done with this cell, and there is
+        // no other producer
+        cell.emplace(f);
+        cell.epoch().store(next, Ordering::Release);
+        self.published(next);
+    }
+
+    /// Non-blocking [`send_with`](Sender::send_with). Returns `Err(f)` without
+    /// calling it if the queue is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        let cell = self.ptr.cell_at(self.local_tail);
+        if cell.epoch().load(Ordering::Acquire) != self.local_tail {
+            self.stats.full();
+            return Err(f);
+        }
+
+        let next = self.local_tail.wrapping_add(1);
+        cell.emplace(f);
+        cell.epoch().store(next, Ordering::Release);
+        self.published(next);
+
+        Ok(())
+    }
+
+    fn wait_for_cell(&mut self, spin_count: u32) -> &Cell<T> {
+        let cell = self.ptr.cell_at(self.local_tail);
+        if cell.epoch().load(Ordering::Acquire) != self.local_tail {
+            self.stats.full();
+            let mut backoff = crate::Backoff::with_spin_count(spin_count);
+            while cell.epoch().load(Ordering::Acquire) != self.local_tail {
+                backoff.backoff();
+            }
+        }
+        cell
+    }
+
+    /// Steps past the cell just written and publishes the tail for `len()`.
+    #[inline(always)]
+    fn published(&mut self, next: usize) {
+        self.local_tail = next;
+        self.ptr.tail().store(next, Ordering::Relaxed);
+        self.stats.sent(1);
+        self.stats.observe(|| {
+            crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+        });
+    }
+
+    /// Sends every item of `values`, blocking while the queue is full.
+    ///
+    /// With a single producer no cell needs claiming, so this is
+    /// [`send`](Self::send) in a loop; it exists for parity with the other
+    /// senders.
+    pub fn send_slice(&mut self, values: &[T])
+    where
+        T: Copy,
+    {
+        self.send_all(values.iter().copied());
+    }
+
+    /// Sends items from `iter` until it runs dry or the queue fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// An item is only taken from `iter` once its cell is free; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spmc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<u32>(NonZeroUsize::new(2).unwrap());
+    /// let mut items = 1..10;
+    /// assert_eq!(tx.try_send_iter(items.by_ref()), 2);
+    /// assert_eq!(items.next(), Some(3));
+    /// ```
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let mut sent = 0;
+        loop {
+            let cell = self.ptr.cell_at(self.local_tail);
+            if cell.epoch().load(Ordering::Acquire) != self.local_tail {
+                return sent;
+            }
+            let Some(value) = iter.next() else {
+                return sent;
+            };
+
+            let next = self.local_tail.wrapping_add(1);
+            cell.set(value);
+            cell.epoch().store(next, Ordering::Release);
+            self.published(next);
+            sent += 1;
+        }
+    }
+
+    /// Sends every item of `iter`, blocking while the queue is full.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        for value in iter {
+            self.send(value);
+        }
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// Read from the shared head and tail while other handles may be moving
+    /// them, so this is an approximate snapshot. Items whose slot is claimed
+    /// but not yet written or read count as present.
+    #[inline]
+    pub fn len(&self) -> usize {
+        crate::ring::occupancy(self.ptr.head(), self.ptr.tail(), self.ptr.capacity)
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: the requested capacity
+    /// rounded up to the next power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spmc::channel;
+    ///
+    /// let (tx, _rx) = channel::<i32>(NonZeroUsize::new(3).unwrap());
+    /// assert_eq!(tx.capacity(), 4);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.ptr.capacity
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.stats.snapshot()
+    }
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
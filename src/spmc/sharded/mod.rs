@@ -29,6 +29,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 use crate::shard_table::ShardTable;
 
 mod receiver;
@@ -47,7 +49,32 @@ pub fn channel<T>(
     max_shards: NonZeroUsize,
     capacity_per_shard: NonZeroUsize,
 ) -> (Sender<T>, Receiver<T>) {
-    let table = ShardTable::new(max_shards, capacity_per_shard);
+    channel_in(max_shards, capacity_per_shard, Global)
+}
+
+/// Like [`channel`], but allocates every shard from a clone of `allocator`.
+///
+/// Each shard frees itself through its clone once the last endpoint using it
+/// drops, so the clones may outlive one another.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, spmc::sharded::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(
+///     NonZeroUsize::new(4).unwrap(),
+///     NonZeroUsize::new(256).unwrap(),
+///     Global,
+/// );
+/// ```
+pub fn channel_in<T, A: Allocator + Clone + Send + 'static>(
+    max_shards: NonZeroUsize,
+    capacity_per_shard: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    let table = ShardTable::new_in(max_shards, capacity_per_shard, allocator);
 
     let sender = Sender::new(&table);
     let receiver = Receiver::new(table);
@@ -166,7 +193,7 @@ mod test {
                 for (i, &val) in guard.as_slice().iter().enumerate() {
                     assert_eq!(val, received + i);
                 }
-                let count = guard.len();
+                let count = guard.as_slice().len();
                 guard.advance(count);
                 received += count;
             }
@@ -225,4 +252,66 @@ mod test {
             assert_eq!(rx2.recv(), i * 2 + 1);
         }
     }
+
+    #[test]
+    fn send_slice_round_robin() {
+        let (mut tx, mut rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(2).unwrap());
+        let mut rx2 = rx.try_clone().unwrap();
+
+        tx.send_slice(&[0, 1, 2]);
+        assert_eq!(tx.try_send_iter(3..10), 1);
+        assert_eq!(tx.shard_lens().collect::<std::vec::Vec<_>>(), [2, 2]);
+
+        assert_eq!((rx.recv(), rx.recv()), (0, 2));
+        assert_eq!((rx2.recv(), rx2.recv()), (1, 3));
+    }
+
+    #[test]
+    fn write_guard_moves_to_next_shard() {
+        let (mut tx, mut rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut rx2 = rx.try_clone().unwrap();
+
+        drop(tx.write_guard());
+        tx.write_guard().extend([0, 1]);
+        tx.write_guard().push(2).unwrap();
+        assert_eq!(tx.shard_lens().collect::<std::vec::Vec<_>>(), [2, 1]);
+
+        assert_eq!((rx.recv(), rx.recv()), (0, 1));
+        assert_eq!(rx2.recv(), 2);
+    }
+
+    #[test]
+    fn len_per_shard() {
+        let (mut tx, rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        for i in 0..3 {
+            tx.send(i);
+        }
+
+        assert_eq!((tx.len(), rx.len()), (3, 3));
+        assert_eq!((tx.capacity(), rx.capacity()), (8, 8));
+        assert_eq!(tx.shard_lens().sum::<usize>(), 3);
+        assert_eq!(
+            tx.shard_lens().collect::<std::vec::Vec<_>>(),
+            rx.shard_lens().collect::<std::vec::Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn stats_sum_over_shards() {
+        let (mut tx, mut rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+        let mut rx2 = rx.try_clone().unwrap();
+        for i in 0..4 {
+            tx.send(i);
+        }
+        while rx.try_recv().is_some() {}
+        while rx2.try_recv().is_some() {}
+
+        assert_eq!(tx.stats().sent, 4);
+        assert_eq!(rx.stats().received + rx2.stats().received, 4);
+    }
 }
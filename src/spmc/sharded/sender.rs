@@ -25,15 +25,7 @@ impl<T> Sender<T> {
 
     /// Sends a value to the next shard in round-robin order, blocking if that shard is full.
     pub fn send(&mut self, value: T) {
-        let producer = &mut self.producers[self.cursor.index()];
-
-        let mut backoff = Backoff::with_spin_count(128);
-        while producer.is_full() {
-            backoff.backoff();
-            producer.refresh_head();
-        }
-        producer.push(value);
-
+        self.wait_for_space().push(value);
         self.cursor.step();
     }
 
@@ -46,6 +38,45 @@ impl<T> Sender<T> {
         Ok(())
     }
 
+    /// Constructs the next item directly in the next shard's tail slot,
+    /// blocking if that shard is full.
+    ///
+    /// The item is published when `f` returns; if `f` panics, nothing is sent
+    /// and the round-robin position does not move.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        unsafe { self.wait_for_space().push_with(f) };
+        self.cursor.step();
+    }
+
+    /// Non-blocking [`send_with`](Sender::send_with). Returns `Err(f)` without
+    /// calling it if the next shard is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.producers[self.cursor.index()].try_push_with(f)?;
+        self.cursor.step();
+        Ok(())
+    }
+
+    /// Spins until the next shard has space and returns its producer.
+    fn wait_for_space(&mut self) -> &mut Producer<Shard<T>> {
+        let producer = &mut self.producers[self.cursor.index()];
+
+        if !producer.has_space() {
+            let mut backoff = Backoff::with_spin_count(128);
+            while producer.is_full() {
+                backoff.backoff();
+                producer.refresh_head();
+            }
+        }
+        producer
+    }
+
     /// Returns a mutable slice of the write buffer for the current shard.
     ///
     /// After writing, call [`commit`](Sender::commit).
@@ -53,6 +84,12 @@ impl<T> Sender<T> {
         self.producers[self.cursor.index()].write_buffer()
     }
 
+    /// Like [`write_buffer`](Self::write_buffer), plus the current shard's free
+    /// slots that wrap to the start of its ring. Fill the first slice first.
+    pub fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        self.producers[self.cursor.index()].write_slices()
+    }
+
     /// Commits `len` elements from the write buffer of the current shard.
     ///
     /// # Safety
@@ -62,6 +99,118 @@ impl<T> Sender<T> {
         unsafe { self.producers[self.cursor.index()].commit(len) };
         self.cursor.step();
     }
+
+    /// Returns a [`WriteGuard`](crate::write_guard::WriteGuard) over the free
+    /// slots of the current shard: a safe alternative to
+    /// [`write_buffer`](Self::write_buffer) and [`commit`](Self::commit).
+    ///
+    /// The guard commits exactly the items written through it when dropped.
+    /// As with [`commit`](Self::commit), the next write goes to the next shard,
+    /// unless the guard was dropped empty.
+    pub fn write_guard(&mut self) -> crate::write_guard::WriteGuard<'_, Self> {
+        crate::write_guard::WriteGuard::new(self)
+    }
+
+    /// Sends every item of `values` in round-robin order, blocking while the
+    /// next shard is full.
+    ///
+    /// Each item goes to its own shard, so this is [`send`](Self::send) in a
+    /// loop; it exists for parity with the other senders.
+    pub fn send_slice(&mut self, values: &[T])
+    where
+        T: Copy,
+    {
+        self.send_all(values.iter().copied());
+    }
+
+    /// Sends items from `iter` in round-robin order until it runs dry or the
+    /// next shard is full, without blocking. Returns the number sent.
+    ///
+    /// An item is only taken from `iter` once its shard has room; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let mut sent = 0;
+        loop {
+            let producer = &mut self.producers[self.cursor.index()];
+            if !producer.has_space() {
+                return sent;
+            }
+            let Some(value) = iter.next() else {
+                return sent;
+            };
+            producer.push(value);
+
+            self.cursor.step();
+            sent += 1;
+        }
+    }
+
+    /// Sends every item of `iter` in round-robin order, blocking while the
+    /// next shard is full.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        for value in iter {
+            self.send(value);
+        }
+    }
+
+    /// Total number of items across all shards.
+    ///
+    /// Each shard is read on its own while other handles keep sending and
+    /// receiving, so the sum is approximate. See [`shard_lens`](Self::shard_lens)
+    /// for the per-shard breakdown.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shard_lens().sum()
+    }
+
+    /// Returns `true` if every shard is empty. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if every shard is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Total capacity: `max_shards * capacity_per_shard`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.producers.len() * self.producers[0].size()
+    }
+
+    /// Number of items in each shard, in shard index order. Each entry is a
+    /// snapshot, as with [`len`](Self::len).
+    pub fn shard_lens(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.producers.iter().map(|producer| producer.len())
+    }
+
+    /// Counters for this sender, summed over every shard it feeds. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.producers
+            .iter()
+            .map(|producer| producer.stats.snapshot())
+            .fold(crate::Stats::default(), crate::Stats::merge)
+    }
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
+
+unsafe impl<T> crate::write_guard::BatchWriter for Sender<T> {
+    type Item = T;
+
+    fn write_buffer(&mut self) -> &mut [MaybeUninit<T>] {
+        Sender::write_buffer(self)
+    }
+
+    fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        Sender::write_slices(self)
+    }
+
+    unsafe fn commit(&mut self, n: usize) {
+        unsafe { Sender::commit(self, n) }
+    }
+}
@@ -28,6 +28,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 use crate::shard_table::ShardTable;
 
 mod receiver;
@@ -46,7 +48,32 @@ pub fn channel<T>(
     max_shards: NonZeroUsize,
     capacity_per_shard: NonZeroUsize,
 ) -> (Sender<T>, Receiver<T>) {
-    let table = ShardTable::new(max_shards, capacity_per_shard);
+    channel_in(max_shards, capacity_per_shard, Global)
+}
+
+/// Like [`channel`], but allocates every shard from a clone of `allocator`.
+///
+/// Each shard frees itself through its clone once the last endpoint using it
+/// drops, so the clones may outlive one another.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, spmc::sharded_parking::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(
+///     NonZeroUsize::new(4).unwrap(),
+///     NonZeroUsize::new(256).unwrap(),
+///     Global,
+/// );
+/// ```
+pub fn channel_in<T, A: Allocator + Clone + Send + 'static>(
+    max_shards: NonZeroUsize,
+    capacity_per_shard: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    let table = ShardTable::new_in(max_shards, capacity_per_shard, allocator);
 
     let sender = Sender::new(&table);
     let receiver = Receiver::new(table);
@@ -286,4 +313,28 @@ mod test {
         }
         assert_eq!(dropped.load(Ordering::SeqCst), 5);
     }
+
+    #[test]
+    fn iter_ends_for_parked_receivers() {
+        let (mut tx, rx) =
+            channel::<usize>(NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(4).unwrap());
+
+        thread::scope(|scope| {
+            let rx1 = rx.try_clone().unwrap();
+            let handles: Vec<_> = [rx, rx1]
+                .into_iter()
+                .map(|rx| scope.spawn(move || rx.into_iter().count()))
+                .collect();
+
+            for i in 0..100 {
+                tx.send(i);
+            }
+            // let the receivers park before the disconnect has to wake them
+            thread::sleep(core::time::Duration::from_millis(20));
+            drop(tx);
+
+            let received: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+            assert_eq!(received, 100);
+        });
+    }
 }
@@ -29,7 +29,10 @@ impl<T> Receiver<T> {
     /// This scans the shard table and may touch up to `max_shards` atomics. Prefer
     /// creating long-lived receivers instead of cloning and dropping in a hot path.
     pub fn try_clone(&self) -> Option<Self> {
-        Self::init(self.table.clone(), self.shard_idx.wrapping_add(1))
+        let mut clone = Self::init(self.table.clone(), self.shard_idx.wrapping_add(1))?;
+        // the clone reports to the same latency sink, if any
+        clone.consumer.tracer = self.consumer.tracer.clone();
+        Some(clone)
     }
 
     fn init(table: ShardTable<T>, start: usize) -> Option<Self> {
@@ -46,21 +49,35 @@ impl<T> Receiver<T> {
     /// After a brief spin and yield phase, parks on the shard's futex.
     /// The sender wakes parked receivers after writing.
     pub fn recv(&mut self) -> T {
+        self.wait(|_| false);
+        self.consumer.pop()
+    }
+
+    /// Waits until the shard holds an item (`true`), or until `give_up`
+    /// returns `true` with the shard still empty (`false`).
+    #[inline]
+    fn wait(&mut self, give_up: impl Fn(&Self) -> bool) -> bool {
         let mut backoff = crate::ParkingBackoff::new(16, 4);
         while self.consumer.is_empty() {
+            if give_up(self) {
+                // the sender may have published just before giving up
+                self.consumer.refresh_tail();
+                return !self.consumer.is_empty();
+            }
             if backoff.backoff() {
                 let futex = self.consumer.ring().futex();
                 if futex.announce(RECEIVER_WAITING) {
                     // catch lost wakes: recheck against a fresh tail before parking
                     self.consumer.refresh_tail();
-                    if self.consumer.is_empty() {
+                    if self.consumer.is_empty() && !give_up(self) {
+                        self.consumer.stats.park();
                         futex.sleep(RECEIVER_WAITING);
                     }
                 }
             }
             self.consumer.refresh_tail();
         }
-        self.consumer.pop()
+        true
     }
 
     /// Attempts to receive without blocking.
@@ -68,10 +85,123 @@ impl<T> Receiver<T> {
         self.consumer.try_pop()
     }
 
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// the sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, ending once the
+    /// queue is empty. Reads through the batch path, so the head is published
+    /// once per run of items rather than per item. See
+    /// [`Drain`](crate::iter::Drain).
+    pub fn try_iter(&mut self) -> crate::iter::Drain<'_, Self> {
+        crate::iter::Drain::new(self)
+    }
+
     /// Returns a [`ReadGuard`](crate::read_guard::ReadGuard) for batch reading.
     pub fn read_guard(&mut self) -> crate::read_guard::ReadGuard<'_, Self> {
         crate::read_guard::ReadGuard::new(self)
     }
+
+    /// Total number of items across all shards.
+    ///
+    /// Each shard is read on its own while other handles keep sending and
+    /// receiving, so the sum is approximate. See [`shard_lens`](Self::shard_lens)
+    /// for the per-shard breakdown.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.shard_lens().sum()
+    }
+
+    /// Returns `true` if every shard is empty. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if every shard is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once the [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.consumer.is_disconnected()
+    }
+
+    /// Total capacity: `max_shards * capacity_per_shard`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.table.len() * self.table.shard_capacity()
+    }
+
+    /// Number of items in each shard, in shard index order. Each entry is a
+    /// snapshot, as with [`len`](Self::len).
+    pub fn shard_lens(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.table.shard_lens()
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.consumer.stats.snapshot()
+    }
+
+    /// Reports how long each item spent in the queue to `sink`, from the
+    /// moment it was published to the moment it is received here.
+    ///
+    /// Replaces any sink installed earlier. See [`LatencySink`](crate::LatencySink).
+    #[cfg(feature = "latency")]
+    pub fn set_latency_sink(&mut self, sink: crate::Arc<dyn crate::LatencySink>) {
+        self.consumer.tracer.set(sink);
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+
+    fn recv_until_disconnected(&mut self) -> Option<T> {
+        self.wait(Self::is_disconnected)
+            .then(|| self.consumer.pop())
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 unsafe impl<T: Send> Send for Receiver<T> {}
@@ -86,6 +216,10 @@ unsafe impl<T> BatchReader for Receiver<T> {
         self.consumer.read_buffer()
     }
 
+    fn read_slices(&mut self) -> (&[T], &[T]) {
+        self.consumer.read_slices()
+    }
+
     unsafe fn advance(&mut self, n: usize) {
         unsafe { self.consumer.advance(n) };
     }
@@ -0,0 +1,189 @@
+//! Variable-length byte message ring (bip-buffer) on the SPSC core.
+//!
+//! The sender reserves a *contiguous* region of `len` bytes with
+//! [`Sender::grant`], fills it in place, and commits it as one message. The
+//! receiver gets each whole message back as a `&[u8]` with [`Receiver::read`],
+//! straight out of the ring. Nothing is copied on either side beyond the bytes
+//! the sender writes itself.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use core::num::NonZeroUsize;
+//! use gil::spsc::bip::channel;
+//!
+//! let (mut tx, mut rx) = channel(NonZeroUsize::new(1024).unwrap());
+//!
+//! thread::spawn(move || {
+//!     for i in 0..100u8 {
+//!         let mut grant = tx.grant(i as usize % 7 + 1);
+//!         grant.fill(i);
+//!         grant.commit();
+//!     }
+//! });
+//!
+//! for i in 0..100u8 {
+//!     let message = rx.read();
+//!     assert_eq!(message.len(), i as usize % 7 + 1);
+//!     assert!(message.iter().all(|b| *b == i));
+//! }
+//! ```
+//!
+//! # Layout
+//!
+//! Each message is stored as a native-endian `u32` length header followed by the
+//! payload, padded to a multiple of 4 bytes so every header is aligned and never
+//! straddles the end of the buffer. When a message does not fit before the end,
+//! the sender writes a wrap marker in place of a header and skips to the start,
+//! exactly like a bip-buffer.
+//!
+//! # Gotchas
+//!
+//! - **Message Size Limit:** A message can be at most
+//!   [`Sender::max_message_len`] bytes: half the buffer minus the header. Beyond
+//!   that the skip at the wrap point could leave it never fitting. Asking for a
+//!   larger grant panics.
+//! - **Capacity Rounding:** The byte capacity is rounded up to the next power of
+//!   two, with a minimum of 16.
+//! - **Spinning:** The blocking [`Sender::grant`] and [`Receiver::read`] spin; use
+//!   [`Sender::try_grant`] and [`Receiver::try_read`] to build your own wait loop.
+
+use core::num::NonZeroUsize;
+
+pub use self::{
+    receiver::{ReadGrant, Receiver},
+    sender::{Sender, WriteGrant},
+};
+
+mod receiver;
+mod sender;
+
+/// Size of the length header in front of each message.
+const HEADER: usize = size_of::<u32>();
+/// Header value marking the rest of the buffer as skipped.
+const WRAP: u32 = u32::MAX;
+
+/// Bytes a message of `len` bytes takes up in the ring, header included.
+#[inline(always)]
+const fn record_len(len: usize) -> usize {
+    HEADER + len.next_multiple_of(HEADER)
+}
+
+/// Creates a new bip-buffer byte channel.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Arguments
+///
+/// * `capacity` - The size of the ring in bytes, rounded up to the next power of
+///   two, with a minimum of 16.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::spsc::bip::channel;
+///
+/// let (tx, rx) = channel(NonZeroUsize::new(4096).unwrap());
+/// assert_eq!(tx.max_message_len(), 2044);
+/// ```
+pub fn channel(capacity: NonZeroUsize) -> (Sender, Receiver) {
+    // `size == capacity` keeps the usable bytes equal to the buffer, so the wrap
+    // point is always the physical end of the buffer
+    let capacity = capacity
+        .max(NonZeroUsize::new(4 * HEADER).unwrap())
+        .checked_next_power_of_two()
+        .unwrap();
+    let queue = crate::spsc::queue::QueuePtr::<u8>::with_size(capacity);
+    // SAFETY: the buffer is `capacity` bytes; grants hand out `&mut [u8]` into it,
+    // so it must never be read uninitialised.
+    unsafe { queue.exact_at(0).write_bytes(0, capacity.get()) };
+
+    (Sender::new(queue.clone()), Receiver::new(queue))
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn test_wrap() {
+        let (mut tx, mut rx) = channel(NonZeroUsize::new(32).unwrap());
+        assert_eq!(tx.max_message_len(), 12);
+
+        for round in 0..100u8 {
+            let len = round as usize % 12 + 1;
+            let mut grant = tx.try_grant(len).unwrap();
+            grant.fill(round);
+            grant.commit();
+
+            let message = rx.try_read().unwrap();
+            assert_eq!(&*message, &[round; 12][..len]);
+            drop(message);
+            assert!(rx.try_read().is_none());
+        }
+    }
+
+    #[test]
+    fn test_commit_shorter() {
+        let (mut tx, mut rx) = channel(NonZeroUsize::new(64).unwrap());
+
+        let mut grant = tx.grant(16);
+        grant[..3].copy_from_slice(b"abc");
+        grant.commit_len(3);
+
+        // an abandoned grant publishes nothing
+        let _ = tx.grant(8);
+
+        assert_eq!(&*rx.read(), b"abc");
+        assert!(rx.try_read().is_none());
+    }
+
+    #[test]
+    fn test_full() {
+        let (mut tx, mut rx) = channel(NonZeroUsize::new(16).unwrap());
+
+        tx.grant(4).commit();
+        tx.grant(4).commit();
+        assert!(tx.try_grant(1).is_none());
+
+        drop(rx.read());
+        assert!(tx.try_grant(4).is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_oversized_grant() {
+        let (mut tx, _rx) = channel(NonZeroUsize::new(64).unwrap());
+        tx.try_grant(tx.max_message_len() + 1);
+    }
+
+    #[test]
+    fn test_threaded() {
+        const ITER: usize = 100_000;
+
+        let (mut tx, mut rx) = channel(NonZeroUsize::new(256).unwrap());
+
+        thread::spawn(move || {
+            for i in 0..ITER {
+                let len = i % tx.max_message_len() + 1;
+                let mut grant = tx.grant(len);
+                for (j, byte) in grant.iter_mut().enumerate() {
+                    *byte = (i + j) as u8;
+                }
+                grant.commit();
+            }
+        });
+
+        for i in 0..ITER {
+            let message = rx.read();
+            assert_eq!(message.len(), i % 124 + 1);
+            for (j, byte) in message.iter().enumerate() {
+                assert_eq!(*byte, (i + j) as u8);
+            }
+        }
+    }
+}
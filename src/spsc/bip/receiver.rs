@@ -0,0 +1,146 @@
+use core::{ops::Deref, ptr::NonNull};
+
+use crate::ring::{Consumer, Ring};
+
+use super::{HEADER, WRAP, record_len};
+
+/// The consumer end of the bip-buffer channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+pub struct Receiver {
+    consumer: Consumer<Ring<u8>>,
+}
+
+impl Receiver {
+    pub(crate) fn new(ring: Ring<u8>) -> Self {
+        Self {
+            consumer: Consumer::attach(ring),
+        }
+    }
+
+    /// Attempts to read the next message without blocking.
+    ///
+    /// Returns `None` if no message has been committed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::bip::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(64).unwrap());
+    /// assert!(rx.try_read().is_none());
+    ///
+    /// tx.grant(3).copy_from_slice(b"abc");
+    /// // the grant above was dropped without committing
+    /// assert!(rx.try_read().is_none());
+    ///
+    /// let mut grant = tx.grant(3);
+    /// grant.copy_from_slice(b"abc");
+    /// grant.commit();
+    /// assert_eq!(&*rx.try_read().unwrap(), b"abc");
+    /// ```
+    pub fn try_read(&mut self) -> Option<ReadGrant<'_>> {
+        if !self.skip_to_message() {
+            return None;
+        }
+        Some(self.read_ready())
+    }
+
+    /// Reads the next message, spinning until one is committed.
+    ///
+    /// The message is released back to the sender when the returned guard drops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::bip::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(64).unwrap());
+    ///
+    /// thread::spawn(move || {
+    ///     let mut grant = tx.grant(5);
+    ///     grant.copy_from_slice(b"hello");
+    ///     grant.commit();
+    /// });
+    ///
+    /// assert_eq!(&*rx.read(), b"hello");
+    /// ```
+    pub fn read(&mut self) -> ReadGrant<'_> {
+        let mut backoff = crate::Backoff::with_spin_count(128);
+        while !self.skip_to_message() {
+            backoff.backoff();
+        }
+        self.read_ready()
+    }
+
+    /// Returns `true` if a message is at the head, first stepping over a wrap
+    /// marker if there is one.
+    fn skip_to_message(&mut self) -> bool {
+        let buffer = self.consumer.read_buffer();
+        if buffer.is_empty() {
+            return false;
+        }
+
+        if read_header(buffer) != WRAP {
+            return true;
+        }
+
+        // the sender committed the skipped tail in one go, so `buffer` covers
+        // exactly the bytes up to the end
+        let skip = buffer.len();
+        unsafe { self.consumer.advance(skip) };
+        !self.consumer.read_buffer().is_empty()
+    }
+
+    fn read_ready(&mut self) -> ReadGrant<'_> {
+        let buffer = self.consumer.read_buffer();
+        let len = read_header(buffer) as usize;
+        let payload = NonNull::from(&buffer[HEADER..HEADER + len]).cast::<u8>();
+
+        ReadGrant {
+            consumer: &mut self.consumer,
+            payload,
+            len,
+        }
+    }
+}
+
+unsafe impl Send for Receiver {}
+
+#[inline(always)]
+fn read_header(buffer: &[u8]) -> u32 {
+    // every committed record starts with a whole header
+    u32::from_ne_bytes(buffer[..HEADER].try_into().unwrap())
+}
+
+/// A committed message, borrowed straight from the ring.
+///
+/// Dereferences to the message bytes. The space is released back to the sender
+/// when the guard is dropped.
+pub struct ReadGrant<'a> {
+    consumer: &'a mut Consumer<Ring<u8>>,
+    payload: NonNull<u8>,
+    len: usize,
+}
+
+impl Deref for ReadGrant<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the record stays in the ring, untouched by the sender, until
+        // this guard advances past it on drop.
+        unsafe { core::slice::from_raw_parts(self.payload.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for ReadGrant<'_> {
+    fn drop(&mut self) {
+        // SAFETY: the whole record was committed at once, so it is all within
+        // the readable window.
+        unsafe { self.consumer.advance(record_len(self.len)) };
+    }
+}
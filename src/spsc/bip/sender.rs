@@ -0,0 +1,211 @@
+use core::{
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use crate::ring::{Producer, Ring};
+
+use super::{HEADER, WRAP, record_len};
+
+/// The producer end of the bip-buffer channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+pub struct Sender {
+    producer: Producer<Ring<u8>>,
+    max_message_len: usize,
+}
+
+impl Sender {
+    pub(crate) fn new(ring: Ring<u8>) -> Self {
+        Self {
+            // the header also has to tell lengths apart from the wrap marker
+            max_message_len: (ring.capacity / 2 - HEADER).min(WRAP as usize - 1),
+            producer: Producer::attach(ring),
+        }
+    }
+
+    /// The largest message a grant can hold: half the buffer minus the header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::bip::channel;
+    ///
+    /// let (tx, _rx) = channel(NonZeroUsize::new(1024).unwrap());
+    /// assert_eq!(tx.max_message_len(), 508);
+    /// ```
+    #[inline]
+    pub fn max_message_len(&self) -> usize {
+        self.max_message_len
+    }
+
+    /// Attempts to reserve a contiguous region of `len` bytes without blocking.
+    ///
+    /// Returns `None` if the ring does not have room for it yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds [`max_message_len`](Sender::max_message_len).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::bip::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(16).unwrap());
+    ///
+    /// tx.try_grant(4).unwrap().commit();
+    /// tx.try_grant(4).unwrap().commit();
+    /// assert!(tx.try_grant(4).is_none());
+    /// ```
+    pub fn try_grant(&mut self, len: usize) -> Option<WriteGrant<'_>> {
+        if !self.reserve(len) {
+            return None;
+        }
+        Some(self.grant_reserved(len))
+    }
+
+    /// Reserves a contiguous region of `len` bytes, spinning until there is room.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds [`max_message_len`](Sender::max_message_len).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::bip::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(64).unwrap());
+    ///
+    /// let mut grant = tx.grant(5);
+    /// grant.copy_from_slice(b"hello");
+    /// grant.commit();
+    ///
+    /// assert_eq!(&*rx.read(), b"hello");
+    /// ```
+    pub fn grant(&mut self, len: usize) -> WriteGrant<'_> {
+        let mut backoff = crate::Backoff::with_spin_count(128);
+        while !self.reserve(len) {
+            backoff.backoff();
+        }
+        self.grant_reserved(len)
+    }
+
+    /// Makes sure a record for `len` bytes fits contiguously at the tail,
+    /// publishing a wrap marker first if it has to skip to the start.
+    fn reserve(&mut self, len: usize) -> bool {
+        assert!(
+            len <= self.max_message_len,
+            "message of {len} bytes exceeds the maximum of {}",
+            self.max_message_len
+        );
+
+        let record = record_len(len);
+        let contiguous = self.producer.contiguous();
+        let skip = if record > contiguous { contiguous } else { 0 };
+
+        if self.producer.free_slots(skip + record) < skip + record {
+            return false;
+        }
+
+        if skip > 0 {
+            let buffer = self.producer.write_buffer();
+            // SAFETY: `skip` bytes are free and contiguous, and headers are
+            // aligned, so at least `HEADER` of them are left before the end.
+            unsafe {
+                buffer.as_mut_ptr().cast::<u32>().write_unaligned(WRAP);
+                self.producer.commit(skip);
+            }
+        }
+
+        true
+    }
+
+    fn grant_reserved(&mut self, len: usize) -> WriteGrant<'_> {
+        let record = NonNull::from(self.producer.write_buffer()).cast::<u8>();
+        WriteGrant {
+            producer: &mut self.producer,
+            record,
+            len,
+        }
+    }
+}
+
+unsafe impl Send for Sender {}
+
+/// A reserved, contiguous region of the ring, writable in place.
+///
+/// Dereferences to the `len` bytes requested from [`Sender::grant`]. Nothing is
+/// published until [`commit`](WriteGrant::commit) or
+/// [`commit_len`](WriteGrant::commit_len); dropping the grant abandons it.
+pub struct WriteGrant<'a> {
+    producer: &'a mut Producer<Ring<u8>>,
+    /// Start of the record, where the header goes.
+    record: NonNull<u8>,
+    len: usize,
+}
+
+impl WriteGrant<'_> {
+    /// Publishes the whole grant as one message.
+    pub fn commit(self) {
+        let len = self.len;
+        self.commit_len(len);
+    }
+
+    /// Publishes the first `len` bytes of the grant as one message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds the size of the grant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::bip::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(64).unwrap());
+    ///
+    /// let mut grant = tx.grant(16);
+    /// grant[..2].copy_from_slice(b"hi");
+    /// grant.commit_len(2);
+    ///
+    /// assert_eq!(&*rx.read(), b"hi");
+    /// ```
+    pub fn commit_len(self, len: usize) {
+        assert!(
+            len <= self.len,
+            "committing {len} bytes of a {}-byte grant",
+            self.len
+        );
+
+        // SAFETY: `reserve` made room for the whole record at `record`, which
+        // only this grant writes until it is committed.
+        unsafe {
+            self.record.cast::<u32>().write_unaligned(len as u32);
+            self.producer.commit(record_len(len));
+        }
+    }
+}
+
+impl Deref for WriteGrant<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: see `commit_len`; the buffer is zeroed at construction, so the
+        // bytes are always initialised.
+        unsafe { core::slice::from_raw_parts(self.record.add(HEADER).as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for WriteGrant<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: see `deref`.
+        unsafe { core::slice::from_raw_parts_mut(self.record.add(HEADER).as_ptr(), self.len) }
+    }
+}
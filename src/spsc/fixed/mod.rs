@@ -0,0 +1,218 @@
+//! Heap-free single-producer single-consumer (SPSC) queue over inline storage.
+//!
+//! [`StaticSpsc<T, N>`](StaticSpsc) holds the head/tail indices and `N` slots
+//! inline, so it can live in a `static`, on the stack, or inside another
+//! structure. It runs the same ring algorithm as [`spsc::channel`](crate::spsc),
+//! and builds without the `alloc` feature, for firmware with no heap.
+//!
+//! # Examples
+//!
+//! A `static` queue, split once through a shared reference:
+//!
+//! ```
+//! use std::thread;
+//! use gil::spsc::fixed::StaticSpsc;
+//!
+//! static QUEUE: StaticSpsc<u32, 64> = StaticSpsc::new();
+//!
+//! let (mut tx, mut rx) = QUEUE.try_split().unwrap();
+//! assert!(QUEUE.try_split().is_none());
+//!
+//! thread::spawn(move || {
+//!     for i in 0..100 {
+//!         tx.send(i);
+//!     }
+//! });
+//!
+//! for i in 0..100 {
+//!     assert_eq!(rx.recv(), i);
+//! }
+//! ```
+//!
+//! A local queue, split through an exclusive borrow:
+//!
+//! ```
+//! use std::thread;
+//! use gil::spsc::fixed::StaticSpsc;
+//!
+//! let mut queue = StaticSpsc::<u32, 16>::new();
+//! let (mut tx, mut rx) = queue.split();
+//!
+//! thread::scope(|s| {
+//!     s.spawn(move || tx.send(1));
+//!     assert_eq!(rx.recv(), 1);
+//! });
+//! ```
+//!
+//! # Performance
+//!
+//! Same as the heap-allocated SPSC queue: the cursors cache the opposite index
+//! and the two indices sit on separate cache lines. The handles carry a plain
+//! reference instead of a refcounted pointer, so creating and dropping them
+//! costs nothing.
+//!
+//! # Gotchas
+//!
+//! - **Power-of-two capacity:** `N` must be a power of two, checked at compile
+//!   time, and the queue holds exactly `N` items.
+//! - **Borrowed handles:** [`Sender`] and [`Receiver`] borrow the storage, so a
+//!   queue on the stack can only be shared with scoped threads. Use a `static`
+//!   to hand the ends to long-lived threads or interrupt handlers.
+//! - **One split per static:** [`StaticSpsc::try_split`] succeeds once. It needs
+//!   an atomic swap, so targets without one (e.g. `thumbv6m`) must use
+//!   [`StaticSpsc::split`] on a `&mut` instead.
+//! - **No async or parking:** the handles only spin. Busy-wait with
+//!   [`try_send`](Sender::try_send)/[`try_recv`](Receiver::try_recv) to fit your
+//!   own idle strategy (e.g. `wfi`).
+
+pub use self::{queue::StaticSpsc, receiver::Receiver, sender::Sender};
+
+mod queue;
+mod receiver;
+mod sender;
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{read_guard::BatchReader, thread};
+
+    #[test]
+    fn basic() {
+        const COUNT: usize = 10_000;
+        let mut queue = StaticSpsc::<usize, 64>::new();
+        let (mut tx, mut rx) = queue.split();
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..COUNT {
+                    tx.send(i);
+                }
+            });
+
+            for i in 0..COUNT {
+                assert_eq!(rx.recv(), i);
+            }
+        });
+    }
+
+    #[test]
+    fn exact_capacity() {
+        let mut queue = StaticSpsc::<usize, 4>::new();
+        let (mut tx, mut rx) = queue.split();
+
+        for i in 0..4 {
+            tx.try_send(i).unwrap();
+        }
+        assert_eq!(tx.try_send(4), Err(4));
+
+        for round in 0..3 {
+            assert_eq!(rx.try_recv(), Some(round));
+            tx.try_send(round + 4).unwrap();
+        }
+    }
+
+    #[test]
+    fn static_split_once() {
+        static QUEUE: StaticSpsc<usize, 8> = StaticSpsc::new();
+
+        let (mut tx, mut rx) = QUEUE.try_split().unwrap();
+        assert!(QUEUE.try_split().is_none());
+
+        let handle = thread::spawn(move || {
+            for i in 0..100 {
+                tx.send(i);
+            }
+        });
+        for i in 0..100 {
+            assert_eq!(rx.recv(), i);
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn resplit_resumes() {
+        let mut queue = StaticSpsc::<usize, 4>::new();
+        {
+            let (mut tx, _rx) = queue.split();
+            tx.send(1);
+            tx.send(2);
+        }
+
+        let (mut tx, mut rx) = queue.split();
+        tx.send(3);
+        assert_eq!(rx.recv(), 1);
+        assert_eq!(rx.recv(), 2);
+        assert_eq!(rx.recv(), 3);
+    }
+
+    #[test]
+    fn batch_wraps() {
+        let mut queue = StaticSpsc::<usize, 8>::new();
+        let (mut tx, mut rx) = queue.split();
+
+        for i in 0..6 {
+            tx.send(i);
+        }
+        for i in 0..6 {
+            assert_eq!(rx.recv(), i);
+        }
+
+        // tail sits at 6, so the first write buffer stops at the end of storage
+        let buf = tx.write_buffer();
+        assert_eq!(buf.len(), 2);
+        buf[0].write(10);
+        buf[1].write(11);
+        unsafe { tx.commit(2) };
+        tx.send(12);
+
+        assert_eq!(rx.read_buffer(), &[10, 11]);
+        unsafe { rx.advance(2) };
+        assert_eq!(rx.try_recv(), Some(12));
+    }
+
+    #[test]
+    fn drops_in_flight() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted;
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut queue = StaticSpsc::<Counted, 4>::new();
+        {
+            let (mut tx, mut rx) = queue.split();
+            for _ in 0..4 {
+                tx.send(Counted);
+            }
+            drop(rx.recv());
+            tx.send(Counted);
+        }
+        assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+
+        drop(queue);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    fn iter_ends_when_sender_drops() {
+        let mut queue = StaticSpsc::<usize, 4>::new();
+        let (mut tx, mut rx) = queue.split();
+        assert!(!rx.is_disconnected());
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..100 {
+                    tx.send(i);
+                }
+            });
+
+            assert!(rx.iter().eq(0..100));
+        });
+        assert!(rx.is_disconnected());
+    }
+}
@@ -0,0 +1,161 @@
+use core::{cell::UnsafeCell, mem::MaybeUninit, ptr::NonNull};
+
+#[cfg(target_has_atomic = "8")]
+use crate::atomic::AtomicBool;
+use crate::{
+    atomic::{AtomicUsize, Ordering},
+    padded::Padded,
+    ring::RingPtr,
+};
+
+use super::{Receiver, Sender};
+
+/// Inline storage for an SPSC queue of `N` items, for use without a heap.
+///
+/// `N` must be a power of two. The queue is usually placed in a `static` and
+/// split once with [`try_split`](Self::try_split), or kept on the stack and
+/// split with [`split`](Self::split). The handles borrow the storage, so it has
+/// to outlive them.
+///
+/// See the [module-level documentation](super) for an example.
+#[repr(C)]
+pub struct StaticSpsc<T, const N: usize> {
+    head: Padded<AtomicUsize>,
+    tail: Padded<AtomicUsize>,
+    /// `1` while a [`Sender`] is attached; only stored, so it works without
+    /// atomic read-modify-writes.
+    sender: AtomicUsize,
+    #[cfg(target_has_atomic = "8")]
+    split: AtomicBool,
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+}
+
+// SAFETY: the ring protocol hands each slot to exactly one side at a time, and
+// the handles that touch the slots move items of `T` between threads.
+unsafe impl<T: Send, const N: usize> Sync for StaticSpsc<T, N> {}
+
+impl<T, const N: usize> StaticSpsc<T, N> {
+    /// Creates an empty queue. Usable in `const` and `static` initialisers.
+    ///
+    /// Fails to compile if `N` is not a power of two.
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                N.is_power_of_two(),
+                "StaticSpsc capacity must be a power of two"
+            )
+        };
+        Self {
+            head: Padded::new(AtomicUsize::new(0)),
+            tail: Padded::new(AtomicUsize::new(0)),
+            sender: AtomicUsize::new(0),
+            #[cfg(target_has_atomic = "8")]
+            split: AtomicBool::new(false),
+            buffer: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+        }
+    }
+
+    /// Borrows the queue as a [`Sender`]/[`Receiver`] pair.
+    ///
+    /// The exclusive borrow guarantees no other pair exists. Splitting again
+    /// after both handles drop resumes with the items still queued.
+    pub fn split(&mut self) -> (Sender<'_, T, N>, Receiver<'_, T, N>) {
+        let this = &*self;
+        (Sender::new(this), Receiver::new(this))
+    }
+
+    /// Like [`split`](Self::split), through a shared reference, so a `static`
+    /// queue can be split without `static mut`.
+    ///
+    /// Succeeds only once per queue; later calls return `None`. Needs an atomic
+    /// swap, so it is unavailable on targets without one (e.g. `thumbv6m`).
+    #[cfg(target_has_atomic = "8")]
+    pub fn try_split(&self) -> Option<(Sender<'_, T, N>, Receiver<'_, T, N>)> {
+        if self.split.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        Some((Sender::new(self), Receiver::new(self)))
+    }
+}
+
+impl<T, const N: usize> Default for StaticSpsc<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for StaticSpsc<T, N> {
+    fn drop(&mut self) {
+        if !core::mem::needs_drop::<T>() {
+            return;
+        }
+
+        let head = *self.head.value.get_mut();
+        let tail = *self.tail.value.get_mut();
+        for i in 0..tail.wrapping_sub(head) {
+            let idx = head.wrapping_add(i) & (N - 1);
+            // SAFETY: `head..tail` holds the items sent but not yet received.
+            unsafe { self.buffer[idx].get_mut().assume_init_drop() };
+        }
+    }
+}
+
+impl<T, const N: usize> RingPtr for &StaticSpsc<T, N> {
+    type Item = T;
+
+    #[inline(always)]
+    fn head(&self) -> &AtomicUsize {
+        &self.head.value
+    }
+
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        &self.tail.value
+    }
+
+    #[inline(always)]
+    fn size(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    fn mask(&self) -> usize {
+        N - 1
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn attach_sender(&self) {
+        self.sender.store(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn detach_sender(&self) {
+        self.sender.store(0, Ordering::Release);
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        self.sender.load(Ordering::Acquire) == 0
+    }
+
+    #[inline(always)]
+    unsafe fn exact_at(&self, index: usize) -> NonNull<T> {
+        // SAFETY: `index < N`, and the cell pointer is never null.
+        unsafe { NonNull::new_unchecked(self.buffer.get_unchecked(index).get().cast()) }
+    }
+
+    #[inline(always)]
+    unsafe fn get(&self, index: usize) -> T {
+        unsafe { self.exact_at(index & (N - 1)).read() }
+    }
+
+    #[inline(always)]
+    unsafe fn set(&self, index: usize, value: T) {
+        unsafe { self.exact_at(index & (N - 1)).write(value) }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{read_guard::BatchReader, ring::Consumer};
+
+use super::StaticSpsc;
+
+/// The consumer end of a [`StaticSpsc`].
+///
+/// Borrows the queue storage. `Send` when `T` is.
+///
+/// # Examples
+///
+/// ```
+/// use gil::spsc::fixed::StaticSpsc;
+///
+/// let mut queue = StaticSpsc::<i32, 16>::new();
+/// let (mut tx, mut rx) = queue.split();
+/// tx.send(7);
+/// assert_eq!(rx.try_recv(), Some(7));
+/// assert_eq!(rx.try_recv(), None);
+/// ```
+pub struct Receiver<'a, T, const N: usize> {
+    consumer: Consumer<&'a StaticSpsc<T, N>>,
+}
+
+impl<'a, T, const N: usize> Receiver<'a, T, N> {
+    pub(crate) fn new(queue: &'a StaticSpsc<T, N>) -> Self {
+        Self {
+            consumer: Consumer::attach(queue),
+        }
+    }
+
+    /// Attempts to receive a value without blocking.
+    ///
+    /// Returns `None` if the queue is empty.
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.consumer.try_pop()
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// the sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, ending once the
+    /// queue is empty. Reads through the batch path, so the head is published
+    /// once per run of items rather than per item. See
+    /// [`Drain`](crate::iter::Drain).
+    pub fn try_iter(&mut self) -> crate::iter::Drain<'_, Self> {
+        crate::iter::Drain::new(self)
+    }
+
+    /// Receives a value, spinning while the queue is empty.
+    ///
+    /// Uses a spin count of 128; see [`recv_with_spin_count`](Self::recv_with_spin_count).
+    pub fn recv(&mut self) -> T {
+        self.recv_with_spin_count(128)
+    }
+
+    /// Receives a value, spinning while the queue is empty, with a custom spin
+    /// count between yields.
+    pub fn recv_with_spin_count(&mut self, spin_count: u32) -> T {
+        let mut backoff = crate::Backoff::with_spin_count(spin_count);
+        while self.consumer.is_empty() {
+            backoff.backoff();
+            self.consumer.refresh_tail();
+        }
+        self.consumer.pop()
+    }
+
+    /// Returns a guard over the available items, advancing past the consumed
+    /// ones in one store when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::spsc::fixed::StaticSpsc;
+    ///
+    /// let mut queue = StaticSpsc::<usize, 8>::new();
+    /// let (mut tx, mut rx) = queue.split();
+    /// for i in 0..3 {
+    ///     tx.send(i);
+    /// }
+    ///
+    /// let mut guard = rx.read_guard();
+    /// assert_eq!(guard.as_slice(), &[0, 1, 2]);
+    /// guard.advance(guard.len());
+    /// drop(guard);
+    ///
+    /// assert_eq!(rx.try_recv(), None);
+    /// ```
+    pub fn read_guard(&mut self) -> crate::read_guard::ReadGuard<'_, Self> {
+        crate::read_guard::ReadGuard::new(self)
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// The sender may be filling it concurrently, so this is a snapshot and an
+    /// lower bound.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.consumer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once the [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.consumer.is_disconnected()
+    }
+
+    /// The number of items the queue holds when full, `N`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.consumer.size()
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.consumer.stats.snapshot()
+    }
+}
+
+impl<'a, T, const N: usize> crate::iter::Receive for Receiver<'a, T, N> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for Receiver<'a, T, N> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, 'b, T, const N: usize> IntoIterator for &'b mut Receiver<'a, T, N> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'b, Receiver<'a, T, N>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// # Safety
+///
+/// Delegates to the ring cursor: `read_buffer` returns the contiguous
+/// initialised window after the head, and `advance` publishes the new head with
+/// a `Release` store.
+unsafe impl<T, const N: usize> BatchReader for Receiver<'_, T, N> {
+    type Item = T;
+
+    #[inline]
+    fn read_buffer(&mut self) -> &[T] {
+        self.consumer.read_buffer()
+    }
+
+    #[inline]
+    fn read_slices(&mut self) -> (&[T], &[T]) {
+        self.consumer.read_slices()
+    }
+
+    #[inline(always)]
+    unsafe fn advance(&mut self, n: usize) {
+        unsafe { self.consumer.advance(n) };
+    }
+}
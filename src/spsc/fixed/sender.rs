@@ -0,0 +1,249 @@
+use core::mem::MaybeUninit;
+
+use crate::ring::Producer;
+
+use super::StaticSpsc;
+
+/// The producer end of a [`StaticSpsc`].
+///
+/// Borrows the queue storage. `Send` when `T` is, so it can be handed to another
+/// thread (or an interrupt handler) for as long as the storage lives.
+///
+/// # Examples
+///
+/// ```
+/// use gil::spsc::fixed::StaticSpsc;
+///
+/// let mut queue = StaticSpsc::<i32, 16>::new();
+/// let (mut tx, mut rx) = queue.split();
+/// tx.send(1);
+/// tx.send(2);
+/// assert_eq!(rx.recv(), 1);
+/// assert_eq!(rx.recv(), 2);
+/// ```
+pub struct Sender<'a, T, const N: usize> {
+    producer: Producer<&'a StaticSpsc<T, N>>,
+}
+
+impl<'a, T, const N: usize> Sender<'a, T, N> {
+    pub(crate) fn new(queue: &'a StaticSpsc<T, N>) -> Self {
+        Self {
+            producer: Producer::attach(queue),
+        }
+    }
+
+    /// Attempts to send a value without blocking.
+    ///
+    /// Returns `Err(value)` if the queue is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::spsc::fixed::StaticSpsc;
+    ///
+    /// let mut queue = StaticSpsc::<i32, 2>::new();
+    /// let (mut tx, mut rx) = queue.split();
+    ///
+    /// assert!(tx.try_send(1).is_ok());
+    /// assert!(tx.try_send(2).is_ok());
+    /// assert_eq!(tx.try_send(3), Err(3));
+    ///
+    /// assert_eq!(rx.try_recv(), Some(1));
+    /// assert!(tx.try_send(3).is_ok());
+    /// ```
+    pub fn try_send(&mut self, value: T) -> Result<(), T> {
+        self.producer.try_push(value)
+    }
+
+    /// Sends a value, spinning while the queue is full.
+    ///
+    /// Uses a spin count of 128; see [`send_with_spin_count`](Self::send_with_spin_count).
+    pub fn send(&mut self, value: T) {
+        self.send_with_spin_count(value, 128);
+    }
+
+    /// Sends a value, spinning while the queue is full, with a custom spin
+    /// count between yields.
+    pub fn send_with_spin_count(&mut self, value: T, spin_count: u32) {
+        self.wait_for_space(spin_count);
+        self.producer.push(value);
+    }
+
+    /// Constructs the next item directly in its slot, spinning while the queue
+    /// is full.
+    ///
+    /// `f` is called once a slot is free. The item is published when `f`
+    /// returns; if `f` panics, nothing is sent.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        self.wait_for_space(128);
+        unsafe { self.producer.push_with(f) };
+    }
+
+    /// Non-blocking [`send_with`](Self::send_with). Returns `Err(f)` without
+    /// calling it if the queue is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.producer.try_push_with(f)
+    }
+
+    /// Returns the contiguous free slots after the tail, for zero-copy batch
+    /// writes. Publish them with [`commit`](Self::commit).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::spsc::fixed::StaticSpsc;
+    ///
+    /// let mut queue = StaticSpsc::<usize, 8>::new();
+    /// let (mut tx, mut rx) = queue.split();
+    ///
+    /// let buf = tx.write_buffer();
+    /// buf[0].write(10);
+    /// buf[1].write(20);
+    /// unsafe { tx.commit(2) };
+    ///
+    /// assert_eq!(rx.recv(), 10);
+    /// assert_eq!(rx.recv(), 20);
+    /// ```
+    pub fn write_buffer(&mut self) -> &mut [MaybeUninit<T>] {
+        self.producer.write_buffer()
+    }
+
+    /// Like [`write_buffer`](Self::write_buffer), plus the free slots that wrap
+    /// to the start of the ring buffer. Fill the first slice first; one
+    /// [`commit`](Self::commit) may cover both.
+    pub fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        self.producer.write_slices()
+    }
+
+    /// Makes `len` items written via [`write_buffer`](Self::write_buffer)
+    /// visible to the receiver.
+    ///
+    /// # Safety
+    ///
+    /// `len` must not exceed the length of the most recent `write_buffer` slice
+    /// (or `write_slices` pair), and all `len` slots must be initialised.
+    #[inline(always)]
+    pub unsafe fn commit(&mut self, len: usize) {
+        unsafe { self.producer.commit(len) };
+    }
+
+    /// Returns a [`WriteGuard`](crate::write_guard::WriteGuard) over the free
+    /// slots of the queue: a safe alternative to
+    /// [`write_buffer`](Self::write_buffer) and [`commit`](Self::commit).
+    ///
+    /// The guard commits exactly the items written through it when dropped.
+    pub fn write_guard(&mut self) -> crate::write_guard::WriteGuard<'_, Self> {
+        crate::write_guard::WriteGuard::new(self)
+    }
+
+    fn wait_for_space(&mut self, spin_count: u32) {
+        if self.producer.has_space() {
+            return;
+        }
+
+        let mut backoff = crate::Backoff::with_spin_count(spin_count);
+        while self.producer.is_full() {
+            backoff.backoff();
+            self.producer.refresh_head();
+        }
+    }
+
+    /// Sends every item of `values`, blocking while the queue is full.
+    ///
+    /// Copies straight into the ring and publishes each contiguous run of free
+    /// slots at once, rather than item by item.
+    pub fn send_slice(&mut self, mut values: &[T])
+    where
+        T: Copy,
+    {
+        loop {
+            let sent = self.producer.push_slice(values);
+            values = &values[sent..];
+            if values.is_empty() {
+                return;
+            }
+            self.wait_for_space(128);
+        }
+    }
+
+    /// Sends items from `iter` until it runs dry or the queue fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// Only items there is room for are taken from `iter`; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let (Ok(sent) | Err(sent)) = self.producer.push_iter(&mut iter);
+        sent
+    }
+
+    /// Sends every item of `iter`, blocking while the queue is full.
+    ///
+    /// Like [`send_slice`](Self::send_slice), items are published a run of
+    /// free slots at a time.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        let mut iter = iter.into_iter().peekable();
+        loop {
+            let pushed = self.producer.push_iter(&mut iter);
+            if pushed.is_ok() || iter.peek().is_none() {
+                return;
+            }
+            self.wait_for_space(128);
+        }
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// The receiver may be draining it concurrently, so this is a snapshot and an
+    /// upper bound.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.producer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full, `N`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.producer.size()
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.producer.stats.snapshot()
+    }
+}
+
+unsafe impl<T, const N: usize> crate::write_guard::BatchWriter for Sender<'_, T, N> {
+    type Item = T;
+
+    fn write_buffer(&mut self) -> &mut [MaybeUninit<T>] {
+        Sender::write_buffer(self)
+    }
+
+    fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        Sender::write_slices(self)
+    }
+
+    unsafe fn commit(&mut self, n: usize) {
+        unsafe { Sender::commit(self, n) }
+    }
+}
@@ -72,15 +72,33 @@
 //!
 //! * [Facebook Folly ProducerConsumerQueue](https://github.com/facebook/folly/blob/main/folly/ProducerConsumerQueue.h)
 
+#[cfg(feature = "alloc")]
 use core::num::NonZeroUsize;
 
+#[cfg(feature = "alloc")]
+use crate::{Allocator, Global};
+
+#[cfg(feature = "alloc")]
 pub use self::{receiver::Receiver, sender::Sender};
 
+#[cfg(feature = "alloc")]
+pub mod bip;
+#[cfg(not(feature = "loom"))]
+pub mod fixed;
+#[cfg(feature = "alloc")]
+pub mod overwriting;
 #[cfg(feature = "std")]
 pub mod parking;
+#[cfg(feature = "alloc")]
+pub mod pool;
+#[cfg(feature = "alloc")]
 pub(crate) mod queue;
+#[cfg(feature = "alloc")]
 mod receiver;
+#[cfg(feature = "alloc")]
 mod sender;
+#[cfg(all(feature = "shm", target_os = "linux", not(feature = "loom")))]
+pub mod shm;
 
 /// Creates a new single-producer single-consumer (SPSC) queue.
 ///
@@ -105,12 +123,78 @@ mod sender;
 ///
 /// let (tx, rx) = channel::<usize>(NonZeroUsize::new(1024).unwrap());
 /// ```
+#[cfg(feature = "alloc")]
 pub fn channel<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
-    let queue = queue::QueuePtr::with_size(capacity);
+    channel_in(capacity, Global)
+}
+
+/// Like [`channel`], but allocates the queue from `allocator`.
+///
+/// The allocator moves into the queue and frees it once both handles drop.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, spsc::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(NonZeroUsize::new(1024).unwrap(), Global);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn channel_in<T, A: Allocator + Send + 'static>(
+    capacity: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    let queue = queue::QueuePtr::with_header_in(
+        capacity,
+        queue::Head::traced(capacity),
+        queue::Tail::default(),
+        allocator,
+    );
     (Sender::new(queue.clone()), Receiver::new(queue))
 }
 
-#[cfg(all(test, not(feature = "loom")))]
+/// Like [`channel`], but with the buffer mapped twice back-to-back in virtual
+/// memory, so [`Sender::write_buffer`] and [`Receiver::read_buffer`] always
+/// return every free slot or every available item as one slice, and the second
+/// slice of `write_slices`/`read_slices` is always empty.
+///
+/// Needs the buffer (`capacity` rounded up to a power of two, times
+/// `size_of::<T>()`) to be a whole number of pages. Otherwise, or if the
+/// mapping fails, this builds the same queue as [`channel`].
+///
+/// Requires the `mirror` feature; Linux only.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::spsc::channel_mirrored;
+///
+/// let (mut tx, mut rx) = channel_mirrored::<u8>(NonZeroUsize::new(1 << 16).unwrap());
+/// tx.send_slice(&[0; 60_000]);
+/// rx.read_guard().advance(60_000);
+///
+/// // the free space wraps the end of the buffer, but comes back whole
+/// let (free, wrapped) = tx.write_slices();
+/// assert_eq!((free.len(), wrapped.len()), (1 << 16, 0));
+/// ```
+#[cfg(all(feature = "mirror", target_os = "linux", not(feature = "loom")))]
+pub fn channel_mirrored<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
+    let head = queue::Head::traced(capacity);
+    let tail = queue::Tail::default();
+    let queue = match queue::QueuePtr::with_header_mirrored(capacity, head, tail) {
+        Some(queue) => queue,
+        None => queue::QueuePtr::with_header(
+            capacity,
+            queue::Head::traced(capacity),
+            queue::Tail::default(),
+        ),
+    };
+    (Sender::new(queue.clone()), Receiver::new(queue))
+}
+
+#[cfg(all(test, feature = "alloc", not(feature = "loom")))]
 mod test {
     use std::num::NonZeroUsize;
 
@@ -246,6 +330,349 @@ mod test {
         // All 5 items should have been dropped
         assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 5);
     }
+
+    #[test]
+    fn test_send_with() {
+        let (mut tx, mut rx) = channel::<[u64; 512]>(NonZeroUsize::new(2).unwrap());
+
+        for i in 0..2 {
+            assert!(tx.try_send_with(|slot| slot.write([i; 512])).is_ok());
+        }
+        let mut called = false;
+        let full = tx.try_send_with(|slot| {
+            called = true;
+            slot.write([9; 512])
+        });
+        assert!(full.is_err());
+        assert!(!called);
+
+        assert_eq!(rx.recv(), [0; 512]);
+        tx.send_with(|slot| slot.write([2; 512]));
+        assert_eq!(rx.recv(), [1; 512]);
+        assert_eq!(rx.recv(), [2; 512]);
+    }
+
+    #[test]
+    fn test_send_with_panic_sends_nothing() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tx.send_with(|_| panic!("boom"));
+        }));
+        assert!(result.is_err());
+        assert!(rx.try_recv().is_none());
+
+        // handing back some other initialised value doesn't count
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tx.send_with(|_| std::boxed::Box::leak(std::boxed::Box::new(7)));
+        }));
+        assert!(result.is_err());
+        assert!(rx.try_recv().is_none());
+
+        tx.send(1);
+        assert_eq!(rx.recv(), 1);
+    }
+
+    #[test]
+    fn test_peek_and_recv_if() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(2).unwrap());
+        assert_eq!(rx.peek(), None);
+        assert_eq!(rx.recv_if(|_| true), None);
+
+        // walk the head across the wrap
+        for i in 0..5 {
+            tx.send(i);
+            assert_eq!(rx.peek(), Some(&i));
+            assert_eq!(rx.recv_if(|&v| v != i), None);
+            assert_eq!(rx.recv_if(|&v| v == i), Some(i));
+        }
+
+        tx.send(7);
+        *rx.peek_mut().unwrap() = 8;
+        assert_eq!(rx.peek(), Some(&8));
+        assert_eq!(rx.recv(), 8);
+        assert_eq!(rx.peek_mut(), None);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        assert_eq!(rx.try_iter().next(), None);
+
+        // the drain reads the run up to the wrap, then the rest
+        tx.send_slice(&[0, 1, 2]);
+        assert_eq!(rx.recv(), 0);
+        tx.send_slice(&[3, 4]);
+        assert!(rx.try_iter().eq(1..5));
+
+        // a dropped drain leaves the unread items in place
+        tx.send_slice(&[5, 6, 7]);
+        assert_eq!(rx.try_iter().next(), Some(5));
+        assert_eq!(rx.len(), 2);
+
+        assert!((&mut rx).into_iter().take(2).eq(6..8));
+        tx.send_slice(&[8, 9]);
+        let mut sum = 0;
+        for item in &mut rx {
+            sum += item;
+            if sum > 8 {
+                break;
+            }
+        }
+        assert_eq!(sum, 17);
+        tx.send(10);
+        let mut iter = rx.into_iter();
+        assert_eq!(iter.next(), Some(10));
+        assert!(iter.into_inner().is_empty());
+    }
+
+    #[test]
+    fn test_len() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(3).unwrap());
+        assert!(tx.is_empty() && rx.is_empty());
+        assert_eq!((tx.capacity(), rx.capacity()), (3, 3));
+
+        for i in 0..3 {
+            tx.send(i);
+            assert_eq!((tx.len(), rx.len()), (i + 1, i + 1));
+        }
+        assert!(tx.is_full() && rx.is_full());
+
+        rx.recv();
+        assert_eq!((tx.len(), rx.len()), (2, 2));
+        assert!(!tx.is_full());
+    }
+
+    #[test]
+    fn test_send_slice_wraps() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        tx.send_slice(&[0, 1, 2]);
+        assert_eq!(rx.recv(), 0);
+        assert_eq!(rx.recv(), 1);
+
+        // tail at 3: one slot before the end of the buffer, two after the wrap
+        tx.send_slice(&[3, 4, 5]);
+        assert!(tx.is_full());
+        for i in 2..6 {
+            assert_eq!(rx.recv(), i);
+        }
+    }
+
+    #[test]
+    fn test_send_all_blocks_until_drained() {
+        const COUNT: usize = 10_000;
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(16).unwrap());
+
+        thread::spawn(move || tx.send_all(0..COUNT));
+        for i in 0..COUNT {
+            assert_eq!(rx.recv(), i);
+        }
+    }
+
+    #[test]
+    fn test_try_send_iter_panic_publishes_yielded() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(8).unwrap());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tx.try_send_iter((0..5).map(|i| if i < 3 { i } else { panic!("boom") }))
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(
+            (0..3).map(|_| rx.recv()).collect::<std::vec::Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn test_guards_span_the_wrap() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        tx.send_slice(&[0, 1, 2]);
+        rx.recv();
+        rx.recv();
+
+        // tail at 3: one slot before the wrap, two after it
+        let mut guard = tx.write_guard();
+        assert_eq!(guard.remaining(), 3);
+        assert_eq!(guard.write_slice(&[3, 4, 5, 6]), 3);
+        drop(guard);
+
+        let mut guard = rx.read_guard();
+        assert_eq!(guard.as_slices(), (&[2, 3][..], &[4, 5][..]));
+        assert_eq!((guard.len(), ExactSizeIterator::len(&guard)), (4, 4));
+        guard.advance(3);
+        assert_eq!(guard.as_slice(), [5]);
+        drop(guard);
+        assert_eq!(rx.recv(), 5);
+
+        tx.send_slice(&[6, 7, 8, 9]);
+        let mut out = std::vec::Vec::new();
+        assert_eq!(rx.read_guard().drain_into(&mut out), 4);
+        assert_eq!(out, [6, 7, 8, 9]);
+        assert_eq!(rx.try_recv(), None);
+
+        tx.send_slice(&[10, 11, 12]);
+        let mut out = [0; 4];
+        let mut guard = rx.read_guard();
+        assert_eq!(guard.as_slices(), (&[10, 11][..], &[12][..]));
+        // SAFETY: `out` has room for every item in the guard.
+        assert_eq!(unsafe { guard.drain_into_ptr(out.as_mut_ptr()) }, 3);
+        assert!(guard.is_empty());
+        drop(guard);
+        assert_eq!(out[..3], [10, 11, 12]);
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn test_write_guard_commits_what_was_written() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+
+        let mut guard = tx.write_guard();
+        assert_eq!(guard.remaining(), 4);
+        assert_eq!(guard.write_slice(&[0, 1]), 2);
+        assert_eq!(guard.extend(2..10), 2);
+        assert_eq!(guard.push(10), Err(10));
+        drop(guard);
+
+        // an empty guard commits nothing
+        drop(tx.write_guard());
+        assert_eq!(
+            (0..4).map(|_| rx.recv()).collect::<std::vec::Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(rx.try_recv(), None);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut guard = tx.write_guard();
+            guard.extend((4..8).map(|i| if i < 6 { i } else { panic!("boom") }));
+        }));
+        assert!(result.is_err());
+        assert_eq!((rx.recv(), rx.recv()), (4, 5));
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[cfg(all(feature = "mirror", target_os = "linux"))]
+    #[test]
+    fn test_mirrored_never_wraps() {
+        const CAPACITY: usize = 1 << 16;
+        let (mut tx, mut rx) = super::channel_mirrored::<u8>(NonZeroUsize::new(CAPACITY).unwrap());
+        tx.send_slice(&[0; CAPACITY - 10]);
+        rx.read_guard().advance(CAPACITY - 10);
+
+        // tail 10 slots before the end: the free run and the data both wrap
+        let values: std::vec::Vec<u8> = (0..100).collect();
+        let (free, wrapped) = tx.write_slices();
+        assert_eq!((free.len(), wrapped.len()), (CAPACITY, 0));
+        tx.send_slice(&values);
+
+        let guard = rx.read_guard();
+        assert_eq!(guard.as_slices(), (&values[..], &[][..]));
+    }
+
+    #[cfg(all(feature = "mirror", target_os = "linux"))]
+    #[test]
+    fn test_mirrored_threads() {
+        const COUNT: usize = 1 << 20;
+        let (mut tx, mut rx) =
+            super::channel_mirrored::<usize>(NonZeroUsize::new(1 << 12).unwrap());
+
+        thread::spawn(move || tx.send_all(0..COUNT));
+        let mut expected = 0;
+        while expected < COUNT {
+            let mut guard = rx.read_guard();
+            for &value in guard.as_slice() {
+                assert_eq!(value, expected);
+                expected += 1;
+            }
+            let n = guard.len();
+            guard.advance(n);
+        }
+    }
+
+    #[cfg(all(feature = "mirror", target_os = "linux"))]
+    #[test]
+    fn test_mirrored_falls_back() {
+        // three bytes are not a whole page
+        let (mut tx, mut rx) = super::channel_mirrored::<u8>(NonZeroUsize::new(3).unwrap());
+        tx.send_slice(&[1, 2, 3]);
+        rx.recv();
+        rx.recv();
+        tx.send_slice(&[4, 5]);
+        let guard = rx.read_guard();
+        assert_eq!(guard.as_slices(), (&[3, 4][..], &[5][..]));
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_stats() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(3).unwrap());
+        for i in 0..3 {
+            tx.send(i);
+        }
+        assert!(tx.try_send(3).is_err());
+        assert!(tx.try_send(3).is_err());
+        for _ in 0..3 {
+            rx.recv();
+        }
+
+        let sent = tx.stats();
+        assert_eq!((sent.sent, sent.received, sent.full), (3, 0, 2));
+        assert_eq!(sent.high_water, 3);
+
+        let received = rx.stats();
+        assert_eq!((received.sent, received.received, received.full), (0, 3, 0));
+        assert_eq!(received.high_water, 3);
+    }
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn test_stats_high_water_tracks_the_live_head() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(8).unwrap());
+        for i in 0..20 {
+            tx.send(i);
+            assert_eq!(rx.recv(), i);
+        }
+        assert_eq!(tx.stats().high_water, 1);
+        assert_eq!(rx.stats().high_water, 1);
+    }
+
+    #[cfg(feature = "latency")]
+    #[test]
+    fn test_latency_sink() {
+        use std::{sync::Mutex, time::Duration, vec::Vec};
+
+        #[derive(Default)]
+        struct Dwells(Mutex<Vec<Duration>>);
+
+        impl crate::LatencySink for Dwells {
+            fn record(&self, dwell: Duration) {
+                self.0.lock().unwrap().push(dwell);
+            }
+        }
+
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+        // sent before the sink goes in; still stamped, so still reported
+        tx.send(0);
+
+        let dwells = crate::Arc::new(Dwells::default());
+        rx.set_latency_sink(dwells.clone());
+
+        tx.send(1);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(rx.recv(), 0);
+        assert_eq!(rx.recv(), 1);
+
+        // the read-guard path reports too
+        tx.send(2);
+        tx.send(3);
+        let mut guard = rx.read_guard();
+        guard.advance(guard.len());
+        drop(guard);
+
+        let dwells = dwells.0.lock().unwrap();
+        assert_eq!(dwells.len(), 4);
+        assert!(dwells[..2].iter().all(|&d| d >= Duration::from_millis(5)));
+    }
 }
 
 #[cfg(all(test, feature = "loom"))]
@@ -326,7 +753,7 @@ mod loom_test {
             while received < total {
                 let mut guard = rx.read_guard();
                 if !guard.is_empty() {
-                    let count = guard.len();
+                    let count = guard.as_slice().len();
                     for (i, item) in guard.as_slice().iter().enumerate() {
                         assert_eq!(*item, received + i);
                     }
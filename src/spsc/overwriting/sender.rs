@@ -0,0 +1,54 @@
+use crate::overwrite::{Producer, QueuePtr};
+
+/// The producer end of the overwriting SPSC queue.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another thread, but cannot
+/// be shared across threads.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::spsc::overwriting::channel;
+///
+/// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+/// tx.send(1);
+/// tx.send(2);
+/// assert_eq!(rx.recv(), 1);
+/// assert_eq!(rx.recv(), 2);
+/// ```
+pub struct Sender<T> {
+    producer: Producer<T>,
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(queue_ptr: QueuePtr<T>) -> Self {
+        Self {
+            producer: Producer::new(queue_ptr),
+        }
+    }
+
+    /// Sends a value into the queue without ever blocking.
+    ///
+    /// If the queue is full, the oldest unread item is evicted to make room and
+    /// returned as `Some(evicted)`. Returns `None` if there was free space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::overwriting::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(2).unwrap());
+    ///
+    /// assert_eq!(tx.send(1), None);
+    /// assert_eq!(tx.send(2), None);
+    /// assert_eq!(tx.send(3), Some(1));
+    /// assert_eq!(rx.recv(), 2);
+    /// ```
+    pub fn send(&mut self, value: T) -> Option<T> {
+        self.producer.push(value)
+    }
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
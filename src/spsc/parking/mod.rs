@@ -56,6 +56,8 @@
 
 use core::num::NonZeroUsize;
 
+use crate::{Allocator, Global};
+
 pub use self::{receiver::Receiver, sender::Sender};
 
 mod receiver;
@@ -85,7 +87,50 @@ mod sender;
 /// let (tx, rx) = channel::<usize>(NonZeroUsize::new(1024).unwrap());
 /// ```
 pub fn channel<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
-    let queue = super::queue::QueuePtr::with_size(capacity);
+    channel_in(capacity, Global)
+}
+
+/// Like [`channel`], but allocates the queue from `allocator`.
+///
+/// The allocator moves into the queue and frees it once both handles drop.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::{Global, spsc::parking::channel_in};
+///
+/// let (tx, rx) = channel_in::<usize, _>(NonZeroUsize::new(1024).unwrap(), Global);
+/// ```
+pub fn channel_in<T, A: Allocator + Send + 'static>(
+    capacity: NonZeroUsize,
+    allocator: A,
+) -> (Sender<T>, Receiver<T>) {
+    let queue = super::queue::QueuePtr::with_header_in(
+        capacity,
+        super::queue::Head::traced(capacity),
+        super::queue::Tail::default(),
+        allocator,
+    );
+    (Sender::new(queue.clone()), Receiver::new(queue))
+}
+
+/// Like [`channel`], with the buffer mapped twice so it never wraps. See
+/// [`spsc::channel_mirrored`](crate::spsc::channel_mirrored).
+///
+/// Requires the `mirror` feature; Linux only.
+#[cfg(all(feature = "mirror", target_os = "linux", not(feature = "loom")))]
+pub fn channel_mirrored<T>(capacity: NonZeroUsize) -> (Sender<T>, Receiver<T>) {
+    let head = super::queue::Head::traced(capacity);
+    let tail = super::queue::Tail::default();
+    let queue = match super::queue::QueuePtr::with_header_mirrored(capacity, head, tail) {
+        Some(queue) => queue,
+        None => super::queue::QueuePtr::with_header(
+            capacity,
+            super::queue::Head::traced(capacity),
+            super::queue::Tail::default(),
+        ),
+    };
     (Sender::new(queue.clone()), Receiver::new(queue))
 }
 
@@ -202,6 +247,24 @@ mod test {
 
         assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 5);
     }
+
+    #[test]
+    fn iter_ends_when_sender_drops() {
+        let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 0..100 {
+                    tx.send(i);
+                }
+                // let the receiver park before the disconnect has to wake it
+                thread::sleep(core::time::Duration::from_millis(20));
+            });
+
+            assert!(rx.iter().eq(0..100));
+        });
+        assert!(rx.is_disconnected());
+    }
 }
 
 #[cfg(all(test, feature = "loom"))]
@@ -282,7 +345,7 @@ mod loom_test {
             while received < total {
                 let mut guard = rx.read_guard();
                 if !guard.is_empty() {
-                    let count = guard.len();
+                    let count = guard.as_slice().len();
                     for (i, item) in guard.as_slice().iter().enumerate() {
                         assert_eq!(*item, received + i);
                     }
@@ -52,10 +52,27 @@ impl<T> Receiver<T> {
     /// ```
     pub fn try_recv(&mut self) -> Option<T> {
         let value = self.consumer.try_pop()?;
-        self.consumer.ring().futex().wake();
+        self.consumer
+            .stats
+            .wake(self.consumer.ring().futex().wake());
         Some(value)
     }
 
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// the sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, ending once the
+    /// queue is empty. Reads through the batch path, so the head is published
+    /// once per run of items rather than per item. See
+    /// [`Drain`](crate::iter::Drain).
+    pub fn try_iter(&mut self) -> crate::iter::Drain<'_, Self> {
+        crate::iter::Drain::new(self)
+    }
+
     /// Receives a value from the queue, blocking if necessary.
     ///
     /// This method spins briefly, then yields, and finally parks the thread via
@@ -77,23 +94,45 @@ impl<T> Receiver<T> {
         // Wait for data, then move the value straight out of the ring. We don't
         // route the value through `try_pop` here: its `Option<T>` would add a
         // copy of the value on the hot path for large payloads.
+        self.wait(|_| false);
+        self.take()
+    }
+
+    /// Waits until the ring holds an item (`true`), or until `give_up` returns
+    /// `true` with the ring still empty (`false`).
+    #[inline]
+    fn wait(&mut self, give_up: impl Fn(&Self) -> bool) -> bool {
         let mut backoff = crate::ParkingBackoff::new(16, 4);
         while self.consumer.is_empty() {
+            if give_up(self) {
+                // the sender may have published just before giving up
+                self.consumer.refresh_tail();
+                return !self.consumer.is_empty();
+            }
             if backoff.backoff() {
                 let futex = self.consumer.ring().futex();
                 if futex.announce(RECEIVER_WAITING) {
                     // catch lost wakes: recheck against a fresh tail before parking
                     self.consumer.refresh_tail();
-                    if self.consumer.is_empty() {
+                    if self.consumer.is_empty() && !give_up(self) {
+                        self.consumer.stats.park();
                         futex.sleep(RECEIVER_WAITING);
                     }
                 }
             }
             self.consumer.refresh_tail();
         }
+        true
+    }
+
+    /// Pops the item [`wait`](Self::wait) found and wakes a parked sender.
+    #[inline]
+    fn take(&mut self) -> T {
         let value = self.consumer.pop();
 
-        self.consumer.ring().futex().wake();
+        self.consumer
+            .stats
+            .wake(self.consumer.ring().futex().wake());
 
         value
     }
@@ -127,6 +166,164 @@ impl<T> Receiver<T> {
     pub fn read_guard(&mut self) -> crate::read_guard::ReadGuard<'_, Self> {
         crate::read_guard::ReadGuard::new(self)
     }
+
+    /// Returns a reference to the next item without receiving it, or `None` if
+    /// the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::parking::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// assert_eq!(rx.peek(), None);
+    ///
+    /// tx.send(42);
+    /// assert_eq!(rx.peek(), Some(&42));
+    /// assert_eq!(rx.recv(), 42);
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: the head item stays put until this receiver advances, which
+        // the shared borrow rules out.
+        self.consumer.peek().map(|ptr| unsafe { ptr.as_ref() })
+    }
+
+    /// Like [`peek`](Self::peek), but lets the next item be modified in place
+    /// before it is received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::parking::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(1);
+    ///
+    /// *rx.peek_mut().unwrap() += 1;
+    /// assert_eq!(rx.recv(), 2);
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `peek`, and the sender never touches an unreceived item.
+        self.consumer
+            .peek_mut()
+            .map(|mut ptr| unsafe { ptr.as_mut() })
+    }
+
+    /// Receives the next item only if `predicate` accepts it, without blocking.
+    ///
+    /// Returns `None` if the queue is empty or `predicate` returns `false`; in
+    /// the latter case the item stays at the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::parking::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(-1);
+    ///
+    /// assert_eq!(rx.recv_if(|&v| v > 0), None);
+    /// assert_eq!(rx.recv_if(|&v| v < 0), Some(-1));
+    /// ```
+    pub fn recv_if(&mut self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if !predicate(self.peek_mut()?) {
+            return None;
+        }
+        self.try_recv()
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// The sender may be filling it concurrently, so this is a snapshot and an
+    /// lower bound.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.consumer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once the [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.consumer.is_disconnected()
+    }
+
+    /// The number of items the queue holds when full: exactly the requested capacity.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.consumer.size()
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.consumer.stats.snapshot()
+    }
+
+    /// Reports how long each item spent in the queue to `sink`, from the
+    /// moment it was published to the moment it is received here.
+    ///
+    /// Replaces any sink installed earlier. See [`LatencySink`](crate::LatencySink).
+    #[cfg(feature = "latency")]
+    pub fn set_latency_sink(&mut self, sink: crate::Arc<dyn crate::LatencySink>) {
+        self.consumer.tracer.set(sink);
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+
+    fn recv_until_disconnected(&mut self) -> Option<T> {
+        self.wait(Self::is_disconnected).then(|| self.take())
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 unsafe impl<T: Send> Send for Receiver<T> {}
@@ -178,6 +375,11 @@ unsafe impl<T> BatchReader for Receiver<T> {
         self.consumer.read_buffer()
     }
 
+    #[inline]
+    fn read_slices(&mut self) -> (&[T], &[T]) {
+        self.consumer.read_slices()
+    }
+
     /// Advances the consumer head by `n` items.
     ///
     /// This marks items previously obtained via [`read_buffer`](BatchReader::read_buffer)
@@ -212,6 +414,8 @@ unsafe impl<T> BatchReader for Receiver<T> {
     #[inline(always)]
     unsafe fn advance(&mut self, n: usize) {
         unsafe { self.consumer.advance(n) };
-        self.consumer.ring().futex().wake();
+        self.consumer
+            .stats
+            .wake(self.consumer.ring().futex().wake());
     }
 }
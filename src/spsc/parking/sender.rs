@@ -58,7 +58,9 @@ impl<T> Sender<T> {
     /// ```
     pub fn try_send(&mut self, value: T) -> Result<(), T> {
         self.producer.try_push(value)?;
-        self.producer.ring().futex().wake();
+        self.producer
+            .stats
+            .wake(self.producer.ring().futex().wake());
         Ok(())
     }
 
@@ -83,6 +85,67 @@ impl<T> Sender<T> {
         // Wait for space, then move the value straight into the ring. We don't
         // route the value through `try_push` here: its `Result<(), T>` would add
         // a copy of `value` on the hot path for large payloads.
+        self.wait_for_space();
+        self.producer.push(value);
+
+        self.producer
+            .stats
+            .wake(self.producer.ring().futex().wake());
+    }
+
+    /// Constructs the next item directly in its queue slot, blocking (and
+    /// eventually parking) while the queue is full.
+    ///
+    /// `f` is called once a slot is free and gets the uninitialised slot, which
+    /// saves [`send`](Sender::send)'s stack-to-ring copy for large `T`. The item
+    /// is published when `f` returns; if `f` panics, nothing is sent.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::parking::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<[u8; 4096]>(NonZeroUsize::new(4).unwrap());
+    /// // SAFETY: the bytes cover the whole array.
+    /// tx.send_with(|slot| unsafe {
+    ///     slot.as_mut_ptr().write_bytes(7, 1);
+    ///     slot.assume_init_mut()
+    /// });
+    /// assert!(rx.recv().iter().all(|&b| b == 7));
+    /// ```
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        self.wait_for_space();
+        unsafe { self.producer.push_with(f) };
+
+        self.producer
+            .stats
+            .wake(self.producer.ring().futex().wake());
+    }
+
+    /// Non-blocking [`send_with`](Sender::send_with).
+    ///
+    /// Returns `Err(f)` without calling it if the queue is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.producer.try_push_with(f)?;
+        self.producer
+            .stats
+            .wake(self.producer.ring().futex().wake());
+        Ok(())
+    }
+
+    fn wait_for_space(&mut self) {
+        if self.producer.has_space() {
+            return;
+        }
+
         let mut backoff = crate::ParkingBackoff::new(16, 4);
         while self.producer.is_full() {
             if backoff.backoff() {
@@ -91,15 +154,13 @@ impl<T> Sender<T> {
                     // catch lost wakes: recheck against a fresh head before parking
                     self.producer.refresh_head();
                     if self.producer.is_full() {
+                        self.producer.stats.park();
                         futex.sleep(SENDER_WAITING);
                     }
                 }
             }
             self.producer.refresh_head();
         }
-        self.producer.push(value);
-
-        self.producer.ring().futex().wake();
     }
 
     /// Returns a mutable slice to the available write buffer in the queue.
@@ -143,6 +204,13 @@ impl<T> Sender<T> {
         self.producer.write_buffer()
     }
 
+    /// Like [`write_buffer`](Self::write_buffer), plus the free slots that wrap
+    /// to the start of the ring buffer. Fill the first slice first; one
+    /// [`commit`](Self::commit) may cover both.
+    pub fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        self.producer.write_slices()
+    }
+
     /// Commits items written to the buffer obtained via [`write_buffer`](Sender::write_buffer).
     ///
     /// This makes `len` items visible to the receiver.
@@ -150,8 +218,10 @@ impl<T> Sender<T> {
     /// # Safety
     ///
     /// * `len` must be less than or equal to the length of the slice returned by the
-    ///   most recent call to [`write_buffer`](Sender::write_buffer).
-    /// * All `len` items in the buffer must have been initialized before calling this.
+    ///   most recent call to [`write_buffer`](Sender::write_buffer), or of both slices
+    ///   returned by [`write_slices`](Sender::write_slices).
+    /// * All `len` items in the buffer must have been initialized before calling this,
+    ///   filling the first of the two slices before the second.
     ///
     /// # Examples
     ///
@@ -172,8 +242,139 @@ impl<T> Sender<T> {
     #[inline(always)]
     pub unsafe fn commit(&mut self, len: usize) {
         unsafe { self.producer.commit(len) };
-        self.producer.ring().futex().wake();
+        self.producer
+            .stats
+            .wake(self.producer.ring().futex().wake());
+    }
+
+    /// Returns a [`WriteGuard`](crate::write_guard::WriteGuard) over the free
+    /// slots of the queue: a safe alternative to
+    /// [`write_buffer`](Self::write_buffer) and [`commit`](Self::commit).
+    ///
+    /// The guard commits exactly the items written through it when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::parking::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(128).unwrap());
+    ///
+    /// let mut guard = tx.write_guard();
+    /// guard.push(1).unwrap();
+    /// guard.write_slice(&[2, 3]);
+    /// guard.extend(4..6);
+    /// drop(guard);
+    ///
+    /// for i in 1..6 {
+    ///     assert_eq!(rx.recv(), i);
+    /// }
+    /// ```
+    pub fn write_guard(&mut self) -> crate::write_guard::WriteGuard<'_, Self> {
+        crate::write_guard::WriteGuard::new(self)
+    }
+
+    /// Sends every item of `values`, blocking while the queue is full.
+    ///
+    /// Copies straight into the ring and publishes each contiguous run of free
+    /// slots at once, rather than item by item.
+    pub fn send_slice(&mut self, mut values: &[T])
+    where
+        T: Copy,
+    {
+        loop {
+            let sent = self.producer.push_slice(values);
+            values = &values[sent..];
+            self.producer
+                .stats
+                .wake(self.producer.ring().futex().wake());
+            if values.is_empty() {
+                return;
+            }
+            self.wait_for_space();
+        }
+    }
+
+    /// Sends items from `iter` until it runs dry or the queue fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// Only items there is room for are taken from `iter`; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let (Ok(sent) | Err(sent)) = self.producer.push_iter(&mut iter);
+        self.producer
+            .stats
+            .wake(self.producer.ring().futex().wake());
+        sent
+    }
+
+    /// Sends every item of `iter`, blocking while the queue is full.
+    ///
+    /// Like [`send_slice`](Self::send_slice), items are published a run of
+    /// free slots at a time.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        let mut iter = iter.into_iter().peekable();
+        loop {
+            let pushed = self.producer.push_iter(&mut iter);
+            self.producer
+                .stats
+                .wake(self.producer.ring().futex().wake());
+            if pushed.is_ok() || iter.peek().is_none() {
+                return;
+            }
+            self.wait_for_space();
+        }
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// The receiver may be draining it concurrently, so this is a snapshot and an
+    /// upper bound.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.producer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: exactly the requested capacity.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.producer.size()
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.producer.stats.snapshot()
     }
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
+
+unsafe impl<T> crate::write_guard::BatchWriter for Sender<T> {
+    type Item = T;
+
+    fn write_buffer(&mut self) -> &mut [MaybeUninit<T>] {
+        Sender::write_buffer(self)
+    }
+
+    fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        Sender::write_slices(self)
+    }
+
+    unsafe fn commit(&mut self, n: usize) {
+        unsafe { Sender::commit(self, n) }
+    }
+}
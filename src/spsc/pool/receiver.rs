@@ -0,0 +1,189 @@
+use core::{
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+
+use alloc_crate::boxed::Box;
+
+/// The consumer end of the pooled SPSC channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::spsc::pool::channel;
+///
+/// let (mut tx, mut rx) = channel(NonZeroUsize::new(4).unwrap(), || 0);
+/// *tx.acquire() = 1; // dropped unsent: back to the pool
+///
+/// let mut slot = tx.acquire();
+/// *slot = 2;
+/// slot.send();
+/// assert_eq!(*rx.recv(), 2);
+/// ```
+pub struct Receiver<T> {
+    forward: crate::spsc::Receiver<Box<T>>,
+    free: crate::mpsc::Sender<Box<T>>,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn new(
+        forward: crate::spsc::Receiver<Box<T>>,
+        free: crate::mpsc::Sender<Box<T>>,
+    ) -> Self {
+        Self { forward, free }
+    }
+
+    /// Receives an object, blocking if none has been sent.
+    ///
+    /// The object returns to the pool when the [`Pooled`] handle is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::pool::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(4).unwrap(), || [0u8; 64]);
+    ///
+    /// thread::spawn(move || {
+    ///     let mut slot = tx.acquire();
+    ///     slot[0] = 42;
+    ///     slot.send();
+    /// });
+    ///
+    /// assert_eq!(rx.recv()[0], 42);
+    /// ```
+    pub fn recv(&mut self) -> Pooled<T> {
+        let value = self.forward.recv();
+        Pooled::new(value, self.free.clone())
+    }
+
+    /// Attempts to receive an object without blocking.
+    ///
+    /// Returns `None` if nothing has been sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::pool::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(4).unwrap(), || 0);
+    /// assert!(rx.try_recv().is_none());
+    ///
+    /// tx.acquire().send();
+    /// assert!(rx.try_recv().is_some());
+    /// ```
+    pub fn try_recv(&mut self) -> Option<Pooled<T>> {
+        let value = self.forward.try_recv()?;
+        Some(Pooled::new(value, self.free.clone()))
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// the sender has been dropped. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Returns `true` once the [`Sender`](super::Sender) has been dropped.
+    /// Objects sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.forward.is_disconnected()
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = Pooled<T>;
+
+    #[inline]
+    fn recv(&mut self) -> Pooled<T> {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<Pooled<T>> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = Pooled<T>;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = Pooled<T>;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
+
+/// A pooled object handed to the [`Receiver`].
+///
+/// Returns to the pool when dropped, on whichever thread drops it. This struct
+/// is `Send`, so the object can be handed on before it is released.
+pub struct Pooled<T> {
+    value: ManuallyDrop<Box<T>>,
+    free: crate::mpsc::Sender<Box<T>>,
+}
+
+impl<T> Pooled<T> {
+    fn new(value: Box<T>, free: crate::mpsc::Sender<Box<T>>) -> Self {
+        Self {
+            value: ManuallyDrop::new(value),
+            free,
+        }
+    }
+}
+
+impl<T> Deref for Pooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Pooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> Drop for Pooled<T> {
+    fn drop(&mut self) {
+        // SAFETY: `value` is never touched again.
+        let value = unsafe { ManuallyDrop::take(&mut self.value) };
+        // the free list has room for the whole pool, so this never waits on
+        // the sender
+        self.free.send(value);
+    }
+}
+
+unsafe impl<T: Send> Send for Pooled<T> {}
@@ -0,0 +1,149 @@
+use core::ops::{Deref, DerefMut};
+
+use alloc_crate::boxed::Box;
+
+/// The producer end of the pooled SPSC channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::spsc::pool::channel;
+///
+/// let (mut tx, mut rx) = channel(NonZeroUsize::new(4).unwrap(), Vec::<u8>::new);
+///
+/// let mut slot = tx.acquire();
+/// slot.extend_from_slice(b"hello");
+/// slot.send();
+///
+/// assert_eq!(*rx.recv(), b"hello");
+/// ```
+pub struct Sender<T> {
+    forward: crate::spsc::Sender<Box<T>>,
+    free: crate::mpsc::Receiver<Box<T>>,
+    /// An object acquired but never sent, reused before the free list.
+    spare: Option<Box<T>>,
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(
+        forward: crate::spsc::Sender<Box<T>>,
+        free: crate::mpsc::Receiver<Box<T>>,
+    ) -> Self {
+        Self {
+            forward,
+            free,
+            spare: None,
+        }
+    }
+
+    /// Takes an object from the pool, blocking until one is returned if the pool
+    /// is exhausted.
+    ///
+    /// The object still holds whatever it held when it was last returned; clear
+    /// it if that matters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::pool::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(1).unwrap(), || 0);
+    ///
+    /// let mut slot = tx.acquire();
+    /// *slot = 1;
+    /// slot.send();
+    /// drop(rx.recv());
+    ///
+    /// // the same object, back from the receiver
+    /// assert_eq!(*tx.acquire(), 1);
+    /// ```
+    pub fn acquire(&mut self) -> Slot<'_, T> {
+        let value = match self.spare.take() {
+            Some(value) => value,
+            None => self.free.recv(),
+        };
+        Slot {
+            value: Some(value),
+            sender: self,
+        }
+    }
+
+    /// Attempts to take an object from the pool without blocking.
+    ///
+    /// Returns `None` if every object is in flight or held by the receiver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::pool::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(NonZeroUsize::new(1).unwrap(), || 0);
+    ///
+    /// tx.try_acquire().unwrap().send();
+    /// assert!(tx.try_acquire().is_none());
+    ///
+    /// drop(rx.recv());
+    /// assert!(tx.try_acquire().is_some());
+    /// ```
+    pub fn try_acquire(&mut self) -> Option<Slot<'_, T>> {
+        let value = match self.spare.take() {
+            Some(value) => value,
+            None => self.free.try_recv()?,
+        };
+        Some(Slot {
+            value: Some(value),
+            sender: self,
+        })
+    }
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
+
+/// A pooled object checked out by the [`Sender`], ready to be filled.
+///
+/// Call [`send`](Slot::send) to pass it to the receiver. Dropping it unsent puts
+/// it back in the pool.
+pub struct Slot<'a, T> {
+    // `Some` until sent
+    value: Option<Box<T>>,
+    sender: &'a mut Sender<T>,
+}
+
+impl<T> Slot<'_, T> {
+    /// Sends the object to the receiver.
+    ///
+    /// Never blocks: the channel has room for every object in the pool.
+    pub fn send(mut self) {
+        let value = self.value.take().unwrap();
+        // the ring holds exactly as many items as the pool, so this never waits
+        self.sender.forward.send(value);
+    }
+}
+
+impl<T> Deref for Slot<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_deref().unwrap()
+    }
+}
+
+impl<T> DerefMut for Slot<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_deref_mut().unwrap()
+    }
+}
+
+impl<T> Drop for Slot<'_, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.sender.spare = Some(value);
+        }
+    }
+}
@@ -10,13 +10,15 @@
 //!   Shards have no async API, so this layout carries only what shards use: the
 //!   indices and (under `std`) the futex the parking variants park on.
 
-use core::ptr::NonNull;
 #[cfg(feature = "async")]
 use core::task::Waker;
+use core::{num::NonZeroUsize, ptr::NonNull};
 
 #[cfg(feature = "async")]
 use futures::task::AtomicWaker;
 
+#[cfg(feature = "latency")]
+use crate::latency::Stamps;
 #[cfg(feature = "std")]
 use crate::{atomic::AtomicU32, futex::HasFutex};
 use crate::{
@@ -34,6 +36,9 @@ pub struct Head {
     futex: Padded<AtomicU32>,
     #[cfg(feature = "async")]
     receiver_waker: Padded<AtomicWaker>,
+    /// Read by both cursors, written by neither after construction.
+    #[cfg(feature = "latency")]
+    stamps: Option<Stamps>,
 }
 
 #[derive(Default)]
@@ -50,6 +55,8 @@ pub struct ShardHead {
     head: Padded<AtomicUsize>,
     #[cfg(feature = "std")]
     futex: Padded<AtomicU32>,
+    #[cfg(feature = "latency")]
+    stamps: Option<Stamps>,
 }
 
 #[derive(Default)]
@@ -58,11 +65,48 @@ pub struct ShardTail {
     tail: Padded<AtomicUsize>,
 }
 
+impl Head {
+    /// The header for a traced ring of `size` slots: with `latency`, carries
+    /// the stamp array the plain [`Default`] header goes without.
+    #[cfg_attr(not(feature = "latency"), allow(unused_variables))]
+    pub(crate) fn traced(size: NonZeroUsize) -> Self {
+        Self {
+            #[cfg(feature = "latency")]
+            stamps: Some(Stamps::new(size.get().next_power_of_two())),
+            ..Self::default()
+        }
+    }
+}
+
+impl ShardHead {
+    /// See [`Head::traced`].
+    #[cfg_attr(not(feature = "latency"), allow(unused_variables))]
+    pub(crate) fn traced(size: NonZeroUsize) -> Self {
+        Self {
+            #[cfg(feature = "latency")]
+            stamps: Some(Stamps::new(size.get().next_power_of_two())),
+            ..Self::default()
+        }
+    }
+}
+
 impl RingHead for Head {
     #[inline(always)]
     fn head(&self) -> &AtomicUsize {
         &self.head.value
     }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn disconnected(&self) {
+        crate::futex::Futex::new(self.futex()).wake();
+    }
+
+    #[cfg(feature = "latency")]
+    #[inline(always)]
+    fn stamps(&self) -> Option<&Stamps> {
+        self.stamps.as_ref()
+    }
 }
 
 impl RingTail for Tail {
@@ -77,6 +121,18 @@ impl RingHead for ShardHead {
     fn head(&self) -> &AtomicUsize {
         &self.head.value
     }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn disconnected(&self) {
+        crate::futex::Futex::new(self.futex()).wake();
+    }
+
+    #[cfg(feature = "latency")]
+    #[inline(always)]
+    fn stamps(&self) -> Option<&Stamps> {
+        self.stamps.as_ref()
+    }
 }
 
 impl RingTail for ShardTail {
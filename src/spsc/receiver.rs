@@ -64,6 +64,49 @@ impl<T> Receiver<T> {
         Some(value)
     }
 
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop, ending once the queue is empty and
+    /// the sender has been dropped. See [`iter`](crate::iter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(1);
+    /// tx.send(2);
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.iter().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, ending once the
+    /// queue is empty. Reads through the batch path, so the head is published
+    /// once per run of items rather than per item. See
+    /// [`Drain`](crate::iter::Drain).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(1);
+    /// tx.send(2);
+    ///
+    /// assert_eq!(rx.try_iter().collect::<Vec<_>>(), [1, 2]);
+    /// assert_eq!(rx.try_recv(), None);
+    /// ```
+    pub fn try_iter(&mut self) -> crate::iter::Drain<'_, Self> {
+        crate::iter::Drain::new(self)
+    }
+
     /// Receives a value from the queue, blocking if necessary.
     ///
     /// This method uses a spin loop with a default spin count of 128 to wait
@@ -154,6 +197,74 @@ impl<T> Receiver<T> {
         crate::read_guard::ReadGuard::new(self)
     }
 
+    /// Returns a reference to the next item without receiving it, or `None` if
+    /// the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// assert_eq!(rx.peek(), None);
+    ///
+    /// tx.send(42);
+    /// assert_eq!(rx.peek(), Some(&42));
+    /// assert_eq!(rx.recv(), 42);
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: the head item stays put until this receiver advances, which
+        // the shared borrow rules out.
+        self.consumer.peek().map(|ptr| unsafe { ptr.as_ref() })
+    }
+
+    /// Like [`peek`](Self::peek), but lets the next item be modified in place
+    /// before it is received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(1);
+    ///
+    /// *rx.peek_mut().unwrap() += 1;
+    /// assert_eq!(rx.recv(), 2);
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `peek`, and the sender never touches an unreceived item.
+        self.consumer
+            .peek_mut()
+            .map(|mut ptr| unsafe { ptr.as_mut() })
+    }
+
+    /// Receives the next item only if `predicate` accepts it, without blocking.
+    ///
+    /// Returns `None` if the queue is empty or `predicate` returns `false`; in
+    /// the latter case the item stays at the front of the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<i32>(NonZeroUsize::new(16).unwrap());
+    /// tx.send(-1);
+    ///
+    /// assert_eq!(rx.recv_if(|&v| v > 0), None);
+    /// assert_eq!(rx.recv_if(|&v| v < 0), Some(-1));
+    /// ```
+    pub fn recv_if(&mut self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if !predicate(self.peek_mut()?) {
+            return None;
+        }
+        self.try_recv()
+    }
+
     /// Receives a value from the queue asynchronously.
     ///
     /// This method yields the current task if the queue is empty, and resumes
@@ -196,6 +307,105 @@ impl<T> Receiver<T> {
 
         Poll::Ready(value)
     }
+
+    /// Number of items in the queue.
+    ///
+    /// The sender may be filling it concurrently, so this is a snapshot and an
+    /// lower bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, rx) = channel::<i32>(NonZeroUsize::new(3).unwrap());
+    /// tx.send(1);
+    /// tx.send(2);
+    /// assert_eq!(rx.len(), 2);
+    /// assert_eq!(rx.capacity(), 3);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.consumer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns `true` once the [`Sender`](super::Sender) has been dropped.
+    /// Items sent before that may still be queued.
+    #[inline]
+    pub fn is_disconnected(&self) -> bool {
+        self.consumer.is_disconnected()
+    }
+
+    /// The number of items the queue holds when full: exactly the requested capacity.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.consumer.size()
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.consumer.stats.snapshot()
+    }
+
+    /// Reports how long each item spent in the queue to `sink`, from the
+    /// moment it was published to the moment it is received here.
+    ///
+    /// Replaces any sink installed earlier. See [`LatencySink`](crate::LatencySink).
+    #[cfg(feature = "latency")]
+    pub fn set_latency_sink(&mut self, sink: crate::Arc<dyn crate::LatencySink>) {
+        self.consumer.tracer.set(sink);
+    }
+}
+
+impl<T> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        Receiver::is_disconnected(self)
+    }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 unsafe impl<T: Send> Send for Receiver<T> {}
@@ -227,7 +437,8 @@ unsafe impl<T> BatchReader for Receiver<T> {
     ///
     /// The returned slice contains contiguous available items starting from the current head.
     /// It may not represent *all* available items if the buffer wraps around; call
-    /// `read_buffer` again after advancing to get the next contiguous chunk.
+    /// `read_buffer` again after advancing to get the next contiguous chunk, or use
+    /// [`read_slices`](BatchReader::read_slices) to get both at once.
     ///
     /// Items are returned by shared reference — ownership is **not** transferred.
     /// See [`BatchReader`](crate::read_guard::BatchReader#ownership) for details.
@@ -258,6 +469,35 @@ unsafe impl<T> BatchReader for Receiver<T> {
         self.consumer.read_buffer()
     }
 
+    /// Returns all available items as two slices: the run up to the end of the
+    /// ring buffer, then the items that wrapped to its start.
+    ///
+    /// A single [`advance`](BatchReader::advance) may cover both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    /// use gil::read_guard::BatchReader;
+    ///
+    /// let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+    ///
+    /// tx.send_slice(&[0, 1, 2]);
+    /// rx.recv();
+    /// rx.recv();
+    /// tx.send_slice(&[3, 4]);
+    ///
+    /// let (front, back) = rx.read_slices();
+    /// assert_eq!((front, back), (&[2, 3][..], &[4][..]));
+    /// unsafe { rx.advance(3) };
+    /// assert_eq!(rx.try_recv(), None);
+    /// ```
+    #[inline]
+    fn read_slices(&mut self) -> (&[T], &[T]) {
+        self.consumer.read_slices()
+    }
+
     /// Advances the consumer head by `n` items.
     ///
     /// This marks items previously obtained via [`read_buffer`](BatchReader::read_buffer)
@@ -265,8 +505,9 @@ unsafe impl<T> BatchReader for Receiver<T> {
     ///
     /// # Safety
     ///
-    /// * `n` must be less than or equal to the length of the slice returned by the
-    ///   most recent call to [`read_buffer`](BatchReader::read_buffer).
+    /// * `n` must be less than or equal to the number of items returned by the
+    ///   most recent call to [`read_buffer`](BatchReader::read_buffer) or
+    ///   [`read_slices`](BatchReader::read_slices).
     /// * Advancing past the available data results in undefined behavior.
     ///
     /// # Examples
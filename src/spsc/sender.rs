@@ -109,15 +109,95 @@ impl<T> Sender<T> {
         // Spin until there is space, then move the value straight into the ring.
         // We don't route the value through `try_push` here: its `Result<(), T>`
         // would add a copy of `value` on the hot path for large payloads.
+        self.wait_for_space(spin_count);
+        self.producer.push(value);
+
+        #[cfg(feature = "async")]
+        self.producer.ring().wake_receiver();
+    }
+
+    /// Constructs the next item directly in its queue slot, blocking while the
+    /// queue is full.
+    ///
+    /// `f` is called once a slot is free and gets the uninitialised slot, which
+    /// saves [`send`](Sender::send)'s stack-to-ring copy for large `T`. The item
+    /// is published when `f` returns; if `f` panics, nothing is sent.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<[u64; 512]>(NonZeroUsize::new(4).unwrap());
+    ///
+    /// tx.send_with(|slot| {
+    ///     let words = slot.as_mut_ptr().cast::<u64>();
+    ///     // SAFETY: the 512 writes initialise the whole array.
+    ///     unsafe {
+    ///         for i in 0..512 {
+    ///             words.add(i).write(i as u64);
+    ///         }
+    ///         slot.assume_init_mut()
+    ///     }
+    /// });
+    /// assert_eq!(rx.recv()[511], 511);
+    /// ```
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        self.wait_for_space(128);
+        unsafe { self.producer.push_with(f) };
+
+        #[cfg(feature = "async")]
+        self.producer.ring().wake_receiver();
+    }
+
+    /// Non-blocking [`send_with`](Sender::send_with).
+    ///
+    /// Returns `Err(f)` without calling it if the queue is full.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<u32>(NonZeroUsize::new(1).unwrap());
+    ///
+    /// assert!(tx.try_send_with(|slot| slot.write(1)).is_ok());
+    /// assert!(tx.try_send_with(|slot| slot.write(2)).is_err());
+    /// assert_eq!(rx.recv(), 1);
+    /// ```
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.producer.try_push_with(f)?;
+
+        #[cfg(feature = "async")]
+        self.producer.ring().wake_receiver();
+
+        Ok(())
+    }
+
+    fn wait_for_space(&mut self, spin_count: u32) {
+        // One full event per blocked send; `has_space` records it.
+        if self.producer.has_space() {
+            return;
+        }
+
         let mut backoff = crate::Backoff::with_spin_count(spin_count);
         while self.producer.is_full() {
             backoff.backoff();
             self.producer.refresh_head();
         }
-        self.producer.push(value);
-
-        #[cfg(feature = "async")]
-        self.producer.ring().wake_receiver();
     }
 
     /// Sends a value into the queue asynchronously.
@@ -141,7 +221,7 @@ impl<T> Sender<T> {
     pub async fn send_async(&mut self, value: T) {
         use core::task::Poll;
 
-        if self.producer.is_full() {
+        if !self.producer.has_space() {
             futures::future::poll_fn(|ctx| {
                 self.producer.refresh_head();
                 if self.producer.is_full() {
@@ -203,6 +283,35 @@ impl<T> Sender<T> {
         self.producer.write_buffer()
     }
 
+    /// Returns all free slots as two slices: the run from the tail to the end of
+    /// the ring buffer, then the slots that wrap to its start.
+    ///
+    /// Fill the first slice before the second; a single [`commit`](Sender::commit)
+    /// may then publish items from both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(4).unwrap());
+    /// tx.send_slice(&[0, 1, 2]);
+    /// rx.recv();
+    /// rx.recv();
+    ///
+    /// let (front, back) = tx.write_slices();
+    /// assert_eq!((front.len(), back.len()), (1, 2));
+    /// front[0].write(3);
+    /// back[0].write(4);
+    /// unsafe { tx.commit(2) };
+    ///
+    /// assert_eq!((rx.recv(), rx.recv(), rx.recv()), (2, 3, 4));
+    /// ```
+    pub fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        self.producer.write_slices()
+    }
+
     /// Commits items written to the buffer obtained via [`write_buffer`](Sender::write_buffer).
     ///
     /// This makes `len` items visible to the receiver.
@@ -210,8 +319,10 @@ impl<T> Sender<T> {
     /// # Safety
     ///
     /// * `len` must be less than or equal to the length of the slice returned by the
-    ///   most recent call to [`write_buffer`](Sender::write_buffer).
-    /// * All `len` items in the buffer must have been initialized before calling this.
+    ///   most recent call to [`write_buffer`](Sender::write_buffer), or of both slices
+    ///   returned by [`write_slices`](Sender::write_slices).
+    /// * All `len` items in the buffer must have been initialized before calling this,
+    ///   filling the first of the two slices before the second.
     ///
     /// # Examples
     ///
@@ -236,6 +347,159 @@ impl<T> Sender<T> {
         #[cfg(feature = "async")]
         self.producer.ring().wake_receiver();
     }
+
+    /// Returns a [`WriteGuard`](crate::write_guard::WriteGuard) over the free
+    /// slots of the queue: a safe alternative to
+    /// [`write_buffer`](Self::write_buffer) and [`commit`](Self::commit).
+    ///
+    /// The guard commits exactly the items written through it when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<usize>(NonZeroUsize::new(128).unwrap());
+    ///
+    /// let mut guard = tx.write_guard();
+    /// guard.push(1).unwrap();
+    /// guard.write_slice(&[2, 3]);
+    /// guard.extend(4..6);
+    /// drop(guard);
+    ///
+    /// for i in 1..6 {
+    ///     assert_eq!(rx.recv(), i);
+    /// }
+    /// ```
+    pub fn write_guard(&mut self) -> crate::write_guard::WriteGuard<'_, Self> {
+        crate::write_guard::WriteGuard::new(self)
+    }
+
+    /// Sends every item of `values`, blocking while the queue is full.
+    ///
+    /// Copies straight into the ring and publishes each contiguous run of free
+    /// slots at once, rather than item by item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, mut rx) = channel::<u32>(NonZeroUsize::new(4).unwrap());
+    /// tx.send_slice(&[1, 2, 3]);
+    ///
+    /// // one slot left: only `4` is taken from the range
+    /// assert_eq!(tx.try_send_iter(4..10), 1);
+    /// assert_eq!([rx.recv(), rx.recv(), rx.recv(), rx.recv()], [1, 2, 3, 4]);
+    /// ```
+    pub fn send_slice(&mut self, mut values: &[T])
+    where
+        T: Copy,
+    {
+        loop {
+            let sent = self.producer.push_slice(values);
+            values = &values[sent..];
+            #[cfg(feature = "async")]
+            self.producer.ring().wake_receiver();
+            if values.is_empty() {
+                return;
+            }
+            self.wait_for_space(128);
+        }
+    }
+
+    /// Sends items from `iter` until it runs dry or the queue fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// Only items there is room for are taken from `iter`; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let (Ok(sent) | Err(sent)) = self.producer.push_iter(&mut iter);
+        #[cfg(feature = "async")]
+        self.producer.ring().wake_receiver();
+        sent
+    }
+
+    /// Sends every item of `iter`, blocking while the queue is full.
+    ///
+    /// Like [`send_slice`](Self::send_slice), items are published a run of
+    /// free slots at a time.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        let mut iter = iter.into_iter().peekable();
+        loop {
+            let pushed = self.producer.push_iter(&mut iter);
+            #[cfg(feature = "async")]
+            self.producer.ring().wake_receiver();
+            if pushed.is_ok() || iter.peek().is_none() {
+                return;
+            }
+            self.wait_for_space(128);
+        }
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// The receiver may be draining it concurrently, so this is a snapshot and an
+    /// upper bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::num::NonZeroUsize;
+    /// use gil::spsc::channel;
+    ///
+    /// let (mut tx, _rx) = channel::<i32>(NonZeroUsize::new(3).unwrap());
+    /// tx.send(1);
+    /// assert_eq!(tx.len(), 1);
+    /// assert_eq!(tx.capacity(), 3);
+    /// assert!(!tx.is_full());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.producer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: exactly the requested capacity.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.producer.size()
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.producer.stats.snapshot()
+    }
 }
 
 unsafe impl<T: Send> Send for Sender<T> {}
+
+unsafe impl<T> crate::write_guard::BatchWriter for Sender<T> {
+    type Item = T;
+
+    fn write_buffer(&mut self) -> &mut [MaybeUninit<T>] {
+        Sender::write_buffer(self)
+    }
+
+    fn write_slices(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        Sender::write_slices(self)
+    }
+
+    unsafe fn commit(&mut self, n: usize) {
+        unsafe { Sender::commit(self, n) }
+    }
+}
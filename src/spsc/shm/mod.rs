@@ -0,0 +1,227 @@
+//! Inter-process SPSC queue over shared memory (Linux, `shm` feature).
+//!
+//! The queue's header and buffer live in a shared mapping of a file descriptor
+//! you supply: a `memfd` passed to a child process, an `shm_open` object (a file
+//! under `/dev/shm`), or a regular file. One process creates the queue with
+//! [`Sender::create`] or [`Receiver::create`]; the other attaches with
+//! [`Receiver::open`] or [`Sender::open`]. Blocked endpoints park on a shared
+//! futex, so a sender in one process wakes a receiver in another.
+//!
+//! The ring algorithm is the same as [`spsc::parking`](crate::spsc::parking);
+//! only where the memory comes from differs.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{fs::File, os::fd::FromRawFd, thread};
+//! use core::num::NonZeroUsize;
+//! use gil::spsc::shm::{Receiver, Sender};
+//!
+//! // a memfd, which a real program would pass to a child process
+//! let fd = unsafe { libc::memfd_create(c"gil-queue".as_ptr(), 0) };
+//! assert!(fd >= 0);
+//! let file = unsafe { File::from_raw_fd(fd) };
+//!
+//! let mut tx = Sender::<[u8; 32]>::create(&file, NonZeroUsize::new(64).unwrap()).unwrap();
+//!
+//! // stands in for the other process: it only needs the descriptor
+//! let child = thread::spawn(move || {
+//!     let mut rx = Receiver::<[u8; 32]>::open(&file).unwrap();
+//!     (0..100).map(|_| rx.recv()[0] as usize).sum::<usize>()
+//! });
+//!
+//! for i in 0..100u8 {
+//!     tx.send([i; 32]);
+//! }
+//! assert_eq!(child.join().unwrap(), 4950);
+//! ```
+//!
+//! # Layout
+//!
+//! The region starts with a versioned header recording a magic number, the
+//! layout version, the queue kind, the pointer width, the element size and
+//! alignment, and the capacity. [`open`](Receiver::open) rejects a region that
+//! disagrees with the opener on any of them, so mismatched builds fail loudly
+//! instead of misreading each other's data.
+//!
+//! # Gotchas
+//!
+//! - **Plain Data Only:** Items are copied bit-for-bit between address spaces,
+//!   hence `T: Copy`. Pointers and references inside `T` are meaningless to the
+//!   other process.
+//! - **Same Type on Both Sides:** The header catches size and alignment
+//!   mismatches, not two different types of the same shape.
+//! - **One Sender, One Receiver:** Attaching a second sender or receiver fails
+//!   with [`ResourceBusy`](std::io::ErrorKind::ResourceBusy). A process that
+//!   dies while attached leaves its slot claimed; recreate the queue.
+//! - **Creating Truncates:** `create` discards whatever the file held, including
+//!   a queue someone else still has mapped.
+//! - **Linux Only:** Shared futexes and `memfd` are Linux APIs.
+
+pub use self::{receiver::Receiver, sender::Sender};
+
+mod queue;
+mod receiver;
+mod sender;
+
+#[cfg(test)]
+mod test {
+    use core::num::NonZeroUsize;
+    use std::{fs::File, io, os::fd::FromRawFd};
+
+    use super::*;
+    use crate::thread;
+
+    fn memfd() -> File {
+        let fd = unsafe { libc::memfd_create(c"gil-test".as_ptr(), 0) };
+        assert!(fd >= 0);
+        unsafe { File::from_raw_fd(fd) }
+    }
+
+    #[test]
+    fn separate_mappings() {
+        const ITER: usize = 100_000;
+
+        let file = memfd();
+        let mut tx = Sender::<usize>::create(&file, NonZeroUsize::new(8).unwrap()).unwrap();
+
+        // every handle maps the region at its own address, like another process
+        // would, so parking must go through the shared futex
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut rx = Receiver::<usize>::open(&file).unwrap();
+                for i in 0..ITER {
+                    assert_eq!(rx.recv(), i);
+                }
+            });
+
+            for i in 0..ITER {
+                tx.send(i);
+            }
+        });
+    }
+
+    #[test]
+    fn across_fork() {
+        const ITER: u64 = 10_000;
+
+        let file = memfd();
+        let mut tx = Sender::<u64>::create(&file, NonZeroUsize::new(16).unwrap()).unwrap();
+
+        let pid = unsafe { libc::fork() };
+        assert!(pid >= 0);
+        if pid == 0 {
+            // child: nothing here allocates, so forking a threaded test runner is fine
+            let ok = match Receiver::<u64>::open(&file) {
+                Ok(mut rx) => (0..ITER).all(|i| rx.recv() == i),
+                Err(_) => false,
+            };
+            unsafe { libc::_exit(if ok { 0 } else { 1 }) };
+        }
+
+        for i in 0..ITER {
+            tx.send(i);
+        }
+        let mut status = 0;
+        assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+        assert!(libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0);
+    }
+
+    #[test]
+    fn exact_capacity() {
+        let file = memfd();
+        let mut tx = Sender::<u32>::create(&file, NonZeroUsize::new(3).unwrap()).unwrap();
+        let mut rx = Receiver::<u32>::open(&file).unwrap();
+
+        for i in 0..3 {
+            assert!(tx.try_send(i).is_ok());
+        }
+        assert_eq!(tx.try_send(3), Err(3));
+        assert_eq!(rx.try_recv(), Some(0));
+        assert!(tx.try_send(3).is_ok());
+    }
+
+    #[test]
+    fn rejects_mismatched_layout() {
+        let file = memfd();
+        let _tx = Sender::<u32>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap();
+
+        let err = Receiver::<u64>::open(&file).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = crate::mpsc::shm::Receiver::<u32>::open(&file)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let empty = memfd();
+        let err = Receiver::<u32>::open(&empty).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn single_attachment() {
+        let file = memfd();
+        let tx = Sender::<u32>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap();
+
+        let err = Sender::<u32>::open(&file).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::ResourceBusy);
+
+        drop(tx);
+        assert!(Sender::<u32>::open(&file).is_ok());
+    }
+
+    #[test]
+    fn reattach_resumes() {
+        let file = memfd();
+        let mut tx = Sender::<u32>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap();
+        let mut rx = Receiver::<u32>::open(&file).unwrap();
+
+        tx.send(1);
+        tx.send(2);
+        assert_eq!(rx.recv(), 1);
+        drop((tx, rx));
+
+        let mut rx = Receiver::<u32>::open(&file).unwrap();
+        assert_eq!(rx.try_recv(), Some(2));
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn rejects_oversized_capacity() {
+        use std::os::unix::fs::FileExt;
+
+        let err = Sender::<u32>::create(&memfd(), NonZeroUsize::MAX)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let file = memfd();
+        drop(Sender::<u32>::create(&file, NonZeroUsize::new(4).unwrap()).unwrap());
+
+        // the header's `size` field, after the magic, version, kind, word size,
+        // element alignment and element size
+        const SIZE_OFFSET: u64 = 32;
+        let mut size = [0; 8];
+        file.read_exact_at(&mut size, SIZE_OFFSET).unwrap();
+        assert_eq!(u64::from_ne_bytes(size), 4);
+
+        // rounds up past `usize::MAX`
+        let size = (usize::MAX / 2 + 2) as u64;
+        file.write_all_at(&size.to_ne_bytes(), SIZE_OFFSET).unwrap();
+        let err = Receiver::<u32>::open(&file).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn send_with() {
+        let file = memfd();
+        let mut tx = Sender::<[u32; 64]>::create(&file, NonZeroUsize::new(2).unwrap()).unwrap();
+        let mut rx = Receiver::<[u32; 64]>::open(&file).unwrap();
+
+        tx.send_with(|slot| slot.write([1; 64]));
+        assert!(tx.try_send_with(|slot| slot.write([2; 64])).is_ok());
+        assert!(tx.try_send_with(|_| unreachable!()).is_err());
+        assert_eq!(rx.recv(), [1; 64]);
+        assert_eq!(rx.recv(), [2; 64]);
+    }
+}
@@ -0,0 +1,155 @@
+use core::{mem::MaybeUninit, num::NonZeroUsize, ptr::NonNull};
+use std::{io, os::fd::BorrowedFd};
+
+use crate::{
+    atomic::{AtomicU32, AtomicUsize},
+    futex::Futex,
+    padded::Padded,
+    ring::RingPtr,
+    shm::{Header, Kind, Region, layout},
+};
+
+/// The spsc control block, right after the header.
+#[repr(C)]
+struct Control {
+    header: Header,
+    head: Padded<AtomicUsize>,
+    tail: Padded<AtomicUsize>,
+    futex: Padded<AtomicU32>,
+}
+
+/// One process's handle to a shared-memory ring. Plays the role of
+/// [`Ring`](crate::ring::Ring) for the shared-memory endpoints.
+pub(crate) struct ShmRing<T> {
+    region: Region,
+    buffer: NonNull<T>,
+    size: usize,
+    mask: usize,
+    capacity: usize,
+}
+
+impl<T: Copy> ShmRing<T> {
+    pub(crate) fn create(fd: BorrowedFd<'_>, size: NonZeroUsize) -> io::Result<Self> {
+        let size = size.get();
+        let capacity = size
+            .checked_next_power_of_two()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "queue too large"))?;
+        let (offset, len) = layout::<Control, T>(capacity)?;
+
+        let region = Region::create(fd, len)?;
+        // SAFETY: zeroed cursors and futex word are the empty-queue state, and
+        // the buffer needs no initialisation.
+        unsafe { Header::publish::<T>(&region, Kind::Spsc, size) };
+        Ok(Self::new(region, offset, size, capacity))
+    }
+
+    pub(crate) fn open(fd: BorrowedFd<'_>) -> io::Result<Self> {
+        let region = Region::open(fd)?;
+        let size = region.header().validate::<T>(Kind::Spsc)?;
+        // `size` comes from the region, so it may be anything a corrupted or
+        // crafted file holds
+        let capacity = size.checked_next_power_of_two().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "queue capacity out of range")
+        })?;
+        let (offset, len) = layout::<Control, T>(capacity)?;
+        if region.len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "region shorter than its queue",
+            ));
+        }
+        Ok(Self::new(region, offset, size, capacity))
+    }
+
+    fn new(region: Region, offset: usize, size: usize, capacity: usize) -> Self {
+        Self {
+            buffer: region.buffer(offset),
+            region,
+            size,
+            mask: capacity - 1,
+            capacity,
+        }
+    }
+}
+
+impl<T> ShmRing<T> {
+    #[inline(always)]
+    fn control(&self) -> &Control {
+        // SAFETY: `Control` is `repr(C)` with the header first, and the region
+        // was sized for it by `create`/`open`.
+        unsafe { self.region.control() }
+    }
+
+    #[inline(always)]
+    pub(crate) fn header(&self) -> &Header {
+        &self.control().header
+    }
+
+    #[inline(always)]
+    pub(crate) fn futex(&self) -> Futex {
+        Futex::new(&self.control().futex.value)
+    }
+}
+
+impl<T> RingPtr for ShmRing<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn head(&self) -> &AtomicUsize {
+        &self.control().head.value
+    }
+
+    #[inline(always)]
+    fn tail(&self) -> &AtomicUsize {
+        &self.control().tail.value
+    }
+
+    #[inline(always)]
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline(always)]
+    fn mask(&self) -> usize {
+        self.mask
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // The producer lives in another process, may attach after the receiver or
+    // after a previous producer detached, and can exit without detaching, so
+    // the ring never reports a disconnection.
+    #[inline(always)]
+    fn attach_sender(&self) {}
+
+    #[inline(always)]
+    fn detach_sender(&self) {}
+
+    #[inline(always)]
+    fn is_disconnected(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    unsafe fn exact_at(&self, index: usize) -> NonNull<T> {
+        unsafe { self.buffer.add(index) }
+    }
+
+    #[inline(always)]
+    unsafe fn get(&self, index: usize) -> T {
+        unsafe { self.buffer.add(index & self.mask).read() }
+    }
+
+    #[inline(always)]
+    unsafe fn set(&self, index: usize, value: T) {
+        unsafe {
+            self.buffer
+                .add(index & self.mask)
+                .cast::<MaybeUninit<T>>()
+                .write(MaybeUninit::new(value))
+        }
+    }
+}
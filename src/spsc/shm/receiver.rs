@@ -0,0 +1,199 @@
+use core::num::NonZeroUsize;
+use std::{io, os::fd::AsFd};
+
+use super::queue::ShmRing;
+use crate::{futex::RECEIVER_WAITING, ring::Consumer};
+
+/// The consumer end of a shared-memory SPSC queue.
+///
+/// Each queue accepts one attached receiver at a time, across all processes.
+/// This struct is `Send` but not `Sync` or `Clone`.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::spsc::shm::{Receiver, Sender};
+///
+/// let file = memfd();
+/// let mut rx = Receiver::<u64>::create(&file, NonZeroUsize::new(16).unwrap()).unwrap();
+/// let mut tx = Sender::<u64>::open(&file).unwrap();
+///
+/// assert_eq!(rx.try_recv(), None);
+/// tx.send(1);
+/// assert_eq!(rx.try_recv(), Some(1));
+/// # fn memfd() -> std::fs::File {
+/// #     use std::os::fd::FromRawFd;
+/// #     let fd = unsafe { libc::memfd_create(c"gil".as_ptr(), 0) };
+/// #     assert!(fd >= 0);
+/// #     unsafe { std::fs::File::from_raw_fd(fd) }
+/// # }
+/// ```
+pub struct Receiver<T: Copy> {
+    consumer: Consumer<ShmRing<T>>,
+}
+
+impl<T: Copy> Receiver<T> {
+    /// Creates a new queue in `fd` and attaches to it as the receiver.
+    ///
+    /// The file is truncated and resized to fit the queue; anything in it is
+    /// lost. `capacity` is the exact number of items the queue holds.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from resizing or mapping the file.
+    pub fn create(fd: &impl AsFd, capacity: NonZeroUsize) -> io::Result<Self> {
+        Self::attach(ShmRing::create(fd.as_fd(), capacity)?)
+    }
+
+    /// Attaches as the receiver to a queue another handle created in `fd`.
+    ///
+    /// # Errors
+    ///
+    /// * [`WouldBlock`](io::ErrorKind::WouldBlock) if the creator has not finished
+    ///   initialising the queue.
+    /// * [`InvalidData`](io::ErrorKind::InvalidData) if `fd` does not hold an
+    ///   spsc queue of `T` with this crate's layout version.
+    /// * [`ResourceBusy`](io::ErrorKind::ResourceBusy) if a receiver is already
+    ///   attached.
+    pub fn open(fd: &impl AsFd) -> io::Result<Self> {
+        Self::attach(ShmRing::open(fd.as_fd())?)
+    }
+
+    fn attach(ring: ShmRing<T>) -> io::Result<Self> {
+        ring.header().attach_receiver()?;
+        Ok(Self {
+            consumer: Consumer::attach(ring),
+        })
+    }
+
+    /// Attempts to receive a value from the queue without blocking.
+    ///
+    /// Returns `None` if the queue is empty.
+    pub fn try_recv(&mut self) -> Option<T> {
+        let value = self.consumer.try_pop()?;
+        self.consumer.ring().futex().wake_shared();
+        Some(value)
+    }
+
+    /// A blocking iterator over received items, like calling
+    /// [`recv`](Self::recv) in a loop. The senders live in other processes,
+    /// so the receiver cannot see them disconnect and this never ends on its
+    /// own. See [`iter`](crate::iter).
+    pub fn iter(&mut self) -> crate::iter::Iter<'_, Self> {
+        crate::iter::Receive::iter(self)
+    }
+
+    /// A non-blocking iterator over the items available now, like calling
+    /// [`try_recv`](Self::try_recv) until it returns `None`.
+    pub fn try_iter(&mut self) -> crate::iter::TryIter<'_, Self> {
+        crate::iter::Receive::try_iter(self)
+    }
+
+    /// Receives a value from the queue, blocking if necessary.
+    ///
+    /// Spins briefly, then yields, and finally parks on the queue's shared
+    /// futex until the sender, in whichever process, publishes an item.
+    pub fn recv(&mut self) -> T {
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        while self.consumer.is_empty() {
+            if backoff.backoff() {
+                let futex = self.consumer.ring().futex();
+                if futex.announce(RECEIVER_WAITING) {
+                    // catch lost wakes: recheck against a fresh tail before parking
+                    self.consumer.refresh_tail();
+                    if self.consumer.is_empty() {
+                        self.consumer.stats.park();
+                        futex.sleep_shared(RECEIVER_WAITING);
+                    }
+                }
+            }
+            self.consumer.refresh_tail();
+        }
+        let value = self.consumer.pop();
+
+        self.consumer.ring().futex().wake_shared();
+
+        value
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// The sender may be filling it concurrently, so this is a snapshot and an
+    /// lower bound.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.consumer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: exactly the requested capacity.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.consumer.size()
+    }
+
+    /// A snapshot of this receiver's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.consumer.stats.snapshot()
+    }
+}
+
+impl<T: Copy> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.consumer.ring().header().detach_receiver();
+    }
+}
+
+impl<T: Copy> crate::iter::Receive for Receiver<T> {
+    type Item = T;
+
+    #[inline]
+    fn recv(&mut self) -> T {
+        Receiver::recv(self)
+    }
+
+    #[inline]
+    fn try_recv(&mut self) -> Option<T> {
+        Receiver::try_recv(self)
+    }
+
+    /// Always `false`: senders in other processes can attach at any time and
+    /// can exit without detaching. See [`iter`](crate::iter).
+    #[inline]
+    fn is_disconnected(&self) -> bool {
+        false
+    }
+}
+
+impl<T: Copy> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::IntoIter<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        crate::iter::IntoIter::new(self)
+    }
+}
+
+impl<'a, T: Copy> IntoIterator for &'a mut Receiver<T> {
+    type Item = T;
+    type IntoIter = crate::iter::Iter<'a, Receiver<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+unsafe impl<T: Copy + Send> Send for Receiver<T> {}
@@ -0,0 +1,220 @@
+use core::{mem::MaybeUninit, num::NonZeroUsize};
+use std::{io, os::fd::AsFd};
+
+use super::queue::ShmRing;
+use crate::{futex::SENDER_WAITING, ring::Producer};
+
+/// The producer end of a shared-memory SPSC queue.
+///
+/// Each queue accepts one attached sender at a time, across all processes. This
+/// struct is `Send` but not `Sync` or `Clone`.
+///
+/// # Examples
+///
+/// ```
+/// use core::num::NonZeroUsize;
+/// use gil::spsc::shm::{Receiver, Sender};
+///
+/// let file = memfd();
+/// let mut tx = Sender::<u64>::create(&file, NonZeroUsize::new(16).unwrap()).unwrap();
+/// let mut rx = Receiver::<u64>::open(&file).unwrap();
+///
+/// tx.send(1);
+/// assert_eq!(rx.recv(), 1);
+/// # fn memfd() -> std::fs::File {
+/// #     use std::os::fd::FromRawFd;
+/// #     let fd = unsafe { libc::memfd_create(c"gil".as_ptr(), 0) };
+/// #     assert!(fd >= 0);
+/// #     unsafe { std::fs::File::from_raw_fd(fd) }
+/// # }
+/// ```
+pub struct Sender<T: Copy> {
+    producer: Producer<ShmRing<T>>,
+}
+
+impl<T: Copy> Sender<T> {
+    /// Creates a new queue in `fd` and attaches to it as the sender.
+    ///
+    /// The file is truncated and resized to fit the queue; anything in it is
+    /// lost. `capacity` is the exact number of items the queue holds.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from resizing or mapping the file.
+    pub fn create(fd: &impl AsFd, capacity: NonZeroUsize) -> io::Result<Self> {
+        Self::attach(ShmRing::create(fd.as_fd(), capacity)?)
+    }
+
+    /// Attaches as the sender to a queue another handle created in `fd`.
+    ///
+    /// # Errors
+    ///
+    /// * [`WouldBlock`](io::ErrorKind::WouldBlock) if the creator has not finished
+    ///   initialising the queue.
+    /// * [`InvalidData`](io::ErrorKind::InvalidData) if `fd` does not hold an
+    ///   spsc queue of `T` with this crate's layout version.
+    /// * [`ResourceBusy`](io::ErrorKind::ResourceBusy) if a sender is already
+    ///   attached.
+    pub fn open(fd: &impl AsFd) -> io::Result<Self> {
+        Self::attach(ShmRing::open(fd.as_fd())?)
+    }
+
+    fn attach(ring: ShmRing<T>) -> io::Result<Self> {
+        ring.header().attach_sender()?;
+        Ok(Self {
+            producer: Producer::attach(ring),
+        })
+    }
+
+    /// Attempts to send a value into the queue without blocking.
+    ///
+    /// Returns `Err(value)` if the queue is full.
+    pub fn try_send(&mut self, value: T) -> Result<(), T> {
+        self.producer.try_push(value)?;
+        self.producer.ring().futex().wake_shared();
+        Ok(())
+    }
+
+    /// Sends a value into the queue, blocking if necessary.
+    ///
+    /// Spins briefly, then yields, and finally parks on the queue's shared
+    /// futex until the receiver, in whichever process, frees a slot.
+    pub fn send(&mut self, value: T) {
+        self.wait_for_space();
+        self.producer.push(value);
+
+        self.producer.ring().futex().wake_shared();
+    }
+
+    /// Constructs the next item directly in its queue slot, blocking while the
+    /// queue is full.
+    ///
+    /// Waits like [`send`](Self::send), then calls `f` on the free slot, which
+    /// saves the copy into shared memory for large `T`. The item is published
+    /// when `f` returns; if `f` panics, nothing is sent.
+    ///
+    /// `f` returns the slot once it is initialised: the `&mut T` that
+    /// [`MaybeUninit::write`] gives back. Returning any other reference panics
+    /// without sending.
+    pub fn send_with(&mut self, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+        self.wait_for_space();
+        // SAFETY: `wait_for_space` returns once the ring has room.
+        unsafe { self.producer.push_with(f) };
+
+        self.producer.ring().futex().wake_shared();
+    }
+
+    /// Non-blocking [`send_with`](Self::send_with).
+    ///
+    /// Returns `Err(f)` without calling it if the queue is full.
+    pub fn try_send_with<F>(&mut self, f: F) -> Result<(), F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        self.producer.try_push_with(f)?;
+        self.producer.ring().futex().wake_shared();
+        Ok(())
+    }
+
+    fn wait_for_space(&mut self) {
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        while self.producer.is_full() {
+            if backoff.backoff() {
+                let futex = self.producer.ring().futex();
+                if futex.announce(SENDER_WAITING) {
+                    // catch lost wakes: recheck against a fresh head before parking
+                    self.producer.refresh_head();
+                    if self.producer.is_full() {
+                        self.producer.stats.park();
+                        futex.sleep_shared(SENDER_WAITING);
+                    }
+                }
+            }
+            self.producer.refresh_head();
+        }
+    }
+
+    /// Sends every item of `values`, blocking while the queue is full.
+    ///
+    /// Copies straight into the ring and publishes each contiguous run of free
+    /// slots at once, rather than item by item.
+    pub fn send_slice(&mut self, mut values: &[T]) {
+        loop {
+            let sent = self.producer.push_slice(values);
+            values = &values[sent..];
+            self.producer.ring().futex().wake_shared();
+            if values.is_empty() {
+                return;
+            }
+            self.wait_for_space();
+        }
+    }
+
+    /// Sends items from `iter` until it runs dry or the queue fills up,
+    /// without blocking. Returns the number sent.
+    ///
+    /// Only items there is room for are taken from `iter`; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    pub fn try_send_iter(&mut self, mut iter: impl Iterator<Item = T>) -> usize {
+        let (Ok(sent) | Err(sent)) = self.producer.push_iter(&mut iter);
+        self.producer.ring().futex().wake_shared();
+        sent
+    }
+
+    /// Sends every item of `iter`, blocking while the queue is full.
+    ///
+    /// Like [`send_slice`](Self::send_slice), items are published a run of
+    /// free slots at a time.
+    pub fn send_all(&mut self, iter: impl IntoIterator<Item = T>) {
+        let mut iter = iter.into_iter().peekable();
+        loop {
+            let pushed = self.producer.push_iter(&mut iter);
+            self.producer.ring().futex().wake_shared();
+            if pushed.is_ok() || iter.peek().is_none() {
+                return;
+            }
+            self.wait_for_space();
+        }
+    }
+
+    /// Number of items in the queue.
+    ///
+    /// The receiver may be draining it concurrently, so this is a snapshot and an
+    /// upper bound.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.producer.len()
+    }
+
+    /// Returns `true` if the queue holds no items. See [`len`](Self::len).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the queue is at capacity. See [`len`](Self::len).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// The number of items the queue holds when full: exactly the requested capacity.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.producer.size()
+    }
+
+    /// A snapshot of this sender's counters. See [`Stats`](crate::Stats).
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::Stats {
+        self.producer.stats.snapshot()
+    }
+}
+
+impl<T: Copy> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.producer.ring().header().detach_sender();
+    }
+}
+
+unsafe impl<T: Copy + Send> Send for Sender<T> {}
@@ -0,0 +1,141 @@
+//! Per-endpoint runtime counters.
+//!
+//! Every handle embeds a [`Counters`]. With the `stats` feature it holds a
+//! [`Stats`] that only that handle writes. The counters are plain integers
+//! behind `&mut self` — no atomics, nothing shared, so no cache line ping-pongs
+//! between endpoints. Without the feature `Counters` is a zero-sized type whose
+//! methods are empty, and every call site compiles away.
+
+/// A snapshot of one handle's counters, returned by its `stats()` method.
+///
+/// Counts are per handle: a cloned handle starts from zero, and a counter a
+/// handle has no use for (e.g. `received` on a sender) stays at zero.
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// Items sent through this handle.
+    pub sent: u64,
+    /// Items received through this handle.
+    pub received: u64,
+    /// Send attempts that found the queue full: each failed `try_send`, and
+    /// each blocking send that had to wait.
+    pub full: u64,
+    /// Times this handle parked its thread on the futex.
+    pub parks: u64,
+    /// Wake syscalls this handle issued. A wake with no announced waiter
+    /// costs no syscall and is not counted.
+    pub wakes: u64,
+    /// Failed compare-exchanges while claiming a slot against other handles
+    /// of the same side.
+    pub cas_retries: u64,
+    /// The highest queue length this handle observed right after its own
+    /// sends or receives. Senders measure against the live head; a receiver
+    /// may measure against its cached copy of the tail, which can only
+    /// understate the length.
+    pub high_water: usize,
+}
+
+#[cfg(feature = "stats")]
+impl Stats {
+    /// Sums the counts and keeps the larger high-water mark. Used by handles
+    /// that own several shards.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn merge(self, other: Self) -> Self {
+        Self {
+            sent: self.sent + other.sent,
+            received: self.received + other.received,
+            full: self.full + other.full,
+            parks: self.parks + other.parks,
+            wakes: self.wakes + other.wakes,
+            cas_retries: self.cas_retries + other.cas_retries,
+            high_water: self.high_water.max(other.high_water),
+        }
+    }
+}
+
+/// The counters embedded in an endpoint; empty unless `stats` is enabled.
+#[derive(Default)]
+pub(crate) struct Counters {
+    #[cfg(feature = "stats")]
+    stats: Stats,
+}
+
+// A clone is a new endpoint, so it starts from zero.
+impl Clone for Counters {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+impl Counters {
+    #[inline(always)]
+    pub(crate) fn sent(&mut self, n: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.sent += n as u64;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn received(&mut self, n: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.received += n as u64;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn full(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.full += 1;
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub(crate) fn park(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.parks += 1;
+        }
+    }
+
+    /// Takes the result of [`Futex::wake`](crate::futex::Futex::wake) or
+    /// [`wake_all`](crate::futex::Futex::wake_all).
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub(crate) fn wake(&mut self, issued: bool) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.wakes += issued as u64;
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub(crate) fn cas_retry(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.cas_retries += 1;
+        }
+    }
+
+    /// Records an observed length. Takes a closure so callers that need
+    /// atomic loads to compute it skip them when the feature is off.
+    #[inline(always)]
+    pub(crate) fn observe(&mut self, len: impl FnOnce() -> usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.high_water = self.stats.high_water.max(len());
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub(crate) fn snapshot(&self) -> Stats {
+        self.stats
+    }
+}
@@ -0,0 +1,193 @@
+//! Latest-value ("watch") channel backed by a triple buffer.
+//!
+//! For configuration and state snapshots only the most recent value matters. The
+//! writer never blocks and simply replaces whatever the reader has not seen yet;
+//! the reader always gets the newest complete value, never a torn one.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::thread;
+//! use gil::watch::channel;
+//!
+//! let (mut tx, mut rx) = channel(0usize);
+//!
+//! thread::spawn(move || {
+//!     for i in 1..=100 {
+//!         tx.send(i);
+//!     }
+//! });
+//!
+//! while *rx.wait() != 100 {}
+//! ```
+//!
+//! ## Async
+//!
+//! Async support is available with the `async` feature flag. See
+//! [`Receiver::wait_async`].
+//!
+//! ```rust,ignore
+//! use gil::watch::channel;
+//!
+//! // In an async context:
+//! let (mut tx, mut rx) = channel(0usize);
+//! tx.send(42);
+//! assert_eq!(*rx.wait_async().await, 42);
+//! ```
+//!
+//! # Performance
+//!
+//! - **Wait-free Both Ways:** A send is one write and one atomic swap; a read of a
+//!   new value is one atomic swap, and a read of an unchanged value is one load.
+//! - **No Copies on Read:** [`Receiver::latest`] returns a reference into the
+//!   buffer rather than cloning the value out.
+//! - **Memory:** Up to three values are alive at once: the reader's, the writer's
+//!   last one, and the one in between.
+//!
+//! # Gotchas
+//!
+//! - **Single Writer, Single Reader:** Neither end is `Clone`.
+//! - **Dropped Values:** A value overwritten before the reader saw it is dropped on
+//!   the writer's thread, up to two sends later, rather than immediately.
+//! - **No Disconnection:** [`Receiver::wait`] blocks forever once the [`Sender`] is
+//!   dropped.
+
+use core::num::NonZeroUsize;
+
+pub use self::{receiver::Receiver, sender::Sender};
+
+mod queue;
+mod receiver;
+mod sender;
+
+/// Creates a new watch channel holding `initial`.
+///
+/// See the [module-level documentation](self) for more details.
+///
+/// # Examples
+///
+/// ```
+/// use gil::watch::channel;
+///
+/// let (tx, mut rx) = channel(String::from("initial"));
+/// assert_eq!(rx.latest(), "initial");
+/// ```
+pub fn channel<T>(initial: T) -> (Sender<T>, Receiver<T>) {
+    let queue = queue::QueuePtr::with_size(NonZeroUsize::new(3).unwrap());
+    // SAFETY: freshly allocated; the reader's slot must start initialised.
+    unsafe { queue.slot(queue::READER_SLOT).write(initial) };
+
+    (Sender::new(queue.clone()), Receiver::new(queue))
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn test_no_tearing() {
+        const ITER: usize = 100_000;
+
+        let (mut tx, mut rx) = channel([0usize; 16]);
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 1..=ITER {
+                    tx.send([i; 16]);
+                }
+            });
+
+            let mut last = 0;
+            while last != ITER {
+                let value = rx.latest();
+                assert!(value.iter().all(|v| *v == value[0]));
+                assert!(value[0] >= last);
+                last = value[0];
+            }
+        });
+    }
+
+    #[test]
+    fn test_changed() {
+        let (mut tx, mut rx) = channel(0);
+        assert!(!rx.changed());
+
+        tx.send(1);
+        tx.send(2);
+        assert!(rx.changed());
+        assert_eq!(*rx.latest(), 2);
+        assert!(!rx.changed());
+        assert_eq!(*rx.latest(), 2);
+    }
+
+    #[test]
+    fn test_drop() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        for sends in 0..6 {
+            let dropped = Arc::new(AtomicUsize::new(0));
+            {
+                let (mut tx, mut rx) = channel(DropCounter(dropped.clone()));
+                for i in 0..sends {
+                    tx.send(DropCounter(dropped.clone()));
+                    if i % 2 == 0 {
+                        rx.latest();
+                    }
+                }
+            }
+            assert_eq!(dropped.load(Ordering::SeqCst), sends + 1);
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_wait() {
+        let (mut tx, mut rx) = channel(0usize);
+
+        thread::spawn(move || {
+            for i in 1..=1000 {
+                tx.send(i);
+            }
+        });
+
+        futures::executor::block_on(async { while *rx.wait_async().await != 1000 {} });
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_test {
+    use super::*;
+
+    use crate::thread;
+
+    #[test]
+    fn latest_is_monotonic_loom() {
+        loom::model(|| {
+            let (mut tx, mut rx) = channel(0usize);
+
+            let handle = thread::spawn(move || {
+                for i in 1..=3 {
+                    tx.send(i);
+                }
+            });
+
+            let first = *rx.latest();
+            let second = *rx.latest();
+            assert!(second >= first);
+
+            handle.join().unwrap();
+            assert_eq!(*rx.latest(), 3);
+        })
+    }
+}
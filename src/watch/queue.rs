@@ -0,0 +1,129 @@
+//! Triple-buffer layout for the watch channel.
+//!
+//! Three slots: at any time the writer owns one, the reader owns one, and the
+//! third is parked in `middle`. Publishing swaps the freshly written slot into
+//! `middle` with the [`NEW`] bit set; reading swaps the reader's slot back in if
+//! the bit is set. Neither side ever touches a slot the other owns, so the reader
+//! never sees a torn value and the writer never waits.
+//!
+//! The initial value goes in slot 0 (the reader's), `middle` starts at slot 1 and
+//! the writer at slot 2. The writer's first two writes therefore land in slots 2
+//! and 1, which were never initialised; from the third write on every slot holds
+//! a value. `writes` records that for teardown.
+
+use core::ptr::NonNull;
+#[cfg(feature = "async")]
+use core::task::Waker;
+
+#[cfg(feature = "async")]
+use futures::task::AtomicWaker;
+
+#[cfg(feature = "std")]
+use crate::{atomic::AtomicU32, futex::HasFutex};
+use crate::{
+    atomic::{AtomicUsize, Ordering},
+    padded::Padded,
+};
+
+/// Set on `middle` when it holds a slot the reader has not seen yet.
+pub(crate) const NEW: usize = 1 << 2;
+/// Masks the slot index out of `middle`.
+pub(crate) const INDEX: usize = NEW - 1;
+
+pub(crate) const READER_SLOT: usize = 0;
+pub(crate) const MIDDLE_SLOT: usize = 1;
+pub(crate) const WRITER_SLOT: usize = 2;
+
+#[repr(C)]
+pub(crate) struct Head {
+    middle: Padded<AtomicUsize>,
+    /// Writes published so far, saturating at 2. Only the writer touches it
+    /// before teardown.
+    writes: AtomicUsize,
+    #[cfg(feature = "std")]
+    futex: AtomicU32,
+    #[cfg(feature = "async")]
+    receiver_waker: AtomicWaker,
+}
+
+impl Default for Head {
+    fn default() -> Self {
+        Self {
+            middle: Padded::new(AtomicUsize::new(MIDDLE_SLOT)),
+            writes: AtomicUsize::new(0),
+            #[cfg(feature = "std")]
+            futex: AtomicU32::new(0),
+            #[cfg(feature = "async")]
+            receiver_waker: AtomicWaker::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl HasFutex for Head {
+    #[inline(always)]
+    fn futex(&self) -> &AtomicU32 {
+        &self.futex
+    }
+}
+
+/// Drops every slot that was ever written; see the module docs for which.
+pub(crate) struct DropWritten;
+
+impl<T> crate::DropInFlight<Head, (), T> for DropWritten {
+    unsafe fn drop_in_flight(
+        head: &Head,
+        _tail: &(),
+        _capacity: usize,
+        at: impl Fn(usize) -> NonNull<T>,
+    ) {
+        if !core::mem::needs_drop::<T>() {
+            return;
+        }
+
+        let writes = head.writes.load(Ordering::Relaxed);
+        unsafe { at(READER_SLOT).drop_in_place() };
+        if writes >= 1 {
+            unsafe { at(WRITER_SLOT).drop_in_place() };
+        }
+        if writes >= 2 {
+            unsafe { at(MIDDLE_SLOT).drop_in_place() };
+        }
+    }
+}
+
+pub(crate) type QueuePtr<T> = crate::QueuePtr<Head, (), T, DropWritten>;
+
+impl<T> QueuePtr<T> {
+    #[inline(always)]
+    pub(crate) fn middle(&self) -> &AtomicUsize {
+        &self.header().head.middle.value
+    }
+
+    #[inline(always)]
+    pub(crate) fn writes(&self) -> &AtomicUsize {
+        &self.header().head.writes
+    }
+
+    #[inline(always)]
+    pub(crate) fn slot(&self, index: usize) -> NonNull<T> {
+        debug_assert!(index <= WRITER_SLOT);
+        // SAFETY: the buffer has at least three slots.
+        unsafe { self.exact_at(index) }
+    }
+
+    /// Wakes the reader if it is blocked waiting for a change.
+    #[inline(always)]
+    pub(crate) fn wake_receiver(&self) {
+        #[cfg(feature = "std")]
+        self.futex().wake();
+        #[cfg(feature = "async")]
+        self.header().head.receiver_waker.wake();
+    }
+
+    #[cfg(feature = "async")]
+    #[inline(always)]
+    pub(crate) fn register_receiver_waker(&self, waker: &Waker) {
+        self.header().head.receiver_waker.register(waker);
+    }
+}
@@ -0,0 +1,177 @@
+#[cfg(feature = "async")]
+use core::task::{Context, Poll};
+
+use crate::atomic::Ordering;
+#[cfg(feature = "std")]
+use crate::futex::RECEIVER_WAITING;
+
+use super::queue::{INDEX, NEW, QueuePtr, READER_SLOT};
+
+/// The reading end of a watch channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use gil::watch::channel;
+///
+/// let (mut tx, mut rx) = channel(0);
+///
+/// thread::spawn(move || {
+///     for i in 1..=100 {
+///         tx.send(i);
+///     }
+/// });
+///
+/// // values may be skipped, but never go backwards
+/// let mut last = *rx.latest();
+/// while last != 100 {
+///     let value = *rx.wait();
+///     assert!(value > last);
+///     last = value;
+/// }
+/// ```
+pub struct Receiver<T> {
+    ptr: QueuePtr<T>,
+    slot: usize,
+}
+
+impl<T> Receiver<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self {
+            ptr,
+            slot: READER_SLOT,
+        }
+    }
+
+    /// Returns `true` if a value was published since the last
+    /// [`latest`](Receiver::latest) or [`wait`](Receiver::wait).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::watch::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(0);
+    /// assert!(!rx.changed());
+    ///
+    /// tx.send(1);
+    /// assert!(rx.changed());
+    /// ```
+    #[inline]
+    pub fn changed(&self) -> bool {
+        self.ptr.middle().load(Ordering::Relaxed) & NEW != 0
+    }
+
+    /// Returns the newest published value, marking it as seen.
+    ///
+    /// Never blocks. If nothing was published since the last read, returns the
+    /// same value again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::watch::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(0);
+    /// assert_eq!(*rx.latest(), 0);
+    ///
+    /// tx.send(1);
+    /// assert_eq!(*rx.latest(), 1);
+    /// assert_eq!(*rx.latest(), 1);
+    /// ```
+    pub fn latest(&mut self) -> &T {
+        if self.changed() {
+            // `Acquire` synchronises with the writer's publish of the slot we
+            // take; `Release` hands our old slot back only after we're done
+            // reading it.
+            let previous = self.ptr.middle().swap(self.slot, Ordering::AcqRel);
+            self.slot = previous & INDEX;
+        }
+
+        // SAFETY: the reader's slot is always initialised (slot 0 holds the
+        // initial value; any slot swapped in was written first), and the writer
+        // never touches it while we own it.
+        unsafe { self.ptr.slot(self.slot).as_ref() }
+    }
+
+    /// Blocks until a new value is published, then returns it.
+    ///
+    /// Returns immediately if [`changed`](Receiver::changed) is already `true`.
+    /// Under `std` the reader parks on a futex after a short spin; without `std`
+    /// it spins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use gil::watch::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(0);
+    ///
+    /// thread::spawn(move || tx.send(42));
+    /// assert_eq!(*rx.wait(), 42);
+    /// ```
+    pub fn wait(&mut self) -> &T {
+        #[cfg(feature = "std")]
+        let mut backoff = crate::ParkingBackoff::new(16, 4);
+        #[cfg(not(feature = "std"))]
+        let mut backoff = crate::Backoff::with_spin_count(128);
+
+        while !self.changed() {
+            #[cfg(feature = "std")]
+            if backoff.backoff() {
+                let futex = self.ptr.futex();
+                // catch lost wakes: recheck before parking
+                if futex.announce(RECEIVER_WAITING) && !self.changed() {
+                    futex.sleep(RECEIVER_WAITING);
+                }
+            }
+
+            #[cfg(not(feature = "std"))]
+            backoff.backoff();
+        }
+
+        self.latest()
+    }
+
+    /// Waits asynchronously until a new value is published, then returns it.
+    ///
+    /// Requires the `async` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use gil::watch::channel;
+    ///
+    /// let (mut tx, mut rx) = channel(0);
+    /// tx.send(42);
+    /// assert_eq!(*rx.wait_async().await, 42);
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn wait_async(&mut self) -> &T {
+        futures::future::poll_fn(|cx| self.poll_changed(cx)).await;
+        self.latest()
+    }
+
+    #[cfg(feature = "async")]
+    fn poll_changed(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.changed() {
+            return Poll::Ready(());
+        }
+
+        self.ptr.register_receiver_waker(cx.waker());
+
+        // recheck after registering so a send racing the registration is not lost
+        if self.changed() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for Receiver<T> {}
@@ -0,0 +1,76 @@
+use crate::atomic::Ordering;
+
+use super::queue::{INDEX, NEW, QueuePtr, WRITER_SLOT};
+
+/// The writing end of a watch channel.
+///
+/// This struct is `Send` but not `Sync` or `Clone`. It can be moved to another
+/// thread, but cannot be shared across threads.
+///
+/// # Examples
+///
+/// ```
+/// use gil::watch::channel;
+///
+/// let (mut tx, mut rx) = channel(0);
+/// tx.send(1);
+/// tx.send(2);
+/// assert_eq!(*rx.latest(), 2);
+/// ```
+pub struct Sender<T> {
+    ptr: QueuePtr<T>,
+    slot: usize,
+    /// Writes so far, saturating at 2; below that the writer's slot is uninitialised.
+    writes: usize,
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn new(ptr: QueuePtr<T>) -> Self {
+        Self {
+            ptr,
+            slot: WRITER_SLOT,
+            writes: 0,
+        }
+    }
+
+    /// Publishes a new value, replacing whatever the reader has not seen yet.
+    ///
+    /// Never blocks. The value that is overwritten (if any) is dropped here or
+    /// on a later send.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gil::watch::channel;
+    ///
+    /// let (mut tx, mut rx) = channel("initial");
+    /// tx.send("stale");
+    /// tx.send("fresh");
+    ///
+    /// assert!(rx.changed());
+    /// assert_eq!(*rx.latest(), "fresh");
+    /// assert!(!rx.changed());
+    /// ```
+    pub fn send(&mut self, value: T) {
+        let slot = self.ptr.slot(self.slot);
+        if self.writes < 2 {
+            // SAFETY: the first two writes land in never-initialised slots; see
+            // the layout docs. The slot is ours until we swap it into `middle`.
+            unsafe { slot.write(value) };
+            self.writes += 1;
+            self.ptr.writes().store(self.writes, Ordering::Relaxed);
+        } else {
+            // SAFETY: every slot is initialised by now, and this one is ours.
+            unsafe { *slot.as_ptr() = value };
+        }
+
+        // `Release` publishes the slot we just wrote; `Acquire` makes sure the
+        // reader is done with the slot it handed back before we overwrite it.
+        let previous = self.ptr.middle().swap(self.slot | NEW, Ordering::AcqRel);
+        self.slot = previous & INDEX;
+
+        self.ptr.wake_receiver();
+    }
+}
+
+unsafe impl<T: Send> Send for Sender<T> {}
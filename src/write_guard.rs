@@ -0,0 +1,177 @@
+use core::{mem::MaybeUninit, ptr::NonNull};
+
+/// Trait for queue senders that support batch write operations.
+///
+/// # Safety
+///
+/// Implementations must ensure:
+/// - `write_buffer` and `write_slices` return free slots that stay reserved
+///   for this sender until `commit` is called
+/// - `commit` publishes the first `n` of those slots to the consumer
+///   (atomic store + wake)
+/// - `release` releases any held resources, and is a no-op when no resources
+///   are held
+pub unsafe trait BatchWriter {
+    type Item;
+
+    /// Returns a slice of free contiguous slots at the tail.
+    fn write_buffer(&mut self) -> &mut [MaybeUninit<Self::Item>];
+
+    /// Returns all free slots as two slices: the run up to the end of the ring,
+    /// then the slots that wrap to its start. A single
+    /// [`commit`](BatchWriter::commit) may cover both, first slice first.
+    ///
+    /// The default returns [`write_buffer`](BatchWriter::write_buffer) and an
+    /// empty second slice.
+    #[allow(clippy::type_complexity)]
+    fn write_slices(
+        &mut self,
+    ) -> (
+        &mut [MaybeUninit<Self::Item>],
+        &mut [MaybeUninit<Self::Item>],
+    ) {
+        (self.write_buffer(), &mut [])
+    }
+
+    /// Publish the first `n` slots of the write buffer to the consumer.
+    ///
+    /// # Safety
+    ///
+    /// `n` must not exceed the slots last handed out by
+    /// [`write_buffer`](BatchWriter::write_buffer) or
+    /// [`write_slices`](BatchWriter::write_slices), and the first `n` of them
+    /// must be initialized.
+    unsafe fn commit(&mut self, n: usize);
+
+    /// Release held resources.
+    ///
+    /// Default is a no-op for queue types that don't hold resources.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called after every written item has been committed via
+    /// [`commit`](BatchWriter::commit).
+    unsafe fn release(&mut self) {}
+}
+
+/// RAII guard for batch write operations on a queue sender.
+///
+/// Counts the slots it has initialized and commits exactly that many in a
+/// single atomic store on drop — including when a panic unwinds through it,
+/// so nothing already written is leaked.
+///
+/// Writes fill the free run up to the end of the ring, then carry on into the
+/// slots that wrap to its start.
+pub struct WriteGuard<'a, W: BatchWriter> {
+    sender: &'a mut W,
+    data: NonNull<[MaybeUninit<W::Item>]>,
+    wrapped: NonNull<[MaybeUninit<W::Item>]>,
+    written: usize,
+}
+
+impl<'a, W: BatchWriter> WriteGuard<'a, W> {
+    /// Calls the sender's [`write_slices`](BatchWriter::write_slices) and
+    /// converts the returned slices to [`NonNull`]. This is safe because the
+    /// sender keeps the underlying queue allocation alive.
+    pub(crate) fn new(sender: &'a mut W) -> Self {
+        let (data, wrapped) = sender.write_slices();
+        let (data, wrapped) = (NonNull::from_mut(data), NonNull::from_mut(wrapped));
+        Self {
+            sender,
+            data,
+            wrapped,
+            written: 0,
+        }
+    }
+
+    /// Number of items written so far.
+    #[inline]
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Number of free slots left.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.data.len() + self.wrapped.len() - self.written
+    }
+
+    /// Returns `true` if no free slots remain.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// The free slots of the current run: the rest of the first slice, or of
+    /// the wrapped one once the first is full.
+    #[inline]
+    fn spare(&mut self) -> &mut [MaybeUninit<W::Item>] {
+        let (data, wrapped) = unsafe { (self.data.as_mut(), self.wrapped.as_mut()) };
+        if self.written < data.len() {
+            &mut data[self.written..]
+        } else {
+            &mut wrapped[self.written - data.len()..]
+        }
+    }
+
+    /// Writes `value` into the next free slot.
+    ///
+    /// Returns `Err(value)` if the guard is full.
+    #[inline]
+    pub fn push(&mut self, value: W::Item) -> Result<(), W::Item> {
+        match self.spare().first_mut() {
+            Some(slot) => {
+                slot.write(value);
+                self.written += 1;
+                Ok(())
+            }
+            None => Err(value),
+        }
+    }
+
+    /// Writes items from `iter` until it runs dry or the guard is full.
+    /// Returns how many were written.
+    ///
+    /// Items are only taken from `iter` while there is room for them; pass
+    /// [`by_ref`](Iterator::by_ref) to keep the rest.
+    pub fn extend<I: IntoIterator<Item = W::Item>>(&mut self, iter: I) -> usize {
+        let mut iter = iter.into_iter();
+        let start = self.written;
+        while !self.is_full() {
+            let Some(value) = iter.next() else { break };
+            self.spare()[0].write(value);
+            self.written += 1;
+        }
+        self.written - start
+    }
+
+    /// Copies as much of `values` as fits. Returns how many were copied.
+    pub fn write_slice(&mut self, mut values: &[W::Item]) -> usize
+    where
+        W::Item: Copy,
+    {
+        let start = self.written;
+        // at most twice: once per run
+        while !values.is_empty() && !self.is_full() {
+            let spare = self.spare();
+            let n = values.len().min(spare.len());
+            unsafe {
+                core::ptr::copy_nonoverlapping(values.as_ptr(), spare.as_mut_ptr().cast(), n);
+            }
+            self.written += n;
+            values = &values[n..];
+        }
+        self.written - start
+    }
+}
+
+impl<W: BatchWriter> Drop for WriteGuard<'_, W> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.written > 0 {
+                self.sender.commit(self.written);
+            }
+            self.sender.release();
+        }
+    }
+}
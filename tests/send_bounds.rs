@@ -1,8 +1,15 @@
+#![cfg(feature = "alloc")]
+
 use static_assertions::{assert_impl_all, assert_not_impl_any};
 use std::{cell::RefCell, rc::Rc};
 
 type NonSend = Rc<RefCell<usize>>;
 
+assert_impl_all!(gil::deque::Worker<usize>: Send);
+assert_impl_all!(gil::deque::Stealer<usize>: Send);
+assert_impl_all!(gil::disruptor::Producer<usize>: Send);
+assert_impl_all!(gil::disruptor::Consumer<usize>: Send);
+
 assert_impl_all!(gil::mpmc::Sender<usize>: Send);
 assert_impl_all!(gil::mpmc::Receiver<usize>: Send);
 assert_impl_all!(gil::mpmc::sharded::Sender<usize>: Send);
@@ -12,6 +19,16 @@ assert_impl_all!(gil::mpsc::Sender<usize>: Send);
 assert_impl_all!(gil::mpsc::Receiver<usize>: Send);
 assert_impl_all!(gil::mpsc::sharded::Sender<usize>: Send);
 assert_impl_all!(gil::mpsc::sharded::Receiver<usize>: Send);
+assert_impl_all!(gil::mpsc::overwriting::Sender<usize>: Send);
+assert_impl_all!(gil::mpsc::overwriting::Receiver<usize>: Send);
+assert_impl_all!(gil::mpsc::priority::Sender<usize>: Send);
+assert_impl_all!(gil::mpsc::priority::Receiver<usize>: Send);
+
+assert_impl_all!(gil::oneshot::Sender<usize>: Send);
+assert_impl_all!(gil::oneshot::Receiver<usize>: Send);
+
+assert_impl_all!(gil::rendezvous::Sender<usize>: Send);
+assert_impl_all!(gil::rendezvous::Receiver<usize>: Send);
 
 assert_impl_all!(gil::spmc::Sender<usize>: Send);
 assert_impl_all!(gil::spmc::Receiver<usize>: Send);
@@ -20,7 +37,27 @@ assert_impl_all!(gil::spmc::sharded::Receiver<usize>: Send);
 
 assert_impl_all!(gil::spsc::Sender<usize>: Send);
 assert_impl_all!(gil::spsc::Receiver<usize>: Send);
+assert_impl_all!(gil::spsc::overwriting::Sender<usize>: Send);
+assert_impl_all!(gil::spsc::overwriting::Receiver<usize>: Send);
+assert_impl_all!(gil::spsc::pool::Sender<usize>: Send);
+assert_impl_all!(gil::spsc::pool::Receiver<usize>: Send);
+assert_impl_all!(gil::spsc::pool::Pooled<usize>: Send);
+assert_impl_all!(gil::spsc::bip::Sender: Send);
+assert_impl_all!(gil::spsc::bip::Receiver: Send);
+#[cfg(not(feature = "loom"))]
+assert_impl_all!(gil::spsc::fixed::Sender<'static, usize, 4>: Send);
+#[cfg(not(feature = "loom"))]
+assert_impl_all!(gil::spsc::fixed::Receiver<'static, usize, 4>: Send);
+#[cfg(not(feature = "loom"))]
+assert_impl_all!(gil::spsc::fixed::StaticSpsc<usize, 4>: Send, Sync);
 
+assert_impl_all!(gil::watch::Sender<usize>: Send);
+assert_impl_all!(gil::watch::Receiver<usize>: Send);
+
+#[cfg(all(feature = "std", not(feature = "loom")))]
+assert_impl_all!(gil::delay::Sender<usize>: Send);
+#[cfg(all(feature = "std", not(feature = "loom")))]
+assert_impl_all!(gil::delay::Receiver<usize>: Send);
 #[cfg(feature = "std")]
 assert_impl_all!(gil::mpsc::sharded_parking::Sender<usize>: Send);
 #[cfg(feature = "std")]
@@ -34,6 +71,11 @@ assert_impl_all!(gil::spsc::parking::Sender<usize>: Send);
 #[cfg(feature = "std")]
 assert_impl_all!(gil::spsc::parking::Receiver<usize>: Send);
 
+assert_not_impl_any!(gil::deque::Worker<NonSend>: Send);
+assert_not_impl_any!(gil::deque::Stealer<NonSend>: Send);
+assert_not_impl_any!(gil::disruptor::Producer<NonSend>: Send);
+assert_not_impl_any!(gil::disruptor::Consumer<NonSend>: Send);
+
 assert_not_impl_any!(gil::mpmc::Sender<NonSend>: Send);
 assert_not_impl_any!(gil::mpmc::Receiver<NonSend>: Send);
 assert_not_impl_any!(gil::mpmc::sharded::Sender<NonSend>: Send);
@@ -43,6 +85,16 @@ assert_not_impl_any!(gil::mpsc::Sender<NonSend>: Send);
 assert_not_impl_any!(gil::mpsc::Receiver<NonSend>: Send);
 assert_not_impl_any!(gil::mpsc::sharded::Sender<NonSend>: Send);
 assert_not_impl_any!(gil::mpsc::sharded::Receiver<NonSend>: Send);
+assert_not_impl_any!(gil::mpsc::overwriting::Sender<NonSend>: Send);
+assert_not_impl_any!(gil::mpsc::overwriting::Receiver<NonSend>: Send);
+assert_not_impl_any!(gil::mpsc::priority::Sender<NonSend>: Send);
+assert_not_impl_any!(gil::mpsc::priority::Receiver<NonSend>: Send);
+
+assert_not_impl_any!(gil::oneshot::Sender<NonSend>: Send);
+assert_not_impl_any!(gil::oneshot::Receiver<NonSend>: Send);
+
+assert_not_impl_any!(gil::rendezvous::Sender<NonSend>: Send);
+assert_not_impl_any!(gil::rendezvous::Receiver<NonSend>: Send);
 
 assert_not_impl_any!(gil::spmc::Sender<NonSend>: Send);
 assert_not_impl_any!(gil::spmc::Receiver<NonSend>: Send);
@@ -51,7 +103,25 @@ assert_not_impl_any!(gil::spmc::sharded::Receiver<NonSend>: Send);
 
 assert_not_impl_any!(gil::spsc::Sender<NonSend>: Send);
 assert_not_impl_any!(gil::spsc::Receiver<NonSend>: Send);
+assert_not_impl_any!(gil::spsc::overwriting::Sender<NonSend>: Send);
+assert_not_impl_any!(gil::spsc::overwriting::Receiver<NonSend>: Send);
+assert_not_impl_any!(gil::spsc::pool::Sender<NonSend>: Send);
+assert_not_impl_any!(gil::spsc::pool::Receiver<NonSend>: Send);
+assert_not_impl_any!(gil::spsc::pool::Pooled<NonSend>: Send);
+#[cfg(not(feature = "loom"))]
+assert_not_impl_any!(gil::spsc::fixed::Sender<'static, NonSend, 4>: Send);
+#[cfg(not(feature = "loom"))]
+assert_not_impl_any!(gil::spsc::fixed::Receiver<'static, NonSend, 4>: Send);
+#[cfg(not(feature = "loom"))]
+assert_not_impl_any!(gil::spsc::fixed::StaticSpsc<NonSend, 4>: Sync);
+
+assert_not_impl_any!(gil::watch::Sender<NonSend>: Send);
+assert_not_impl_any!(gil::watch::Receiver<NonSend>: Send);
 
+#[cfg(all(feature = "std", not(feature = "loom")))]
+assert_not_impl_any!(gil::delay::Sender<NonSend>: Send);
+#[cfg(all(feature = "std", not(feature = "loom")))]
+assert_not_impl_any!(gil::delay::Receiver<NonSend>: Send);
 #[cfg(feature = "std")]
 assert_not_impl_any!(gil::mpsc::sharded_parking::Sender<NonSend>: Send);
 #[cfg(feature = "std")]
@@ -64,3 +134,21 @@ assert_not_impl_any!(gil::spmc::sharded_parking::Receiver<NonSend>: Send);
 assert_not_impl_any!(gil::spsc::parking::Sender<NonSend>: Send);
 #[cfg(feature = "std")]
 assert_not_impl_any!(gil::spsc::parking::Receiver<NonSend>: Send);
+
+// The shared-memory queues only carry `Copy` data, so they need a `Copy` non-`Send` type.
+#[cfg(all(feature = "shm", target_os = "linux", not(feature = "loom")))]
+mod shm {
+    use static_assertions::{assert_impl_all, assert_not_impl_any};
+
+    type NonSendCopy = *const usize;
+
+    assert_impl_all!(gil::spsc::shm::Sender<usize>: Send);
+    assert_impl_all!(gil::spsc::shm::Receiver<usize>: Send);
+    assert_impl_all!(gil::mpsc::shm::Sender<usize>: Send);
+    assert_impl_all!(gil::mpsc::shm::Receiver<usize>: Send);
+
+    assert_not_impl_any!(gil::spsc::shm::Sender<NonSendCopy>: Send);
+    assert_not_impl_any!(gil::spsc::shm::Receiver<NonSendCopy>: Send);
+    assert_not_impl_any!(gil::mpsc::shm::Sender<NonSendCopy>: Send);
+    assert_not_impl_any!(gil::mpsc::shm::Receiver<NonSendCopy>: Send);
+}